@@ -0,0 +1,68 @@
+use anyhow::Result;
+use clap::Subcommand;
+use tracing::info;
+use crate::errors::{CliError, CliErrorKind};
+use crate::util::api::{api_base, resolve_app_name, ensure_success, network_error};
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Đặt một hoặc nhiều biến cấu hình (KEY=VALUE)
+    Set { #[arg(long)] app: Option<String>, #[arg(required = true)] pairs: Vec<String> },
+    /// Xoá biến cấu hình
+    Unset { #[arg(long)] app: Option<String>, #[arg(required = true)] keys: Vec<String> },
+    /// Liệt kê cấu hình hiện tại
+    List { #[arg(long)] app: Option<String>, #[arg(long, default_value = "text")] format: String },
+}
+
+fn parse_pairs(pairs: &[String]) -> Result<serde_json::Map<String, serde_json::Value>> {
+    let mut map = serde_json::Map::new();
+    for p in pairs {
+        let Some((k, v)) = p.split_once('=').filter(|(k, _)| !k.is_empty()) else {
+            return Err(CliError::new(CliErrorKind::Usage(format!("expected KEY=VALUE, got '{p}'"))).into());
+        };
+        map.insert(k.to_string(), serde_json::Value::String(v.to_string()));
+    }
+    Ok(map)
+}
+
+fn print_rollout(v: &serde_json::Value) {
+    match v.get("rollout_deployment_id").and_then(|d| d.as_str()) {
+        Some(id) => println!("config updated, rolling out deployment {id}"),
+        None => println!("config updated (no rollout)"),
+    }
+}
+
+pub async fn handle(action: ConfigAction) -> Result<()> {
+    let client = reqwest::Client::new();
+    match action {
+        ConfigAction::Set { app, pairs } => {
+            let values = parse_pairs(&pairs)?;
+            let app = resolve_app_name(app)?;
+            let url = format!("{}/apps/{app}/config", api_base()?);
+            info!(event="config.set", app=%app, keys=values.len());
+            let resp = client.put(&url).json(&serde_json::json!({"values": values})).send().await.map_err(|e| network_error("config set", e))?;
+            let v: serde_json::Value = ensure_success(resp, "config set").await?.json().await.map_err(|e| network_error("config set response", e))?;
+            print_rollout(&v);
+        }
+        ConfigAction::Unset { app, keys } => {
+            let app = resolve_app_name(app)?;
+            let url = format!("{}/apps/{app}/config", api_base()?);
+            info!(event="config.unset", app=%app, keys=keys.len());
+            let resp = client.delete(&url).query(&[("keys", keys.join(","))]).send().await.map_err(|e| network_error("config unset", e))?;
+            let v: serde_json::Value = ensure_success(resp, "config unset").await?.json().await.map_err(|e| network_error("config unset response", e))?;
+            print_rollout(&v);
+        }
+        ConfigAction::List { app, format } => {
+            let app = resolve_app_name(app)?;
+            let url = format!("{}/apps/{app}/config", api_base()?);
+            let resp = client.get(&url).send().await.map_err(|e| network_error("config list", e))?;
+            let v: serde_json::Value = ensure_success(resp, "config list").await?.json().await.map_err(|e| network_error("config list response", e))?;
+            let values = v.get("values").cloned().unwrap_or_else(|| serde_json::json!({}));
+            if format == "json" { println!("{}", serde_json::to_string_pretty(&values)?); }
+            else if let Some(obj) = values.as_object() {
+                for (k, val) in obj { println!("{k}={}", val.as_str().unwrap_or_default()); }
+            }
+        }
+    }
+    Ok(())
+}
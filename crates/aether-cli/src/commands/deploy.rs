@@ -1,5 +1,5 @@
 use anyhow::Result;
-use tracing::{info,warn};
+use tracing::{info,warn,debug};
 use std::path::{Path, PathBuf};
 use sha2::{Sha256,Digest};
 use walkdir::WalkDir;
@@ -24,6 +24,21 @@ impl PackageManager {
     fn binary_name(self) -> &'static str { match self { PackageManager::Npm => "npm", PackageManager::Yarn => "yarn", PackageManager::Pnpm => "pnpm" } }
 }
 
+/// Project type, decides whether dependencies get installed and which ecosystem the SBOM reports
+/// (the control plane maps it to a runtime).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProjectKind { Node, Python, Static }
+
+impl ProjectKind {
+    fn detect(root: &Path) -> Option<Self> {
+        if root.join("package.json").exists() { return Some(ProjectKind::Node); }
+        if root.join("requirements.txt").exists() || root.join("pyproject.toml").exists() { return Some(ProjectKind::Python); }
+        if root.join("index.html").exists() { return Some(ProjectKind::Static); }
+        None
+    }
+    fn ecosystem(self) -> &'static str { match self { ProjectKind::Node => "node", ProjectKind::Python => "python", ProjectKind::Static => "static" } }
+}
+
 #[derive(Debug, Serialize)]
 struct ManifestEntry { path: String, size: u64, sha256: String }
 
@@ -42,16 +57,24 @@ pub struct DeployOptions {
     pub format: Option<String>,
     pub use_legacy_upload: bool,
     pub dev_hot: bool,
+    /// Namespace for the app when it gets registered (config `default_namespace`)
+    pub namespace: Option<String>,
+    /// Follow the created deployment until it is running (error when it fails)
+    pub wait: bool,
 }
 
 pub async fn handle(opts: DeployOptions) -> Result<()> {
-    let DeployOptions { dry_run, pack_only, compression_level, out, no_upload, no_cache, no_sbom, format, use_legacy_upload, dev_hot } = opts;
+    let DeployOptions { dry_run, pack_only, compression_level, out, no_upload, no_cache, no_sbom, format, use_legacy_upload, dev_hot, namespace, wait } = opts;
     let root = Path::new(".");
-    if !is_node_project(root) { return Err(CliError::new(CliErrorKind::Usage("not a NodeJS project (missing package.json)".into())).into()); }
-    if dry_run { info!(event="deploy.dry_run", msg="Would run install + prune + package project"); return Ok(()); }
+    let Some(kind) = ProjectKind::detect(root) else {
+        return Err(CliError::new(CliErrorKind::Usage("unsupported project (need package.json, requirements.txt/pyproject.toml or index.html)".into())).into());
+    };
+    if dry_run { info!(event="deploy.dry_run", project=kind.ecosystem(), msg="Would run install + prune + package project"); return Ok(()); }
 
     // Only detect and use a package manager when we actually need to install/prune.
-    if !pack_only {
+    if kind != ProjectKind::Node {
+        info!(event="deploy.install", project=kind.ecosystem(), status="skipped_non_node");
+    } else if !pack_only {
         let pm = detect_package_manager(root)?; // choose manager
         install_dependencies(root, pm, no_cache)?;
         prune_dependencies(root, pm)?;
@@ -79,7 +102,7 @@ pub async fn handle(opts: DeployOptions) -> Result<()> {
 
     create_artifact(root, &paths, &artifact_name, compression_level)?;
     write_manifest(&artifact_name, &manifest)?;
-    if !no_sbom { generate_sbom(root, &artifact_name, &manifest)?; } else { info!(event="deploy.sbom", status="skipped_no_sbom_flag"); }
+    if !no_sbom { generate_sbom(root, kind, &artifact_name, &manifest)?; } else { info!(event="deploy.sbom", status="skipped_no_sbom_flag"); }
     let size = fs::metadata(&artifact_name).map(|m| m.len()).unwrap_or(0);
     let digest_clone = digest.clone();
     let sig_path = artifact_name.with_file_name(format!("{}.sig", artifact_name.file_name().and_then(|s| s.to_str()).unwrap_or("artifact")));
@@ -97,9 +120,16 @@ pub async fn handle(opts: DeployOptions) -> Result<()> {
 
     if !no_upload {
         if let Ok(base) = std::env::var("AETHER_API_BASE") {
+            ensure_app(root, &base, namespace.as_deref()).await;
             let upload_res = if use_legacy_upload { legacy_upload(&artifact_name, root, &base, &digest, sig_path.exists().then(|| sig_path.clone()), dev_hot).await } else { two_phase_upload(&artifact_name, root, &base, &digest, sig_path.exists().then(|| sig_path.clone()), dev_hot).await };
             match upload_res {
-                Ok(url)=> info!(event="deploy.upload", mode= if use_legacy_upload {"legacy"} else {"two_phase"}, base=%base, artifact=%artifact_name.display(), status="ok", returned_url=%url),
+                Ok((url, deployment_id))=> {
+                    info!(event="deploy.upload", mode= if use_legacy_upload {"legacy"} else {"two_phase"}, base=%base, artifact=%artifact_name.display(), status="ok", returned_url=%url, deployment_id=?deployment_id);
+                    if wait {
+                        let Some(id) = deployment_id else { return Err(CliError::new(CliErrorKind::Runtime("deployment was not created, nothing to wait for".into())).into()); };
+                        wait_for_deployment(&base, &id, format.as_deref()==Some("json")).await?;
+                    }
+                }
                 Err(e)=> { return Err(e); }
             }
         } else { info!(event="deploy.upload", status="skipped_missing_env"); }
@@ -107,7 +137,6 @@ pub async fn handle(opts: DeployOptions) -> Result<()> {
     Ok(())
 }
 
-fn is_node_project(root: &Path) -> bool { root.join("package.json").exists() }
 
 fn detect_package_manager(root:&Path) -> Result<PackageManager> {
     // priority: pnpm, yarn, npm (lockfiles)
@@ -276,18 +305,30 @@ fn load_patterns_file(path:PathBuf)->Vec<Pattern> {
 fn matches_patterns(p:&Path, patterns:&[Pattern])->bool { let rel:&Path = p.strip_prefix(".").unwrap_or(p); let s = rel.to_string_lossy(); patterns.iter().any(|pat| pat.matches(&s)) }
 
 #[derive(Deserialize)]
-struct PackageJson { name: Option<String>, version: Option<String>, #[serde(default)] dependencies: Option<serde_json::Map<String, serde_json::Value>> }
+struct PackageJson { name: Option<String>, version: Option<String>, main: Option<String>, #[serde(default)] scripts: Option<serde_json::Map<String, serde_json::Value>>, #[serde(default)] dependencies: Option<serde_json::Map<String, serde_json::Value>>, #[serde(default)] engines: Option<serde_json::Map<String, serde_json::Value>> }
 
 fn parse_package_json(root:&Path)->Option<PackageJson> {
     let content = fs::read_to_string(root.join("package.json")).ok()?;
     serde_json::from_str(&content).ok()
 }
 
-fn generate_sbom(root:&Path, artifact:&Path, manifest:&Manifest) -> Result<()> {
+/// App name: package.json `name`; projects without package.json use the directory name.
+fn app_name(root:&Path) -> String {
+    if let Some(name) = parse_package_json(root).and_then(|p| p.name) { return name; }
+    if !root.join("package.json").exists() {
+        let dir = std::fs::canonicalize(root).ok().and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_lowercase()));
+        if let Some(dir) = dir.filter(|d| !d.is_empty()) { return dir; }
+    }
+    "default-app".into()
+}
+
+fn generate_sbom(root:&Path, kind: ProjectKind, artifact:&Path, manifest:&Manifest) -> Result<()> {
     let pkg = parse_package_json(root);
     #[derive(Serialize)] struct Dependency<'a> { name: &'a str, spec: String }
     #[derive(Serialize)] struct Sbom<'a> {
         schema: &'a str,
+        ecosystem: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")] engines: Option<serde_json::Map<String, serde_json::Value>>,
         package: Option<String>,
         version: Option<String>,
         total_files: usize,
@@ -303,13 +344,48 @@ fn generate_sbom(root:&Path, artifact:&Path, manifest:&Manifest) -> Result<()> {
     let mut h = Sha256::new();
     for f in &manifest.files { h.update(f.path.as_bytes()); h.update(f.sha256.as_bytes()); }
     let manifest_digest = format!("{:x}", h.finalize());
-    let sbom = Sbom { schema: "aether-sbom-v1", package: pkg.as_ref().and_then(|p| p.name.clone()), version: pkg.as_ref().and_then(|p| p.version.clone()), total_files: manifest.total_files, total_size: manifest.total_size, manifest_digest, files: &manifest.files, dependencies: deps };
+    let sbom = Sbom { schema: "aether-sbom-v1", ecosystem: kind.ecosystem(), engines: pkg.as_ref().and_then(|p| p.engines.clone()), package: pkg.as_ref().and_then(|p| p.name.clone()), version: pkg.as_ref().and_then(|p| p.version.clone()), total_files: manifest.total_files, total_size: manifest.total_size, manifest_digest, files: &manifest.files, dependencies: deps };
     let path = artifact.with_file_name(format!("{}.sbom.json", artifact.file_name().and_then(|s| s.to_str()).unwrap_or("artifact")));
     fs::write(&path, serde_json::to_vec_pretty(&sbom)?)?;
     info!(event="deploy.sbom", path=%path.display(), files=manifest.total_files);
     Ok(())
 }
 
+/// SBOM sent along with the upload completion, without the (potentially large) file list.
+fn sbom_for_upload(artifact:&Path) -> Option<serde_json::Value> {
+    let path = artifact.with_file_name(format!("{}.sbom.json", artifact.file_name().and_then(|s| s.to_str()).unwrap_or("artifact")));
+    let mut v: serde_json::Value = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+    v.as_object_mut()?.remove("files");
+    Some(v)
+}
+
+/// Start metadata sent along with the upload completion: package.json `scripts.start`, `main`, `engines.node`
+/// and the Procfile entries (`web:` plus extra process types such as `worker:`). The control plane derives the
+/// container commands, process Deployments and Node image from it.
+fn artifact_metadata(root:&Path) -> serde_json::Value {
+    let pkg = parse_package_json(root);
+    let processes = parse_procfile(root);
+    serde_json::json!({
+        "start_script": pkg.as_ref().and_then(|p| p.scripts.as_ref()?.get("start")?.as_str().map(str::to_string)),
+        "main": pkg.as_ref().and_then(|p| p.main.clone()),
+        "engines_node": pkg.as_ref().and_then(|p| p.engines.as_ref()?.get("node")?.as_str().map(str::to_string)),
+        "procfile_web": processes.get("web"),
+        "processes": processes,
+    })
+}
+
+/// `<type>: <command>` lines of the Procfile (blank lines and `#` comments ignored).
+fn parse_procfile(root:&Path) -> std::collections::BTreeMap<String, String> {
+    let content = fs::read_to_string(root.join("Procfile")).unwrap_or_default();
+    content.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| l.split_once(':'))
+        .map(|(t, cmd)| (t.trim().to_string(), cmd.trim().to_string()))
+        .filter(|(t, cmd)| !t.is_empty() && !cmd.is_empty())
+        .collect()
+}
+
 fn maybe_sign(artifact:&Path, digest:&str) -> Result<()> {
     if let Ok(hex_key) = std::env::var("AETHER_SIGNING_KEY") {
         use ed25519_dalek::{SigningKey,Signer};
@@ -327,9 +403,82 @@ fn maybe_sign(artifact:&Path, digest:&str) -> Result<()> {
     Ok(())
 }
 
-async fn legacy_upload(artifact:&Path, root:&Path, base:&str, digest:&str, sig: Option<PathBuf>, dev_hot: bool) -> Result<String> {
-    let pkg = parse_package_json(root);
-    let app_name = pkg.as_ref().and_then(|p| p.name.clone()).unwrap_or_else(|| "default-app".into());
+/// Register the app (POST /apps) with the configured namespace; an existing app (409) is left untouched.
+async fn ensure_app(root:&Path, base:&str, namespace: Option<&str>) {
+    let app_name = app_name(root);
+    let mut body = serde_json::json!({"name": app_name});
+    if let Some(ns) = namespace { body["namespace"] = serde_json::json!(ns); }
+    let url = format!("{}/apps", base.trim_end_matches('/'));
+    match reqwest::Client::new().post(&url).json(&body).send().await {
+        Ok(r) if r.status().is_success() => info!(event="deploy.app_registered", app=%app_name, namespace=?namespace),
+        Ok(r) if r.status()==reqwest::StatusCode::CONFLICT => debug!(event="deploy.app_exists", app=%app_name),
+        Ok(r) => warn!(event="deploy.app_register_failed", app=%app_name, status=%r.status()),
+        Err(e) => warn!(event="deploy.app_register_failed", app=%app_name, error=%e),
+    }
+}
+
+/// POST /deployments for an uploaded artifact and return the new deployment id (failures are logged, the upload still counts).
+async fn create_deployment(client:&reqwest::Client, base:&str, app_name:&str, artifact_url:&str, dev_hot: bool) -> Option<String> {
+    let dep_body = serde_json::json!({"app_name": app_name, "artifact_url": artifact_url, "dev_hot": dev_hot});
+    let dep_url = format!("{}/deployments", base.trim_end_matches('/'));
+    match client.post(&dep_url).json(&dep_body).send().await {
+        Ok(r) if r.status().is_success() => r.json::<serde_json::Value>().await.ok()
+            .and_then(|v| v.get("id").and_then(|i| i.as_str()).map(str::to_string)),
+        Ok(r) => { warn!(event="deploy.create_deployment_failed", app=%app_name, status=%r.status()); None }
+        Err(e) => { warn!(event="deploy.create_deployment_failed", app=%app_name, error=%e); None }
+    }
+}
+
+/// Follow `GET /deployments/{id}/watch` (Server-Sent Events) until the deployment is running; a failed deployment,
+/// or a stream ending before either outcome, is a runtime error (exit code 20).
+async fn wait_for_deployment(base:&str, id:&str, json: bool) -> Result<()> {
+    let url = format!("{}/deployments/{id}/watch", base.trim_end_matches('/'));
+    info!(event="deploy.wait", deployment_id=%id);
+    let resp = reqwest::Client::new().get(&url).header(reqwest::header::ACCEPT, "text/event-stream").send().await
+        .map_err(|e| crate::util::api::network_error("deployment watch", e))?;
+    let mut resp = crate::util::api::ensure_success(resp, "deployment watch").await?;
+    let mut buf = String::new();
+    while let Some(chunk) = resp.chunk().await.map_err(|e| crate::util::api::network_error("deployment watch stream", e))? {
+        buf.push_str(&String::from_utf8_lossy(&chunk));
+        while let Some(end) = buf.find("\n\n") {
+            let block: String = buf.drain(..end + 2).collect();
+            let (mut event, mut data) = (String::new(), String::new());
+            for line in block.lines() {
+                if let Some(v) = line.strip_prefix("event:") { event = v.trim().to_string(); }
+                if let Some(v) = line.strip_prefix("data:") { data.push_str(v.trim_start()); }
+            }
+            let Ok(v) = serde_json::from_str::<serde_json::Value>(&data) else { continue; };
+            let field = |k: &str| v.get(k).and_then(|x| x.as_str()).unwrap_or("").to_string();
+            match event.as_str() {
+                "event" => {
+                    let msg = field("message");
+                    if !json { eprintln!("  {}{}", field("event_type"), if msg.is_empty() { String::new() } else { format!(": {msg}") }); }
+                    debug!(event="deploy.wait.event", deployment_id=%id, event_type=%field("event_type"), message=%msg);
+                }
+                "status" => {
+                    let status = field("status");
+                    info!(event="deploy.wait.status", deployment_id=%id, status=%status);
+                    match status.as_str() {
+                        "running" => {
+                            if !json { println!("Deployment {id} running"); }
+                            return Ok(());
+                        }
+                        "failed" => {
+                            let reason = v.get("failure_reason").and_then(|x| x.as_str()).unwrap_or("unknown");
+                            return Err(CliError::new(CliErrorKind::Runtime(format!("deployment {id} failed: {reason}"))).into());
+                        }
+                        other => if !json { eprintln!("Deployment {id} {other}"); },
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Err(CliError::new(CliErrorKind::Runtime(format!("deployment {id} watch ended before the deployment finished"))).into())
+}
+
+async fn legacy_upload(artifact:&Path, root:&Path, base:&str, digest:&str, sig: Option<PathBuf>, dev_hot: bool) -> Result<(String, Option<String>)> {
+    let app_name = app_name(root);
     let client = reqwest::Client::new();
     let meta = fs::metadata(artifact)?; let len = meta.len();
     let file_name = artifact.file_name().unwrap().to_string_lossy().to_string();
@@ -353,17 +502,14 @@ async fn legacy_upload(artifact:&Path, root:&Path, base:&str, digest:&str, sig:
     if !resp.status().is_success() { return Err(CliError::new(CliErrorKind::Runtime(format!("upload failed status {}", resp.status()))).into()); }
     let v: serde_json::Value = resp.json().await.map_err(|e| CliError::with_source(CliErrorKind::Runtime("invalid upload response".into()), e))?;
     let artifact_url = v.get("artifact_url").and_then(|x| x.as_str()).unwrap_or("").to_string();
-    let dep_body = serde_json::json!({"app_name": app_name, "artifact_url": artifact_url, "dev_hot": dev_hot});
-    let dep_url = format!("{}/deployments", base.trim_end_matches('/'));
-    let _ = client.post(&dep_url).json(&dep_body).send().await; // ignore error
-    Ok(artifact_url)
+    let deployment_id = create_deployment(&client, base, &app_name, &artifact_url, dev_hot).await;
+    Ok((artifact_url, deployment_id))
 }
 
 // real_upload removed: migration complete; use two_phase_upload unless --legacy-upload provided.
 
-async fn two_phase_upload(artifact:&Path, root:&Path, base:&str, digest:&str, sig: Option<PathBuf>, dev_hot: bool) -> Result<String> {
-    let pkg = parse_package_json(root);
-    let app_name = pkg.as_ref().and_then(|p| p.name.clone()).unwrap_or_else(|| "default-app".into());
+async fn two_phase_upload(artifact:&Path, root:&Path, base:&str, digest:&str, sig: Option<PathBuf>, dev_hot: bool) -> Result<(String, Option<String>)> {
+    let app_name = app_name(root);
     let client = reqwest::Client::new();
     let presign_url = format!("{}/artifacts/presign", base.trim_end_matches('/'));
     let presign_body = serde_json::json!({"app_name": app_name, "digest": digest});
@@ -414,29 +560,24 @@ async fn two_phase_upload(artifact:&Path, root:&Path, base:&str, digest:&str, si
         let signature_hex = if let Some(sig_path) = sig { fs::read_to_string(sig_path).ok().map(|s| s.trim().to_string()) } else { None };
         let complete_url = format!("{}/artifacts/complete", base.trim_end_matches('/'));
         let idempotency_key = format!("idem-{}", digest);
-        let complete_body = serde_json::json!({"app_name": app_name, "digest": digest, "size_bytes": size_bytes, "signature": signature_hex, "idempotency_key": idempotency_key});
+        let complete_body = serde_json::json!({"app_name": app_name, "digest": digest, "size_bytes": size_bytes, "signature": signature_hex, "idempotency_key": idempotency_key, "sbom": sbom_for_upload(artifact), "metadata": artifact_metadata(root)});
         let comp_resp = client.post(&complete_url).header("X-Aether-Upload-Duration", format!("{:.6}", put_duration)).json(&complete_body).send().await.map_err(|e| CliError::with_source(CliErrorKind::Runtime("complete request failed".into()), e))?;
         if !comp_resp.status().is_success() { return Err(CliError::new(CliErrorKind::Runtime(format!("complete status {}", comp_resp.status()))).into()); }
-        // Optionally create deployment referencing storage key
-    let dep_body = serde_json::json!({"app_name": app_name, "artifact_url": storage_key, "dev_hot": dev_hot});
-        let dep_url = format!("{}/deployments", base.trim_end_matches('/'));
-        let _ = client.post(&dep_url).json(&dep_body).send().await; // ignore error
-        return Ok(storage_key);
+        // Create deployment referencing storage key
+        let deployment_id = create_deployment(&client, base, &app_name, &storage_key, dev_hot).await;
+        return Ok((storage_key, deployment_id));
     }
     // Already stored (method NONE) -> create deployment pointing to storage_key
     if method == "NONE" {
-    let dep_body = serde_json::json!({"app_name": app_name, "artifact_url": storage_key, "dev_hot": dev_hot});
-        let dep_url = format!("{}/deployments", base.trim_end_matches('/'));
-        let _ = client.post(&dep_url).json(&dep_body).send().await; // ignore error
-        return Ok(storage_key);
+        let deployment_id = create_deployment(&client, base, &app_name, &storage_key, dev_hot).await;
+        return Ok((storage_key, deployment_id));
     }
     Err(CliError::new(CliErrorKind::Runtime("unsupported presign method".into())).into())
 }
 
-async fn multipart_upload(artifact:&Path, root:&Path, base:&str, digest:&str, sig: Option<PathBuf>, dev_hot: bool) -> Result<String> {
+async fn multipart_upload(artifact:&Path, root:&Path, base:&str, digest:&str, sig: Option<PathBuf>, dev_hot: bool) -> Result<(String, Option<String>)> {
     let client = reqwest::Client::new();
-    let pkg = parse_package_json(root);
-    let app_name = pkg.as_ref().and_then(|p| p.name.clone()).unwrap_or_else(|| "default-app".into());
+    let app_name = app_name(root);
     // init
     let init_url = format!("{}/artifacts/multipart/init", base.trim_end_matches('/'));
     let init_body = serde_json::json!({"app_name": app_name, "digest": digest});
@@ -484,14 +625,12 @@ async fn multipart_upload(artifact:&Path, root:&Path, base:&str, digest:&str, si
     let complete_url = format!("{}/artifacts/multipart/complete", base.trim_end_matches('/'));
     let idempotency_key = format!("idem-{}", digest);
     let parts_json: Vec<serde_json::Value> = parts.iter().map(|(n,e)| serde_json::json!({"part_number": n, "etag": e})).collect();
-    let complete_body = serde_json::json!({"app_name": app_name, "digest": digest, "upload_id": upload_id, "size_bytes": fs::metadata(artifact).map(|m| m.len()).unwrap_or(0) as i64, "parts": parts_json, "signature": signature_hex, "idempotency_key": idempotency_key});
+    let complete_body = serde_json::json!({"app_name": app_name, "digest": digest, "upload_id": upload_id, "size_bytes": fs::metadata(artifact).map(|m| m.len()).unwrap_or(0) as i64, "parts": parts_json, "signature": signature_hex, "idempotency_key": idempotency_key, "sbom": sbom_for_upload(artifact), "metadata": artifact_metadata(root)});
     let resp = client.post(&complete_url).header("X-Aether-Upload-Duration", format!("{:.6}", duration)).json(&complete_body).send().await.map_err(|e| CliError::with_source(CliErrorKind::Runtime("multipart complete failed".into()), e))?;
     if !resp.status().is_success() { return Err(CliError::new(CliErrorKind::Runtime(format!("multipart complete status {}", resp.status()))).into()); }
     // create deployment referencing stored artifact
-    let dep_body = serde_json::json!({"app_name": app_name, "artifact_url": storage_key, "dev_hot": dev_hot});
-    let dep_url = format!("{}/deployments", base.trim_end_matches('/'));
-    let _ = client.post(&dep_url).json(&dep_body).send().await; // ignore error
-    Ok(storage_key)
+    let deployment_id = create_deployment(&client, base, &app_name, &storage_key, dev_hot).await;
+    Ok((storage_key, deployment_id))
 }
 
 // Benchmark helper (not part of public CLI API) kept always available for benches
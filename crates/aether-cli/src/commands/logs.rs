@@ -1,2 +1,33 @@
-use anyhow::Result;use tracing::info;use std::time::{Duration, SystemTime};
-pub async fn handle(app: Option<String>) -> Result<()> { let appn = app.unwrap_or_else(|| "sample-app".into()); let now = SystemTime::now(); for i in 0..5 { info!(event="logs.line", app=%appn, line=i, ts=?now); } tokio::time::sleep(Duration::from_millis(10)).await; Ok(()) }
+use anyhow::Result;
+use std::io::Write;
+use tracing::info;
+use crate::util::api::{api_base, resolve_app_name, ensure_success, network_error};
+
+#[derive(Debug)]
+pub struct LogsOptions {
+    pub app: Option<String>,
+    pub follow: bool,
+    pub tail: Option<i64>,
+    pub since: Option<String>,
+    pub container: Option<String>,
+}
+
+pub async fn handle(opts: LogsOptions) -> Result<()> {
+    let app = resolve_app_name(opts.app)?;
+    let base = api_base()?;
+    let mut query: Vec<(&str, String)> = Vec::new();
+    if let Some(t) = opts.tail { query.push(("tail", t.to_string())); }
+    if let Some(s) = opts.since { query.push(("since", s)); }
+    if let Some(c) = opts.container { query.push(("container", c)); }
+    if opts.follow { query.push(("follow", "true".into())); }
+    let url = format!("{base}/apps/{app}/logs");
+    info!(event="logs.request", app=%app, follow=opts.follow);
+    let resp = reqwest::Client::new().get(&url).query(&query).send().await.map_err(|e| network_error("logs", e))?;
+    let mut resp = ensure_success(resp, "logs").await?;
+    let mut out = std::io::stdout().lock();
+    while let Some(chunk) = resp.chunk().await.map_err(|e| network_error("logs stream", e))? {
+        out.write_all(&chunk)?;
+        out.flush()?;
+    }
+    Ok(())
+}
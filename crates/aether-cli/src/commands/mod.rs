@@ -3,6 +3,9 @@ use clap::{Parser, Subcommand};
 pub mod login;
 pub mod deploy;
 pub mod logs;
+pub mod rollback;
+pub mod scale;
+pub mod config;
 pub mod list;
 pub mod completions;
 pub mod netfail;
@@ -30,7 +33,7 @@ pub struct Cli {
 pub enum Commands {
     /// Đăng nhập mock và lưu token local
     Login { #[arg(long)] username: Option<String> },
-    /// Build & package ứng dụng NodeJS (npm install + tạo artifact .tar.gz)
+    /// Build & package ứng dụng (NodeJS: npm install; Python/static: đóng gói trực tiếp) + tạo artifact .tar.gz
     Deploy { 
         #[arg(long, default_value_t = false)] dry_run: bool,
         /// Chỉ đóng gói, bỏ qua bước npm install (hữu ích cho CI không có Node)
@@ -51,9 +54,37 @@ pub enum Commands {
     #[arg(long, default_value_t = false)] legacy_upload: bool,
     /// Bật chế độ dev hot reload (sidecar fetch loop)
     #[arg(long, default_value_t = false)] dev_hot: bool,
+        /// Chờ deployment chạy xong (stream trạng thái từ Control Plane); thoát mã lỗi khác 0 nếu thất bại
+        #[arg(long, default_value_t = false)] wait: bool,
     },
-    /// Mock hiển thị log gần nhất
-    Logs { #[arg(long)] app: Option<String> },
+    /// Xem log ứng dụng từ Control Plane (mặc định lấy tên app từ package.json)
+    Logs {
+        #[arg(long)] app: Option<String>,
+        /// Tiếp tục stream log mới
+        #[arg(long, short = 'f', default_value_t = false)] follow: bool,
+        /// Số dòng cuối của mỗi pod
+        #[arg(long)] tail: Option<i64>,
+        /// Chỉ lấy log trong khoảng thời gian gần đây (vd: 300, 10m, 2h)
+        #[arg(long)] since: Option<String>,
+        /// Tên container (mặc định: app)
+        #[arg(long)] container: Option<String>,
+    },
+    /// Rollback ứng dụng về bản phát hành đang chạy trước đó
+    Rollback {
+        #[arg(long)] app: Option<String>,
+        /// Deployment id hoặc digest sha256 cần quay về (mặc định: bản running trước đó)
+        #[arg(long)] to: Option<String>,
+    },
+    /// Đặt số replica của ứng dụng (giới hạn bởi max_replicas của app)
+    Scale {
+        app: String,
+        /// Số replica mong muốn (>= 0)
+        #[arg(allow_negative_numbers = true)] replicas: i32,
+        /// Loại process trong Procfile (mặc định: web)
+        #[arg(long)] process: Option<String>,
+    },
+    /// Quản lý biến môi trường (config) của ứng dụng
+    Config { #[command(subcommand)] action: config::ConfigAction },
     /// Mock liệt kê ứng dụng
     List {},
     /// Sinh shell completions (ẩn)
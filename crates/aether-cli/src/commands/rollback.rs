@@ -0,0 +1,35 @@
+use anyhow::Result;
+use tracing::info;
+use crate::errors::{CliError, CliErrorKind};
+use crate::util::api::{api_base, resolve_app_name, ensure_success, network_error};
+
+#[derive(Debug)]
+pub struct RollbackOptions {
+    pub app: Option<String>,
+    /// Deployment id (UUID) or artifact digest (64 hex); None = previous running release.
+    pub to: Option<String>,
+}
+
+fn target_body(to: Option<&str>) -> Result<serde_json::Value> {
+    let Some(t) = to.map(str::trim).filter(|t| !t.is_empty()) else { return Ok(serde_json::json!({})); };
+    if let Ok(id) = uuid::Uuid::parse_str(t) { return Ok(serde_json::json!({"deployment_id": id})); }
+    if t.len()==64 && t.chars().all(|c| c.is_ascii_hexdigit()) { return Ok(serde_json::json!({"digest": t.to_lowercase()})); }
+    Err(CliError::new(CliErrorKind::Usage(format!("--to must be a deployment id or sha256 digest, got '{t}'"))).into())
+}
+
+pub async fn handle(opts: RollbackOptions) -> Result<()> {
+    let app = resolve_app_name(opts.app)?;
+    let body = target_body(opts.to.as_deref())?;
+    let base = api_base()?;
+    let url = format!("{base}/apps/{app}/rollback");
+    info!(event="rollback.request", app=%app, target=?opts.to);
+    let resp = reqwest::Client::new().post(&url).json(&body).send().await.map_err(|e| network_error("rollback", e))?;
+    let resp = ensure_success(resp, "rollback").await?;
+    let v: serde_json::Value = resp.json().await.map_err(|e| network_error("rollback response", e))?;
+    let digest = v.get("digest").and_then(|d| d.as_str()).unwrap_or("-");
+    println!("rollback scheduled: deployment {} (from {}, digest {})",
+        v.get("id").and_then(|x| x.as_str()).unwrap_or("?"),
+        v.get("rolled_back_to").and_then(|x| x.as_str()).unwrap_or("?"),
+        digest);
+    Ok(())
+}
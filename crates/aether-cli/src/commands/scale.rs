@@ -0,0 +1,25 @@
+use anyhow::Result;
+use tracing::info;
+use crate::errors::{CliError, CliErrorKind};
+use crate::util::api::{api_base, ensure_success, network_error};
+
+pub async fn handle(app: String, replicas: i32, process: Option<String>) -> Result<()> {
+    if replicas < 0 { return Err(CliError::new(CliErrorKind::Usage(format!("replicas must be >= 0, got {replicas}"))).into()); }
+    let base = api_base()?;
+    let url = format!("{base}/apps/{app}/scale");
+    info!(event="scale.request", app=%app, replicas, process=?process);
+    let mut body = serde_json::json!({"replicas": replicas});
+    if let Some(p) = &process { body["process"] = serde_json::json!(p); }
+    let resp = reqwest::Client::new().put(&url).json(&body).send().await.map_err(|e| network_error("scale", e))?;
+    let resp = ensure_success(resp, "scale").await?;
+    let v: serde_json::Value = resp.json().await.map_err(|e| network_error("scale response", e))?;
+    let live = if v.get("live_patched").and_then(|x| x.as_bool()).unwrap_or(false) { "applied to live deployment" } else { "applies on next rollout" };
+    let target = match v.get("process").and_then(|p| p.as_str()).or(process.as_deref()) {
+        Some(p) if p != "web" => format!("{app} {p}"),
+        _ => app.clone(),
+    };
+    println!("{target} scaled to {} replicas (max {}, {live})",
+        v.get("replicas").and_then(|x| x.as_i64()).unwrap_or(replicas as i64),
+        v.get("max_replicas").and_then(|x| x.as_i64()).map(|m| m.to_string()).unwrap_or_else(|| "-".into()));
+    Ok(())
+}
@@ -10,7 +10,6 @@ pub struct FileConfig {
 }
 
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub struct EffectiveConfig {
     pub default_namespace: Option<String>,
 }
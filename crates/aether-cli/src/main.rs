@@ -27,13 +27,16 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn dispatch(cli: Cli, _cfg: EffectiveConfig) -> Result<()> {
+async fn dispatch(cli: Cli, cfg: EffectiveConfig) -> Result<()> {
     use std::time::Instant;
     let start = Instant::now();
     let result = match cli.command {
         Commands::Login { username } => { let _span = info_span!("cmd.login").entered(); commands::login::handle(username).await }
-    Commands::Deploy { dry_run, pack_only, compression_level, out, no_upload, no_cache, no_sbom, format, legacy_upload, dev_hot } => { let _span = info_span!("cmd.deploy", dry_run, pack_only, compression_level, out=?out, no_upload, no_cache, no_sbom, format=?format, legacy_upload, dev_hot); commands::deploy::handle(commands::deploy::DeployOptions { dry_run, pack_only, compression_level, out, no_upload, no_cache, no_sbom, format, use_legacy_upload: legacy_upload, dev_hot }).await }
-        Commands::Logs { app } => { let _span = info_span!("cmd.logs"); commands::logs::handle(app).await }
+    Commands::Deploy { dry_run, pack_only, compression_level, out, no_upload, no_cache, no_sbom, format, legacy_upload, dev_hot, wait } => { let _span = info_span!("cmd.deploy", dry_run, pack_only, compression_level, out=?out, no_upload, no_cache, no_sbom, format=?format, legacy_upload, dev_hot, wait); commands::deploy::handle(commands::deploy::DeployOptions { dry_run, pack_only, compression_level, out, no_upload, no_cache, no_sbom, format, use_legacy_upload: legacy_upload, dev_hot, namespace: cfg.default_namespace.clone(), wait }).await }
+        Commands::Logs { app, follow, tail, since, container } => { let _span = info_span!("cmd.logs", follow); commands::logs::handle(commands::logs::LogsOptions { app, follow, tail, since, container }).await }
+        Commands::Rollback { app, to } => { let _span = info_span!("cmd.rollback"); commands::rollback::handle(commands::rollback::RollbackOptions { app, to }).await }
+        Commands::Scale { app, replicas, process } => { let _span = info_span!("cmd.scale"); commands::scale::handle(app, replicas, process).await }
+        Commands::Config { action } => { let _span = info_span!("cmd.config"); commands::config::handle(action).await }
         Commands::List {} => { let _span = info_span!("cmd.list"); commands::list::handle().await }
         Commands::Completions { shell } => { let _span = info_span!("cmd.completions"); commands::completions::handle(shell) }
         Commands::Netfail {} => { let _span = info_span!("cmd.netfail"); commands::netfail::handle().await }
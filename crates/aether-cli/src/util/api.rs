@@ -0,0 +1,36 @@
+use anyhow::Result;
+use std::path::Path;
+use crate::errors::{CliError, CliErrorKind};
+
+/// Control Plane base URL from `AETHER_API_BASE` (trailing slash removed).
+pub fn api_base() -> Result<String> {
+    match std::env::var("AETHER_API_BASE") {
+        Ok(b) if !b.trim().is_empty() => Ok(b.trim().trim_end_matches('/').to_string()),
+        _ => Err(CliError::new(CliErrorKind::Config("AETHER_API_BASE not set".into())).into()),
+    }
+}
+
+/// App name from the explicit flag, falling back to `name` in ./package.json.
+pub fn resolve_app_name(app: Option<String>) -> Result<String> {
+    if let Some(a) = app.filter(|a| !a.trim().is_empty()) { return Ok(a); }
+    let from_pkg = std::fs::read_to_string(Path::new("package.json")).ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|v| v.get("name").and_then(|n| n.as_str()).map(str::to_string));
+    from_pkg.ok_or_else(|| CliError::new(CliErrorKind::Usage("--app required (no package.json name found)".into())).into())
+}
+
+/// Pass through successful responses; otherwise surface the API error message as a runtime error.
+pub async fn ensure_success(resp: reqwest::Response, what: &str) -> Result<reqwest::Response> {
+    if resp.status().is_success() { return Ok(resp); }
+    let status = resp.status();
+    let body = resp.text().await.unwrap_or_default();
+    let msg = serde_json::from_str::<serde_json::Value>(&body).ok()
+        .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(str::to_string))
+        .unwrap_or(body);
+    Err(CliError::new(CliErrorKind::Runtime(format!("{what} failed status {status}: {msg}"))).into())
+}
+
+/// Wrap a transport failure as a network error (exit code 40).
+pub fn network_error(what: &str, e: reqwest::Error) -> anyhow::Error {
+    CliError::with_source(CliErrorKind::Network(format!("{what} request failed")), e).into()
+}
@@ -1 +1,2 @@
-pub mod time; // duration formatting utilities
\ No newline at end of file
+pub mod time; // duration formatting utilities
+pub mod api; // control plane HTTP helpers
@@ -41,9 +41,11 @@ fn deploy_dry_run() {
 }
 
 #[test]
-fn logs_mock() {
+fn logs_requires_api_base() {
     let tmp = tempfile::tempdir().unwrap();
-    bin().env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path()).args(["logs"]).assert().success();
+    let assert = bin().env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path()).env_remove("AETHER_API_BASE")
+        .args(["logs","--app","demo"]).assert().failure();
+    assert_eq!(assert.get_output().status.code(), Some(10), "missing AETHER_API_BASE is a config error");
 }
 
 #[test]
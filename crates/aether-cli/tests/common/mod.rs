@@ -0,0 +1,23 @@
+//! Helpers shared by the CLI integration tests that talk to a mock control plane.
+#![allow(dead_code)] // each test binary uses a subset
+
+use assert_cmd::Command;
+
+pub fn bin() -> Command { Command::cargo_bin("aether-cli").unwrap() }
+
+/// Serve a mock control plane from its own runtime thread so the CLI binary can run synchronously; returns its
+/// base URL. `make_app` receives the bound address (for mocks that hand out URLs pointing back at themselves).
+pub fn spawn_server(make_app: impl FnOnce(std::net::SocketAddr) -> axum::Router + Send + 'static) -> String {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async move {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let app = make_app(addr);
+            tx.send(addr).unwrap();
+            axum::serve(listener, app).await.unwrap();
+        });
+    });
+    format!("http://{}", rx.recv().unwrap())
+}
@@ -0,0 +1,54 @@
+use axum::{Router, routing::get, extract::{Path, Query, State}, Json};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::{bin, spawn_server};
+
+type Store = Arc<Mutex<BTreeMap<String, String>>>;
+
+// In-memory config API mirroring GET/PUT/DELETE /apps/{app}/config.
+fn mock_server(store: Store) -> String {
+    spawn_server(move |_| {
+        Router::new().route("/apps/:app/config",
+            get(|State(s): State<Store>, Path(_app): Path<String>| async move { Json(serde_json::json!({"values": *s.lock().unwrap()})) })
+            .put(|State(s): State<Store>, Path(_app): Path<String>, Json(b): Json<serde_json::Value>| async move {
+                let mut m = s.lock().unwrap();
+                for (k, v) in b["values"].as_object().unwrap() { m.insert(k.clone(), v.as_str().unwrap().to_string()); }
+                Json(serde_json::json!({"values": *m, "rollout_deployment_id": "dep-1"}))
+            })
+            .delete(|State(s): State<Store>, Path(_app): Path<String>, Query(q): Query<HashMap<String,String>>| async move {
+                let mut m = s.lock().unwrap();
+                for k in q["keys"].split(',') { m.remove(k); }
+                Json(serde_json::json!({"values": *m, "rollout_deployment_id": null}))
+            })).with_state(store)
+    })
+}
+
+fn run(base: &str, args: &[&str]) -> assert_cmd::assert::Assert {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut full = vec!["--log-level","error","config"];
+    full.extend_from_slice(args);
+    bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", base)
+        .args(full).assert()
+}
+
+#[test]
+fn config_set_list_unset_roundtrip() {
+    let store: Store = Arc::new(Mutex::new(BTreeMap::new()));
+    let base = mock_server(store.clone());
+    let out = run(&base, &["set","--app","demo","DATABASE_URL=postgres://db/x?a=b","LOG_LEVEL=debug"]).success();
+    assert!(String::from_utf8_lossy(&out.get_output().stdout).contains("rolling out deployment dep-1"));
+    assert_eq!(store.lock().unwrap().get("DATABASE_URL").map(String::as_str), Some("postgres://db/x?a=b"));
+    run(&base, &["unset","--app","demo","LOG_LEVEL"]).success();
+    let out = run(&base, &["list","--app","demo"]).success();
+    let stdout = String::from_utf8_lossy(&out.get_output().stdout).to_string();
+    assert_eq!(stdout.trim(), "DATABASE_URL=postgres://db/x?a=b");
+}
+
+#[test]
+fn config_set_rejects_malformed_pair() {
+    run("http://127.0.0.1:9", &["set","--app","demo","NOVALUE"]).code(2);
+}
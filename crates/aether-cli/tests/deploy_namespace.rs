@@ -0,0 +1,38 @@
+use axum::{Router, routing::post, Json, extract::State};
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::{bin, spawn_server};
+
+type Seen = Arc<Mutex<Vec<serde_json::Value>>>;
+
+// Mock control plane that only records app registrations; upload endpoints are absent (404).
+fn mock_server(seen: Seen) -> String {
+    spawn_server(move |_| {
+        Router::new().route("/apps", post(|State(seen): State<Seen>, Json(b): Json<serde_json::Value>| async move {
+            seen.lock().unwrap().push(b);
+            (axum::http::StatusCode::CREATED, Json(serde_json::json!({})))
+        })).with_state(seen)
+    })
+}
+
+#[test]
+fn deploy_registers_app_in_configured_namespace() {
+    let seen: Seen = Arc::new(Mutex::new(Vec::new()));
+    let base = mock_server(seen.clone());
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("aether")).unwrap();
+    std::fs::write(tmp.path().join("aether/config.toml"), "default_namespace = \"team-a\"\n").unwrap();
+    std::fs::write(tmp.path().join("package.json"), r#"{"name":"nsapp","version":"0.1.0"}"#).unwrap();
+    std::fs::write(tmp.path().join("index.js"), "console.log('hi')").unwrap();
+    // the upload itself fails against this mock; only the registration is of interest
+    let _ = bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","deploy","--pack-only","--no-sbom"])
+        .assert();
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1, "expected one POST /apps");
+    assert_eq!(seen[0]["name"], "nsapp");
+    assert_eq!(seen[0]["namespace"], "team-a");
+}
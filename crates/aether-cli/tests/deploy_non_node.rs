@@ -1,6 +1,5 @@
-use assert_cmd::Command;
-
-fn bin() -> Command { Command::cargo_bin("aether-cli").unwrap() }
+mod common;
+use common::bin;
 
 #[test]
 fn deploy_in_non_node_project_fails_usage() {
@@ -31,3 +30,38 @@ fn deploy_pack_only_creates_artifact() {
     let mut found=false; for e in std::fs::read_dir(tmp.path()).unwrap() { let p=e.unwrap().path(); if p.file_name().unwrap().to_string_lossy().starts_with("app-") { found=true; break; } }
     assert!(found, "expected app-*.tar.gz artifact");
 }
+
+fn pack_json(dir: &std::path::Path) -> serde_json::Value {
+    let out = bin().current_dir(dir)
+        .env("XDG_CACHE_HOME", dir)
+        .env("XDG_CONFIG_HOME", dir)
+        .args(["--log-level","error","deploy","--pack-only","--format","json"])
+        .assert().success().get_output().stdout.clone();
+    serde_json::from_slice(&out).unwrap()
+}
+
+#[test]
+fn deploy_python_and_static_projects_pack() {
+    let py = tempfile::tempdir().unwrap();
+    std::fs::write(py.path().join("requirements.txt"), "flask==3.0.0\n").unwrap();
+    std::fs::write(py.path().join("app.py"), "print('hi')").unwrap();
+    let v = pack_json(py.path());
+    let sbom: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(py.path().join(v["sbom"].as_str().unwrap())).unwrap()).unwrap();
+    assert_eq!(sbom["ecosystem"], "python");
+
+    let site = tempfile::tempdir().unwrap();
+    std::fs::write(site.path().join("index.html"), "<h1>hi</h1>").unwrap();
+    let v = pack_json(site.path());
+    let sbom: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(site.path().join(v["sbom"].as_str().unwrap())).unwrap()).unwrap();
+    assert_eq!(sbom["ecosystem"], "static");
+}
+
+#[test]
+fn node_sbom_records_engines() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("package.json"), r#"{"name":"eng","engines":{"node":">=22"}}"#).unwrap();
+    let v = pack_json(tmp.path());
+    let sbom: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(tmp.path().join(v["sbom"].as_str().unwrap())).unwrap()).unwrap();
+    assert_eq!(sbom["ecosystem"], "node");
+    assert_eq!(sbom["engines"]["node"], ">=22");
+}
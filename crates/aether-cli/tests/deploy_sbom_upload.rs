@@ -0,0 +1,69 @@
+use axum::{Router, routing::{post, put}, Json, extract::State};
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::{bin, spawn_server};
+
+type Seen = Arc<Mutex<Vec<serde_json::Value>>>;
+
+// Mock two-phase upload: presign points the PUT back at this server; completions are recorded.
+fn mock_server(seen: Seen) -> String {
+    spawn_server(move |addr| {
+        Router::new()
+            .route("/apps", post(|| async { (axum::http::StatusCode::CREATED, Json(serde_json::json!({}))) }))
+            .route("/artifacts/presign", post(move || async move {
+                Json(serde_json::json!({"upload_url": format!("http://{addr}/put"), "storage_key": "k", "method": "PUT", "headers": {}}))
+            }))
+            .route("/put", put(|| async { "" }))
+            .route("/artifacts/complete", post(|State(seen): State<Seen>, Json(b): Json<serde_json::Value>| async move {
+                seen.lock().unwrap().push(b);
+                Json(serde_json::json!({"artifact_id":"a","digest":"d","duplicate":false,"verified":false,"storage_key":"k","status":"stored","idempotency_key":null}))
+            }))
+            .route("/deployments", post(|| async { (axum::http::StatusCode::CREATED, Json(serde_json::json!({}))) }))
+            .with_state(seen)
+    })
+}
+
+#[test]
+fn upload_completion_carries_sbom_without_files() {
+    let seen: Seen = Arc::new(Mutex::new(Vec::new()));
+    let base = mock_server(seen.clone());
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("pyproject.toml"), "[project]\nname='svc'\n").unwrap();
+    std::fs::write(tmp.path().join("app.py"), "print('hi')").unwrap();
+    bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","deploy"])
+        .assert().success();
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1, "expected one completion");
+    let sbom = &seen[0]["sbom"];
+    assert_eq!(sbom["ecosystem"], "python");
+    assert!(sbom.get("files").is_none(), "file list is not uploaded");
+    // no package.json: the directory name is the app name
+    let dir = tmp.path().canonicalize().unwrap().file_name().unwrap().to_string_lossy().to_lowercase();
+    assert_eq!(seen[0]["app_name"], dir);
+}
+
+#[test]
+fn upload_completion_carries_start_metadata() {
+    let seen: Seen = Arc::new(Mutex::new(Vec::new()));
+    let base = mock_server(seen.clone());
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("package.json"), r#"{"name":"web","main":"dist/server.js","scripts":{"start":"node dist/server.js --port $PORT"},"engines":{"node":"22.x"}}"#).unwrap();
+    std::fs::write(tmp.path().join("Procfile"), "# processes\nworker: node jobs.js\nweb: node dist/server.js\nclock: node clock.js --every 1m\n").unwrap();
+    bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","deploy","--pack-only"])
+        .assert().success();
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1, "expected one completion");
+    let meta = &seen[0]["metadata"];
+    assert_eq!(meta["start_script"], "node dist/server.js --port $PORT");
+    assert_eq!(meta["main"], "dist/server.js");
+    assert_eq!(meta["engines_node"], "22.x");
+    assert_eq!(meta["procfile_web"], "node dist/server.js");
+    assert_eq!(meta["processes"], serde_json::json!({"web": "node dist/server.js", "worker": "node jobs.js", "clock": "node clock.js --every 1m"}));
+}
@@ -0,0 +1,49 @@
+use axum::{Router, routing::{get, post}, Json, extract::Path};
+
+mod common;
+use common::{bin, spawn_server};
+
+// Mock control plane: the artifact is already stored, the deployment id is the app name and the watch stream
+// fails deployments of apps named `bad*`.
+fn mock_server() -> String {
+    spawn_server(|_| {
+        Router::new()
+            .route("/apps", post(|| async { (axum::http::StatusCode::CREATED, Json(serde_json::json!({}))) }))
+            .route("/artifacts/presign", post(|| async { Json(serde_json::json!({"method":"NONE","storage_key":"k"})) }))
+            .route("/deployments", post(|Json(b): Json<serde_json::Value>| async move {
+                (axum::http::StatusCode::CREATED, Json(serde_json::json!({"id": b["app_name"], "status":"pending"})))
+            }))
+            .route("/deployments/:id/watch", get(|Path(id): Path<String>| async move {
+                let last = if id.starts_with("bad") { r#"{"kind":"status","status":"failed","failure_reason":"crash"}"# } else { r#"{"kind":"status","status":"running"}"# };
+                let body = format!("event: status\ndata: {{\"kind\":\"status\",\"status\":\"pending\"}}\n\n:keep-alive\n\nevent: event\nid: 1\ndata: {{\"kind\":\"event\",\"event_type\":\"rollout\",\"message\":null}}\n\nevent: status\ndata: {last}\n\n");
+                ([(axum::http::header::CONTENT_TYPE, "text/event-stream")], body)
+            }))
+    })
+}
+
+fn deploy(base: &str, app: &str) -> assert_cmd::assert::Assert {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("package.json"), format!(r#"{{"name":"{app}","version":"0.1.0"}}"#)).unwrap();
+    std::fs::write(tmp.path().join("index.js"), "console.log('hi')").unwrap();
+    bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", base)
+        .args(["--log-level","error","deploy","--pack-only","--no-sbom","--wait"])
+        .assert()
+}
+
+#[test]
+fn deploy_wait_succeeds_when_running() {
+    let base = mock_server();
+    let assert = deploy(&base, "goodapp").success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("Deployment goodapp running"), "unexpected output: {stdout}");
+}
+
+#[test]
+fn deploy_wait_fails_when_deployment_fails() {
+    let base = mock_server();
+    let assert = deploy(&base, "badapp").code(20);
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("deployment badapp failed: crash"), "unexpected stderr: {stderr}");
+}
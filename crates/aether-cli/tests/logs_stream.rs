@@ -0,0 +1,44 @@
+use axum::{Router, routing::get, extract::{Path, Query}};
+use std::collections::HashMap;
+
+mod common;
+use common::{bin, spawn_server};
+
+// Mock log endpoint: `tail` lines echoing the app name and the follow flag.
+fn mock_server() -> String {
+    spawn_server(|_| {
+        Router::new().route("/apps/:app/logs", get(|Path(app): Path<String>, Query(q): Query<HashMap<String,String>>| async move {
+            let tail: usize = q.get("tail").and_then(|t| t.parse().ok()).unwrap_or(2);
+            let follow = q.get("follow").cloned().unwrap_or_default();
+            (0..tail).map(|i| format!("{app} line {i} follow={follow}\n")).collect::<String>()
+        }))
+    })
+}
+
+#[test]
+fn logs_streams_from_control_plane() {
+    let base = mock_server();
+    let tmp = tempfile::tempdir().unwrap();
+    let assert = bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","logs","--app","demo","--tail","3","--follow"])
+        .assert().success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert_eq!(stdout.lines().count(), 3);
+    assert!(stdout.contains("demo line 2 follow=true"), "unexpected output: {stdout}");
+}
+
+#[test]
+fn logs_app_name_from_package_json() {
+    let base = mock_server();
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("package.json"), r#"{"name":"pkgapp"}"#).unwrap();
+    let assert = bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","logs"])
+        .assert().success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.starts_with("pkgapp line 0"), "unexpected output: {stdout}");
+}
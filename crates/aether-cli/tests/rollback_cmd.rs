@@ -0,0 +1,38 @@
+use axum::{Router, routing::post, extract::Path, Json};
+
+mod common;
+use common::{bin, spawn_server};
+
+// Mock control plane echoing the requested rollback target back in the response.
+fn mock_server() -> String {
+    spawn_server(|_| {
+        Router::new().route("/apps/:app/rollback", post(|Path(app): Path<String>, Json(b): Json<serde_json::Value>| async move {
+            let digest = b.get("digest").and_then(|d| d.as_str()).unwrap_or("prev").to_string();
+            Json(serde_json::json!({"id": format!("{app}-new"), "status":"pending", "rolled_back_to":"src-id", "digest": digest}))
+        }))
+    })
+}
+
+#[test]
+fn rollback_posts_target_digest() {
+    let base = mock_server();
+    let tmp = tempfile::tempdir().unwrap();
+    let digest = "ab".repeat(32);
+    let assert = bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","rollback","--app","demo","--to",&digest])
+        .assert().success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("deployment demo-new") && stdout.contains(&digest), "unexpected output: {stdout}");
+}
+
+#[test]
+fn rollback_rejects_invalid_target() {
+    let tmp = tempfile::tempdir().unwrap();
+    bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", "http://127.0.0.1:9")
+        .args(["--log-level","error","rollback","--app","demo","--to","v1"])
+        .assert().code(2);
+}
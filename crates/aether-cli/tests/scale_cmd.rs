@@ -0,0 +1,58 @@
+use axum::{Router, routing::put, extract::Path, http::StatusCode, Json};
+
+mod common;
+use common::{bin, spawn_server};
+
+// Mock control plane enforcing a max of 5 replicas.
+fn mock_server() -> String {
+    spawn_server(|_| {
+        Router::new().route("/apps/:app/scale", put(|Path(_app): Path<String>, Json(b): Json<serde_json::Value>| async move {
+            let n = b["replicas"].as_i64().unwrap();
+            if n > 5 { return (StatusCode::BAD_REQUEST, Json(serde_json::json!({"code":"bad_request","message":"replicas exceeds max_replicas 5"}))); }
+            let process = b.get("process").cloned().unwrap_or(serde_json::json!("web"));
+            (StatusCode::OK, Json(serde_json::json!({"process": process, "replicas": n, "max_replicas": 5, "live_patched": true})))
+        }))
+    })
+}
+
+#[test]
+fn scale_puts_replica_count() {
+    let base = mock_server();
+    let tmp = tempfile::tempdir().unwrap();
+    let assert = bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","scale","demo","3"])
+        .assert().success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("demo scaled to 3 replicas") && stdout.contains("max 5"), "unexpected output: {stdout}");
+}
+
+#[test]
+fn scale_process_type() {
+    let base = mock_server();
+    let tmp = tempfile::tempdir().unwrap();
+    let assert = bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","scale","demo","2","--process","worker"])
+        .assert().success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("demo worker scaled to 2 replicas"), "unexpected output: {stdout}");
+}
+
+#[test]
+fn scale_above_max_fails() {
+    let base = mock_server();
+    let tmp = tempfile::tempdir().unwrap();
+    bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","scale","demo","9"])
+        .assert().failure();
+    bin().current_dir(tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path()).env("XDG_CACHE_HOME", tmp.path())
+        .env("AETHER_API_BASE", &base)
+        .args(["--log-level","error","scale","demo","-1"])
+        .assert().code(2);
+}
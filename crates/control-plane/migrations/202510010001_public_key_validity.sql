@@ -0,0 +1,3 @@
+-- Migration: optional validity window for app signing keys (rotation / scheduled expiry)
+ALTER TABLE public_keys ADD COLUMN IF NOT EXISTS not_before TIMESTAMPTZ NULL;
+ALTER TABLE public_keys ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ NULL;
@@ -0,0 +1,3 @@
+-- Migration: per-application Kubernetes namespace
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS namespace TEXT NOT NULL DEFAULT 'default';
+CREATE INDEX IF NOT EXISTS idx_applications_namespace ON applications(namespace);
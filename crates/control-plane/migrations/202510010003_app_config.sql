@@ -0,0 +1,8 @@
+-- Migration: per-application config rendered into the container env
+CREATE TABLE IF NOT EXISTS app_config (
+  app_id UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+  key TEXT NOT NULL,
+  value TEXT NOT NULL,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (app_id, key)
+);
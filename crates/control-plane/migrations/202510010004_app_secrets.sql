@@ -0,0 +1,16 @@
+-- Migration: envelope-encrypted app secrets (per-app data key wrapped by a master key)
+CREATE TABLE IF NOT EXISTS app_data_keys (
+  app_id UUID PRIMARY KEY REFERENCES applications(id) ON DELETE CASCADE,
+  wrapped_key BYTEA NOT NULL,
+  master_key_id TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  rotated_at TIMESTAMPTZ NULL
+);
+
+CREATE TABLE IF NOT EXISTS app_secrets (
+  app_id UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  ciphertext BYTEA NOT NULL,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (app_id, name)
+);
@@ -0,0 +1,3 @@
+-- Migration: persistent per-app replica count and optional per-app maximum
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS replicas INT NOT NULL DEFAULT 1 CHECK (replicas >= 0);
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS max_replicas INT NULL CHECK (max_replicas >= 0);
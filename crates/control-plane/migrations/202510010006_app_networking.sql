@@ -0,0 +1,12 @@
+-- Migration: per-app container port (NULL = runtime default) and custom domains routed through the app Ingress
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS port INT NULL CHECK (port BETWEEN 1 AND 65535);
+
+CREATE TABLE IF NOT EXISTS app_domains (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    app_id UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    hostname TEXT NOT NULL,
+    path TEXT NOT NULL DEFAULT '/',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (hostname, path)
+);
+CREATE INDEX IF NOT EXISTS idx_app_domains_app ON app_domains(app_id);
@@ -0,0 +1,4 @@
+-- Migration: per-app health probes and termination settings (NULL/empty = runtime defaults)
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS probes JSONB NOT NULL DEFAULT '{}'::jsonb;
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS termination_grace_period_seconds INT NULL CHECK (termination_grace_period_seconds >= 0);
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS pre_stop_sleep_seconds INT NULL CHECK (pre_stop_sleep_seconds >= 0);
@@ -0,0 +1,3 @@
+-- Migration: runtime selection per app (NULL = detect from the artifact SBOM)
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS runtime TEXT NULL;
+ALTER TABLE artifacts ADD COLUMN IF NOT EXISTS sbom JSONB NULL;
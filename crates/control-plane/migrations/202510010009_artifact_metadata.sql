@@ -0,0 +1,2 @@
+-- Migration: start metadata recorded by the CLI at upload completion (package.json scripts.start/main/engines.node, Procfile web)
+ALTER TABLE artifacts ADD COLUMN IF NOT EXISTS metadata JSONB NULL;
@@ -0,0 +1,20 @@
+-- Migration: Procfile-style process types. Non-web replica counts live per app (web keeps applications.replicas);
+-- each release records the Kubernetes Deployments it was rendered into and their rollout state.
+CREATE TABLE IF NOT EXISTS app_processes (
+    app_id UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    process_type TEXT NOT NULL,
+    replicas INT NOT NULL DEFAULT 1 CHECK (replicas >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (app_id, process_type)
+);
+
+CREATE TABLE IF NOT EXISTS deployment_processes (
+    deployment_id UUID NOT NULL REFERENCES deployments(id) ON DELETE CASCADE,
+    process_type TEXT NOT NULL,
+    object_name TEXT NOT NULL,
+    desired_replicas INT NOT NULL,
+    available_replicas INT NOT NULL DEFAULT 0,
+    ready BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (deployment_id, process_type)
+);
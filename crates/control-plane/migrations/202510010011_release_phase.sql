@@ -0,0 +1,2 @@
+-- Migration: optional per-app release command run as a one-off Job before each rollout (e.g. database migrations)
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS release_command TEXT NULL;
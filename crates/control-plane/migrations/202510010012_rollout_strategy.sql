@@ -0,0 +1,6 @@
+-- Migration: per-deployment rollout strategy (rolling update in place, or a digest-suffixed candidate Deployment
+-- promoted through a Service selector switch / weighted canary Ingress) and the phase of non-rolling rollouts
+ALTER TABLE deployments ADD COLUMN IF NOT EXISTS strategy TEXT NOT NULL DEFAULT 'rolling' CHECK (strategy IN ('rolling','blue_green','canary'));
+ALTER TABLE deployments ADD COLUMN IF NOT EXISTS canary_percent INT NULL CHECK (canary_percent BETWEEN 1 AND 99);
+-- progressing | awaiting_promotion | promoting | promoted | aborted (NULL for rolling deployments)
+ALTER TABLE deployments ADD COLUMN IF NOT EXISTS rollout_phase TEXT NULL;
@@ -0,0 +1,4 @@
+-- Migration: opt-in automatic rollback when the status watcher fails a deployment; rollback deployments link to the
+-- deployment whose failure created them (loop guard: a failed automatic rollback is never rolled back again)
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS auto_rollback BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE deployments ADD COLUMN IF NOT EXISTS auto_rollback_of UUID NULL REFERENCES deployments(id) ON DELETE SET NULL;
@@ -0,0 +1,2 @@
+-- Migration: per-app rollout policy (progress deadline, minReady, maxSurge/maxUnavailable, watcher timeout); empty = platform defaults
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS rollout_policy JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,14 @@
+-- Migration: durable queue of Kubernetes applies (one row per deployment), drained by background workers with retries
+CREATE TABLE IF NOT EXISTS deployment_apply_queue (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    deployment_id UUID NOT NULL UNIQUE REFERENCES deployments(id) ON DELETE CASCADE,
+    dev_hot BOOLEAN NOT NULL DEFAULT FALSE,
+    release_done BOOLEAN NOT NULL DEFAULT FALSE,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending','done','failed')),
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_error TEXT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    completed_at TIMESTAMPTZ NULL
+);
+CREATE INDEX IF NOT EXISTS idx_deployment_apply_queue_due ON deployment_apply_queue(next_attempt_at) WHERE status='pending';
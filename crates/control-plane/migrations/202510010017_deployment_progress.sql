@@ -0,0 +1,4 @@
+-- Migration: live rollout progress per process Deployment (updated/ready replicas, pod states) cached by the status watcher
+ALTER TABLE deployment_processes ADD COLUMN IF NOT EXISTS updated_replicas INT NOT NULL DEFAULT 0;
+ALTER TABLE deployment_processes ADD COLUMN IF NOT EXISTS ready_replicas INT NOT NULL DEFAULT 0;
+ALTER TABLE deployment_processes ADD COLUMN IF NOT EXISTS pods JSONB NOT NULL DEFAULT '[]'::jsonb;
@@ -0,0 +1,6 @@
+-- Migration: Kubernetes Warning events ingested as `k8s_event` deployment events (object, reason, event UID for dedupe)
+ALTER TABLE deployment_events ADD COLUMN IF NOT EXISTS source_kind TEXT NULL;
+ALTER TABLE deployment_events ADD COLUMN IF NOT EXISTS source_name TEXT NULL;
+ALTER TABLE deployment_events ADD COLUMN IF NOT EXISTS source_reason TEXT NULL;
+ALTER TABLE deployment_events ADD COLUMN IF NOT EXISTS source_uid TEXT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_deployment_events_source_uid ON deployment_events(source_uid) WHERE source_uid IS NOT NULL;
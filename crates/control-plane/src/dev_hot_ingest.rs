@@ -25,27 +25,33 @@ pub async fn spawn_dev_hot_log_ingestion() -> Result<()> {
 }
 
 async fn run_ingest_loop(client: Client) -> Result<()> {
-    let namespace = std::env::var("AETHER_NAMESPACE").unwrap_or_else(|_| "default".to_string());
-    let pods: Api<Pod> = Api::namespaced(client.clone(), &namespace);
+    // AETHER_NAMESPACE restricts ingestion to one namespace; otherwise every app namespace is scanned.
+    let namespace = std::env::var("AETHER_NAMESPACE").ok().filter(|v| !v.trim().is_empty());
+    let pods: Api<Pod> = match namespace.as_deref() {
+        Some(ns) => Api::namespaced(client.clone(), ns),
+        None => Api::all(client.clone()),
+    };
     use std::collections::{HashMap, HashSet};
     use rustc_hash::FxHasher;
     use std::hash::Hasher;
     let mut seen: HashMap<String, HashSet<u64>> = HashMap::new();
     let poll_secs: u64 = std::env::var("AETHER_DEV_HOT_INGEST_POLL_SEC").ok().and_then(|v| v.parse().ok()).unwrap_or(10).max(1);
     let mut err_attempt: u32 = 0;
-    info!(namespace, poll_secs, "dev_hot_ingest_loop_started");
+    info!(namespace=namespace.as_deref().unwrap_or("*"), poll_secs, "dev_hot_ingest_loop_started");
     loop {
-        match pods.list(&ListParams::default()).await {
+        match pods.list(&ListParams::default().labels("app_name")).await {
             Ok(list) => {
                 err_attempt = 0; // reset on success
                 for p in list.items {
                     let ann_ok = p.metadata.annotations.as_ref().and_then(|a| a.get("aether.dev/dev-hot")).map(|v| v=="true").unwrap_or(false);
                     if !ann_ok { continue; }
                     let Some(name) = p.metadata.name.clone() else { continue; };
+                    let pod_ns = p.metadata.namespace.clone().unwrap_or_else(|| "default".into());
+                    let ns_pods: Api<Pod> = Api::namespaced(client.clone(), &pod_ns);
                     let lp = LogParams { container: Some("fetcher".into()), tail_lines: Some(200), ..LogParams::default() };
-                    match pods.logs(&name, &lp).await {
+                    match ns_pods.logs(&name, &lp).await {
                         Ok(text) => {
-                            let entry = seen.entry(name.clone()).or_default();
+                            let entry = seen.entry(format!("{pod_ns}/{name}")).or_default();
                             for line in text.lines() {
                                 let mut hasher = FxHasher::default();
                                 hasher.write(line.as_bytes());
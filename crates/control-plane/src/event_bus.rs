@@ -0,0 +1,136 @@
+//! Cross-replica event bus for deployment and artifact state changes. Publishing broadcasts the event in-process and
+//! sends it through Postgres `NOTIFY` on [`CHANNEL`]; every replica `LISTEN`s and re-broadcasts what the other
+//! replicas published, so SSE streams and in-memory caches see writes handled by any replica (webhook deliveries are
+//! queued in the database and need no fan-out).
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, Pool, Postgres};
+use tokio::sync::broadcast;
+use crate::services::deployments::DeploymentUpdate;
+
+/// Postgres notification channel shared by all replicas.
+pub const CHANNEL: &str = "aether_events";
+
+/// `NOTIFY` payloads must stay below 8000 bytes.
+const MAX_PAYLOAD_BYTES: usize = 7900;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum BusEvent {
+    Deployment(DeploymentUpdate),
+    Artifact(ArtifactUpdate),
+}
+
+/// Artifact lifecycle event (`stored`, `retention_delete`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactUpdate {
+    pub artifact_id: uuid::Uuid,
+    pub app_id: Option<uuid::Uuid>,
+    pub event_type: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope { origin: uuid::Uuid, event: BusEvent }
+
+static REPLICA_ID: Lazy<uuid::Uuid> = Lazy::new(uuid::Uuid::new_v4);
+
+/// Events buffered per subscriber before it lags (`AETHER_EVENT_BUS_BUFFER`, default 1024).
+static LOCAL: Lazy<broadcast::Sender<BusEvent>> = Lazy::new(|| {
+    let cap = std::env::var("AETHER_EVENT_BUS_BUFFER").ok().and_then(|v| v.parse::<usize>().ok()).unwrap_or(1024);
+    broadcast::channel(cap.max(16)).0
+});
+
+/// Identifies this process in notifications so it skips its own.
+pub fn replica_id() -> uuid::Uuid { *REPLICA_ID }
+
+/// Receive every event published after this call, by this replica or (while the listener runs) any other.
+pub fn subscribe() -> broadcast::Receiver<BusEvent> { LOCAL.subscribe() }
+
+/// Broadcast locally, then notify the other replicas (best-effort: a failed `NOTIFY` only affects remote replicas).
+/// Inside a transaction the notification goes out on commit.
+pub async fn publish<'c>(ex: impl PgExecutor<'c>, event: BusEvent) {
+    // no local subscribers is the common case
+    let _ = LOCAL.send(event.clone());
+    let Some(payload) = encode(event) else {
+        crate::telemetry::EVENT_BUS_MESSAGES.with_label_values(&["oversized"]).inc();
+        tracing::warn!("event_bus_payload_too_large");
+        return;
+    };
+    match sqlx::query("SELECT pg_notify($1, $2)").bind(CHANNEL).bind(&payload).execute(ex).await {
+        Ok(_) => crate::telemetry::EVENT_BUS_MESSAGES.with_label_values(&["published"]).inc(),
+        Err(e) => {
+            crate::telemetry::EVENT_BUS_MESSAGES.with_label_values(&["publish_failed"]).inc();
+            tracing::warn!(error=%e, "event_bus_notify_failed");
+        }
+    }
+}
+
+/// Envelope JSON; long deployment event messages (e.g. release logs) are cut to fit the `NOTIFY` limit.
+fn encode(mut event: BusEvent) -> Option<String> {
+    let payload = serde_json::to_string(&Envelope { origin: replica_id(), event: event.clone() }).ok()?;
+    if payload.len() <= MAX_PAYLOAD_BYTES { return Some(payload); }
+    if let BusEvent::Deployment(DeploymentUpdate::Event { message: Some(m), .. }) = &mut event {
+        *m = m.chars().take(1024).collect();
+    }
+    let payload = serde_json::to_string(&Envelope { origin: replica_id(), event }).ok()?;
+    (payload.len() <= MAX_PAYLOAD_BYTES).then_some(payload)
+}
+
+/// Decode a notification; `None` for our own (already broadcast when published) or malformed ones.
+pub fn decode_remote(payload: &str) -> Option<BusEvent> {
+    match serde_json::from_str::<Envelope>(payload) {
+        Ok(env) if env.origin == replica_id() => None,
+        Ok(env) => Some(env.event),
+        Err(e) => { tracing::warn!(error=%e, "event_bus_bad_payload"); None }
+    }
+}
+
+/// Start the `LISTEN` loop unless one is already running (it stops with the runtime that spawned it).
+pub fn spawn_listener(pool: &Pool<Postgres>) {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    static RUNNING: AtomicBool = AtomicBool::new(false);
+    struct Running;
+    impl Drop for Running { fn drop(&mut self) { RUNNING.store(false, Ordering::SeqCst); } }
+    if RUNNING.swap(true, Ordering::SeqCst) { return; }
+    let (pool, running) = (pool.clone(), Running);
+    tokio::spawn(async move {
+        let _running = running;
+        run_listener(pool).await;
+    });
+}
+
+/// `LISTEN` on [`CHANNEL`] and re-broadcast events of other replicas; reconnects with a short delay after errors
+/// (notifications sent while disconnected are lost, streams resynchronise from the database).
+pub async fn run_listener(pool: Pool<Postgres>) {
+    loop {
+        let mut listener = match sqlx::postgres::PgListener::connect_with(&pool).await {
+            Ok(l) => l,
+            Err(e) => { tracing::warn!(error=%e, "event_bus_connect_failed"); tokio::time::sleep(std::time::Duration::from_secs(5)).await; continue; }
+        };
+        if let Err(e) = listener.listen(CHANNEL).await {
+            tracing::warn!(error=%e, "event_bus_listen_failed");
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            continue;
+        }
+        tracing::info!(replica=%replica_id(), channel=CHANNEL, "event_bus_listening");
+        loop {
+            match listener.recv().await {
+                Ok(n) => {
+                    let Some(event) = decode_remote(n.payload()) else { continue; };
+                    crate::telemetry::EVENT_BUS_MESSAGES.with_label_values(&["received"]).inc();
+                    on_remote(&pool, &event).await;
+                    let _ = LOCAL.send(event);
+                }
+                Err(e) => { tracing::warn!(error=%e, "event_bus_recv_failed"); break; }
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+}
+
+/// Keep per-replica state in line with writes made elsewhere.
+async fn on_remote(pool: &Pool<Postgres>, event: &BusEvent) {
+    if let BusEvent::Artifact(_) = event {
+        crate::handlers::uploads::refresh_artifacts_total(pool).await;
+    }
+}
@@ -1,29 +1,38 @@
 use axum::{Json, extract::{Path, State, Query}};
 use serde::{Serialize, Deserialize};
 use utoipa::ToSchema;
-use crate::{AppState, models::Application, error::{ApiError, ApiResult, ApiErrorBody}, services};
-use axum::http::StatusCode;
+use crate::{AppState, models::{Application, AppSettings, DeploymentProcess, ProbeSettings, PublicKey}, error::{ApiError, ApiResult, ApiErrorBody}, services};
+use axum::http::{StatusCode, HeaderMap, header};
+use axum::{body::Body, response::{IntoResponse, Response, sse::{Event, KeepAlive, Sse}}};
+use futures_util::StreamExt;
+use std::convert::Infallible;
 
 #[derive(Deserialize, ToSchema)]
-pub struct CreateAppReq { pub name: String }
+pub struct CreateAppReq {
+    pub name: String,
+    /// Kubernetes namespace (default AETHER_NAMESPACE or "default")
+    #[serde(default)] pub namespace: Option<String>,
+}
 
 #[derive(Serialize, ToSchema)]
-pub struct CreateAppResp { pub id: uuid::Uuid, pub name: String }
+pub struct CreateAppResp { pub id: uuid::Uuid, pub name: String, pub namespace: String }
 
 /// Create application
 #[utoipa::path(post, path = "/apps", request_body = CreateAppReq, responses( (status = 201, body = CreateAppResp), (status=409, body=ApiErrorBody, description="duplicate"), (status=400, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
 #[tracing::instrument(level="info", skip(state, body), fields(app_name=%body.name))]
 pub async fn create_app(State(state): State<AppState>, Json(body): Json<CreateAppReq>) -> ApiResult<(StatusCode, Json<CreateAppResp>)> {
-    let rec: Application = services::apps::create_app(&state.db, &body.name).await.map_err(|e| {
+    let namespace = body.namespace.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()).unwrap_or_else(services::apps::default_namespace);
+    if !services::apps::valid_namespace(&namespace) { return Err(ApiError::bad_request("namespace must be a DNS-1123 label")); }
+    let rec: Application = services::apps::create_app(&state.db, &body.name, &namespace).await.map_err(|e| {
         if let Some(db_code) = e.as_database_error().and_then(|d| d.code()) { if db_code == "23505" { return ApiError::conflict("application name exists"); } }
         ApiError::internal(format!("insert error: {e}"))
     })?;
     tracing::info!(app_id=%rec.id, "application created");
-    Ok((StatusCode::CREATED, Json(CreateAppResp { id: rec.id, name: rec.name })))
+    Ok((StatusCode::CREATED, Json(CreateAppResp { id: rec.id, name: rec.name, namespace: rec.namespace })))
 }
 
 #[derive(Serialize, ToSchema)]
-pub struct ListAppItem { pub id: uuid::Uuid, pub name: String }
+pub struct ListAppItem { pub id: uuid::Uuid, pub name: String, pub namespace: String }
 
 #[derive(Deserialize, ToSchema)]
 pub struct AppsListQuery { pub limit: Option<i64>, pub offset: Option<i64> }
@@ -34,15 +43,72 @@ pub struct AppsListQuery { pub limit: Option<i64>, pub offset: Option<i64> }
 pub async fn list_apps(State(state): State<AppState>, Query(q): Query<AppsListQuery>) -> ApiResult<Json<Vec<ListAppItem>>> {
     let limit = q.limit.unwrap_or(100).clamp(1, 1000);
     let offset = q.offset.unwrap_or(0).max(0);
-    let rows: Vec<Application> = sqlx::query_as::<_, Application>("SELECT id, name, created_at, updated_at FROM applications ORDER BY created_at DESC LIMIT $1 OFFSET $2")
+    let rows: Vec<Application> = sqlx::query_as::<_, Application>("SELECT id, name, created_at, updated_at, namespace FROM applications ORDER BY created_at DESC LIMIT $1 OFFSET $2")
         .bind(limit).bind(offset)
         .fetch_all(&state.db).await.map_err(|e| ApiError::internal(format!("query error: {e}")))?;
-    Ok(Json(rows.into_iter().map(|a| ListAppItem { id: a.id, name: a.name }).collect()))
+    Ok(Json(rows.into_iter().map(|a| ListAppItem { id: a.id, name: a.name, namespace: a.namespace }).collect()))
 }
 
-/// Application logs (placeholder)
-#[utoipa::path(get, path = "/apps/{app_name}/logs", params( ("app_name" = String, Path, description = "Application name") ), responses( (status=200, description="OK") ))]
-pub async fn app_logs(Path(_app_name): Path<String>) -> (StatusCode, String) { (StatusCode::OK, String::new()) }
+#[derive(Deserialize, ToSchema)]
+pub struct AppLogsQuery {
+    pub tail: Option<i64>,
+    pub since: Option<String>,
+    pub container: Option<String>,
+    #[serde(default)] pub follow: bool,
+    pub format: Option<String>,
+}
+
+/// Parse a relative `since` window: plain seconds or a number with s/m/h/d suffix (e.g. `90`, `10m`, `2h`).
+fn parse_since(raw: &str) -> Option<i64> {
+    let raw = raw.trim();
+    let (num, mult) = match raw.chars().last()? {
+        's' => (&raw[..raw.len()-1], 1),
+        'm' => (&raw[..raw.len()-1], 60),
+        'h' => (&raw[..raw.len()-1], 3600),
+        'd' => (&raw[..raw.len()-1], 86400),
+        _ => (raw, 1),
+    };
+    num.parse::<i64>().ok().filter(|v| *v > 0).map(|v| v * mult)
+}
+
+/// Stream application logs from its pods.
+/// Returns chunked `text/plain` (one line per log line) or Server-Sent Events (`event: log`)
+/// when `format=sse` or the client sends `Accept: text/event-stream`.
+#[utoipa::path(get, path = "/apps/{app_name}/logs", params(
+    ("app_name" = String, Path, description = "Application name"),
+    ("tail" = Option<i64>, Query, description = "Lines from the end of each pod log"),
+    ("since" = Option<String>, Query, description = "Relative window, e.g. 300, 10m, 2h"),
+    ("container" = Option<String>, Query, description = "Container name (default app)"),
+    ("follow" = Option<bool>, Query, description = "Keep streaming new lines"),
+    ("format" = Option<String>, Query, description = "text (default) or sse")
+), responses( (status=200, description="Log stream", content_type = "text/plain"), (status=400, body=ApiErrorBody), (status=502, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, q, headers), fields(app_name=%app_name, follow=q.follow))]
+pub async fn app_logs(State(state): State<AppState>, Path(app_name): Path<String>, Query(q): Query<AppLogsQuery>, headers: HeaderMap) -> ApiResult<Response> {
+    let since_seconds = match q.since.as_deref() {
+        Some(s) => Some(parse_since(s).ok_or_else(|| ApiError::bad_request("since must be seconds or a duration like 10m"))?),
+        None => None,
+    };
+    if let Some(t) = q.tail { if t < 0 { return Err(ApiError::bad_request("tail must be >= 0")); } }
+    let opts = crate::k8s::LogOptions { tail_lines: q.tail, since_seconds, container: q.container.clone(), follow: q.follow };
+    let namespace = services::apps::namespace_for(&state.db, &app_name).await
+        .map_err(|e| ApiError::internal(format!("lookup app: {e}")))?
+        .unwrap_or_else(services::apps::default_namespace);
+    let lines = crate::k8s::stream_logs(&app_name, &namespace, &opts).await.map_err(|e| {
+        tracing::warn!(error=%e, "log_stream_failed");
+        ApiError::new(StatusCode::BAD_GATEWAY, "kube_error", format!("log stream: {e}"))
+    })?;
+    let wants_sse = q.format.as_deref() == Some("sse")
+        || headers.get(header::ACCEPT).and_then(|v| v.to_str().ok()).map(|v| v.contains("text/event-stream")).unwrap_or(false);
+    if wants_sse {
+        let events = lines.map(|l| Ok::<_, Infallible>(match l {
+            Ok(line) => Event::default().event("log").data(line),
+            Err(e) => Event::default().event("error").data(e.to_string()),
+        }));
+        return Ok(Sse::new(events).keep_alive(KeepAlive::default()).into_response());
+    }
+    let body = Body::from_stream(lines.map(|l| l.map(|line| format!("{line}\n"))));
+    Ok(([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], body).into_response())
+}
 
 #[derive(serde::Serialize, ToSchema)]
 pub struct AppDeploymentItem { pub id: uuid::Uuid, pub artifact_url: String, pub status: String }
@@ -65,7 +131,7 @@ pub async fn app_deployments(State(state): State<AppState>, Path(app_name): Path
 }
 
 #[derive(Deserialize, ToSchema)]
-pub struct AddPublicKeyReq { pub public_key_hex: String }
+pub struct AddPublicKeyReq { pub public_key_hex: String, #[serde(default)] pub not_before: Option<chrono::DateTime<chrono::Utc>>, #[serde(default)] pub expires_at: Option<chrono::DateTime<chrono::Utc>> }
 
 #[derive(Serialize, ToSchema)]
 pub struct AddPublicKeyResp { pub app_id: uuid::Uuid, pub public_key_hex: String, pub active: bool }
@@ -74,19 +140,282 @@ pub struct AddPublicKeyResp { pub app_id: uuid::Uuid, pub public_key_hex: String
 #[utoipa::path(post, path = "/apps/{app_name}/public-keys", request_body = AddPublicKeyReq, params(("app_name"=String, Path, description="Application name")), responses( (status=201, body=AddPublicKeyResp), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=409, body=ApiErrorBody) ))]
 #[tracing::instrument(level="info", skip(state, body), fields(app_name=%app_name))]
 pub async fn add_public_key(State(state): State<AppState>, Path(app_name): Path<String>, Json(body): Json<AddPublicKeyReq>) -> ApiResult<(StatusCode, Json<AddPublicKeyResp>)> {
-    if body.public_key_hex.len() != 64 || !body.public_key_hex.chars().all(|c| c.is_ascii_hexdigit()) {
-        return Err(ApiError::bad_request("public_key_hex must be 64 hex chars"));
-    }
+    validate_key_hex(&body.public_key_hex)?;
+    if let (Some(nb), Some(exp)) = (body.not_before, body.expires_at) { if exp <= nb { return Err(ApiError::bad_request("expires_at must be after not_before")); } }
     let mut tx = state.db.begin().await.map_err(|e| ApiError::internal(format!("tx begin: {e}")))?;
-    let app: Option<Application> = sqlx::query_as::<_, Application>("SELECT id, name, created_at, updated_at FROM applications WHERE name=$1")
+    let app: Option<Application> = sqlx::query_as::<_, Application>("SELECT id, name, created_at, updated_at, namespace FROM applications WHERE name=$1")
         .bind(&app_name).fetch_optional(&mut *tx).await.map_err(|e| ApiError::internal(format!("query app: {e}")))?;
     let Some(app) = app else { return Err(ApiError::not_found("application not found")); };
     // Insert ignore conflict
-    let _res = sqlx::query("INSERT INTO public_keys (app_id, public_key_hex, active) VALUES ($1,$2,TRUE) ON CONFLICT (app_id, public_key_hex) DO UPDATE SET active=EXCLUDED.active RETURNING app_id")
+    let _res = sqlx::query("INSERT INTO public_keys (app_id, public_key_hex, active, not_before, expires_at) VALUES ($1,$2,TRUE,$3,$4) ON CONFLICT (app_id, public_key_hex) DO UPDATE SET active=EXCLUDED.active, not_before=EXCLUDED.not_before, expires_at=EXCLUDED.expires_at RETURNING app_id")
         .bind(app.id)
         .bind(&body.public_key_hex)
+        .bind(body.not_before)
+        .bind(body.expires_at)
         .fetch_one(&mut *tx).await.map_err(|e| ApiError::internal(format!("insert key: {e}")))?;
     tx.commit().await.map_err(|e| ApiError::internal(format!("commit: {e}")))?;
     tracing::info!(app_id=%app.id, "public_key_added");
     Ok((StatusCode::CREATED, Json(AddPublicKeyResp { app_id: app.id, public_key_hex: body.public_key_hex, active: true })))
 }
+
+fn validate_key_hex(k: &str) -> ApiResult<()> {
+    if k.len() != 64 || !k.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ApiError::bad_request("public_key_hex must be 64 hex chars"));
+    }
+    Ok(())
+}
+
+fn map_key_err(e: sqlx::Error) -> ApiError {
+    if matches!(e, sqlx::Error::RowNotFound) { return ApiError::not_found("application or key not found"); }
+    ApiError::internal(format!("query error: {e}"))
+}
+
+/// List signing keys of an application (including revoked and expired ones)
+#[utoipa::path(get, path = "/apps/{app_name}/public-keys", params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=[PublicKey]), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state), fields(app_name=%app_name))]
+pub async fn list_public_keys(State(state): State<AppState>, Path(app_name): Path<String>) -> ApiResult<Json<Vec<PublicKey>>> {
+    let keys = services::keys::list_keys(&state.db, &app_name).await.map_err(map_key_err)?;
+    Ok(Json(keys))
+}
+
+/// Revoke a signing key (sets active=false); signatures made with it are rejected from now on
+#[utoipa::path(delete, path = "/apps/{app_name}/public-keys/{id}", params(("app_name"=String, Path, description="Application name"), ("id"=uuid::Uuid, Path, description="Key ID")), responses( (status=200, body=PublicKey), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state), fields(app_name=%app_name, key_id=%id))]
+pub async fn delete_public_key(State(state): State<AppState>, Path((app_name, id)): Path<(String, uuid::Uuid)>) -> ApiResult<Json<PublicKey>> {
+    let key = services::keys::deactivate_key(&state.db, &app_name, id).await.map_err(map_key_err)?;
+    tracing::info!(key_id=%key.id, "public_key_deactivated");
+    Ok(Json(key))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RotatePublicKeyReq {
+    pub public_key_hex: String,
+    #[serde(default)] pub not_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Seconds the previous keys stay valid (default AETHER_KEY_ROTATION_GRACE_SECS or 86400)
+    #[serde(default)] pub grace_period_secs: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RotatePublicKeyResp { pub key: PublicKey, pub expiring: Vec<PublicKey>, pub old_keys_expire_at: chrono::DateTime<chrono::Utc> }
+
+/// Rotate signing keys: add a new key and schedule the currently valid ones to expire after a grace period
+#[utoipa::path(post, path = "/apps/{app_name}/public-keys/rotate", request_body = RotatePublicKeyReq, params(("app_name"=String, Path, description="Application name")), responses( (status=201, body=RotatePublicKeyResp), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, body), fields(app_name=%app_name))]
+pub async fn rotate_public_key(State(state): State<AppState>, Path(app_name): Path<String>, Json(body): Json<RotatePublicKeyReq>) -> ApiResult<(StatusCode, Json<RotatePublicKeyResp>)> {
+    validate_key_hex(&body.public_key_hex)?;
+    let grace = body.grace_period_secs.unwrap_or_else(services::keys::default_rotation_grace_secs);
+    if grace < 0 { return Err(ApiError::bad_request("grace_period_secs must be >= 0")); }
+    let old_keys_expire_at = chrono::Utc::now() + chrono::Duration::seconds(grace);
+    let (key, expiring) = services::keys::rotate_key(&state.db, &app_name, &body.public_key_hex, body.not_before, old_keys_expire_at)
+        .await.map_err(map_key_err)?;
+    tracing::info!(key_id=%key.id, expiring=expiring.len(), "public_key_rotated");
+    Ok((StatusCode::CREATED, Json(RotatePublicKeyResp { key, expiring, old_keys_expire_at })))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AppDetail {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub namespace: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub settings: AppSettings,
+    /// Cap actually enforced by `PUT /apps/{app_name}/scale`
+    pub effective_max_replicas: i32,
+    /// Deployment created to roll out a settings change (PATCH only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollout_deployment_id: Option<uuid::Uuid>,
+}
+
+async fn load_app(state: &AppState, app_name: &str) -> ApiResult<Application> {
+    sqlx::query_as::<_, Application>("SELECT id, name, created_at, updated_at, namespace FROM applications WHERE name=$1")
+        .bind(app_name).fetch_optional(&state.db).await.map_err(|e| ApiError::internal(format!("query app: {e}")))?
+        .ok_or_else(|| ApiError::not_found("application not found"))
+}
+
+fn detail(app: Application, settings: AppSettings) -> AppDetail {
+    let effective_max_replicas = settings.max_replicas.unwrap_or_else(services::apps::default_max_replicas);
+    AppDetail { id: app.id, name: app.name, namespace: app.namespace, created_at: app.created_at, settings, effective_max_replicas, rollout_deployment_id: None }
+}
+
+/// Get application with its deployment settings
+#[utoipa::path(get, path = "/apps/{app_name}", params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=AppDetail), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state), fields(app_name=%app_name))]
+pub async fn get_app(State(state): State<AppState>, Path(app_name): Path<String>) -> ApiResult<Json<AppDetail>> {
+    let app = load_app(&state, &app_name).await?;
+    let settings = services::apps::get_settings(&state.db, app.id).await.map_err(|e| ApiError::internal(format!("query settings: {e}")))?;
+    Ok(Json(detail(app, settings)))
+}
+
+// Distinguish an absent field (keep) from an explicit null (clear).
+fn double_option<'de, D, T>(d: D) -> Result<Option<Option<T>>, D::Error> where D: serde::Deserializer<'de>, T: Deserialize<'de> {
+    Option::<T>::deserialize(d).map(Some)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PatchAppReq {
+    /// Per-app replica cap; `null` clears it (platform default applies)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<i32>)]
+    pub max_replicas: Option<Option<i32>>,
+    /// Runtime id (`GET /runtimes`); `null` returns to SBOM detection
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<String>)]
+    pub runtime: Option<Option<String>>,
+    /// Container port; `null` restores the runtime default
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<i32>)]
+    pub port: Option<Option<i32>>,
+    /// Probe overrides (replaces the stored ones; `{}` restores the runtime defaults)
+    #[serde(default)] pub probes: Option<ProbeSettings>,
+    /// `null` restores the runtime default
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<i32>)]
+    pub termination_grace_period_seconds: Option<Option<i32>>,
+    /// `null` restores the runtime default, 0 disables the preStop hook
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<i32>)]
+    pub pre_stop_sleep_seconds: Option<Option<i32>>,
+    /// Release-phase command run before each rollout; `null` (or blank) falls back to the Procfile `release:` entry
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<String>)]
+    pub release_command: Option<Option<String>>,
+    /// Roll back to the last running release automatically when a deployment fails
+    #[serde(default)] pub auto_rollback: Option<bool>,
+    /// Rollout policy (replaces the stored one; `{}` restores the platform defaults)
+    #[serde(default)] pub rollout_policy: Option<crate::models::RolloutPolicy>,
+}
+
+/// Update application settings; changes to the pod template (runtime, port, probes, termination) roll out the current release again,
+/// the release command, auto-rollback and rollout policy apply from the next rollout on
+#[utoipa::path(patch, path = "/apps/{app_name}", request_body = PatchAppReq, params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=AppDetail), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, body), fields(app_name=%app_name))]
+pub async fn patch_app(State(state): State<AppState>, Path(app_name): Path<String>, Json(body): Json<PatchAppReq>) -> ApiResult<Json<AppDetail>> {
+    if body.port.flatten().is_some_and(|p| !(1..=65535).contains(&p)) { return Err(ApiError::bad_request("port must be between 1 and 65535")); }
+    if let Some(Some(id)) = &body.runtime {
+        if crate::runtime::get(id).is_none() { return Err(ApiError::bad_request(format!("unknown runtime '{id}'"))); }
+    }
+    if let Some(probes) = &body.probes { services::apps::validate_probes(probes).map_err(ApiError::bad_request)?; }
+    if let Some(policy) = &body.rollout_policy { services::apps::validate_rollout_policy(policy).map_err(ApiError::bad_request)?; }
+    let app = load_app(&state, &app_name).await?;
+    let mut settings = services::apps::get_settings(&state.db, app.id).await.map_err(|e| ApiError::internal(format!("query settings: {e}")))?;
+    let grace = body.termination_grace_period_seconds.unwrap_or(settings.termination_grace_period_seconds);
+    let pre_stop = body.pre_stop_sleep_seconds.unwrap_or(settings.pre_stop_sleep_seconds);
+    if grace.is_some_and(|g| g < 0) || pre_stop.is_some_and(|p| p < 0) { return Err(ApiError::bad_request("termination settings must be >= 0")); }
+    let (eff_grace, eff_pre_stop) = (grace.unwrap_or(crate::k8s::DEFAULT_TERMINATION_GRACE_SECS), pre_stop.unwrap_or(crate::k8s::DEFAULT_PRE_STOP_SLEEP_SECS));
+    if eff_pre_stop >= eff_grace && eff_pre_stop > 0 {
+        return Err(ApiError::bad_request(format!("pre_stop_sleep_seconds ({eff_pre_stop}) must be below termination_grace_period_seconds ({eff_grace})")));
+    }
+    let internal = |e: sqlx::Error| ApiError::internal(format!("update settings: {e}"));
+    // All fields or none: a failing update must not leave the earlier ones applied.
+    let mut tx = state.db.begin().await.map_err(internal)?;
+    if let Some(max) = body.max_replicas {
+        if max.is_some_and(|m| m < 0) { return Err(ApiError::bad_request("max_replicas must be >= 0")); }
+        let effective = max.unwrap_or_else(services::apps::default_max_replicas);
+        if settings.replicas > effective { return Err(ApiError::bad_request(format!("current replicas ({}) exceed max_replicas {effective}; scale down first", settings.replicas))); }
+        let processes = services::processes::process_replicas(&mut *tx, app.id).await.map_err(internal)?;
+        if let Some((process, replicas)) = processes.iter().find(|(_, r)| **r > effective) {
+            return Err(ApiError::bad_request(format!("{process} replicas ({replicas}) exceed max_replicas {effective}; scale down first")));
+        }
+        settings = services::apps::set_max_replicas(&mut *tx, app.id, max).await.map_err(internal)?;
+    }
+    if let Some(cmd) = body.release_command {
+        let cmd = cmd.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+        if cmd != settings.release_command {
+            settings = services::apps::set_release_command(&mut *tx, app.id, cmd.as_deref()).await.map_err(internal)?;
+        }
+    }
+    if let Some(enabled) = body.auto_rollback.filter(|e| *e != settings.auto_rollback) {
+        settings = services::apps::set_auto_rollback(&mut *tx, app.id, enabled).await.map_err(internal)?;
+    }
+    if let Some(policy) = body.rollout_policy.filter(|p| *p != settings.rollout_policy.0) {
+        settings = services::apps::set_rollout_policy(&mut *tx, app.id, &policy).await.map_err(internal)?;
+    }
+    let mut changed: Vec<&str> = Vec::new();
+    if let Some(runtime) = body.runtime.filter(|r| *r != settings.runtime) {
+        settings = services::apps::set_runtime(&mut *tx, app.id, runtime.as_deref()).await.map_err(internal)?;
+        changed.push("runtime");
+    }
+    if let Some(port) = body.port.filter(|p| *p != settings.port) {
+        settings = services::apps::set_port(&mut *tx, app.id, port).await.map_err(internal)?;
+        changed.push("port");
+    }
+    if let Some(probes) = body.probes.filter(|p| *p != settings.probes.0) {
+        settings = services::apps::set_probes(&mut *tx, app.id, &probes).await.map_err(internal)?;
+        changed.push("probes");
+    }
+    if grace != settings.termination_grace_period_seconds || pre_stop != settings.pre_stop_sleep_seconds {
+        settings = services::apps::set_termination(&mut *tx, app.id, grace, pre_stop).await.map_err(internal)?;
+        changed.push("termination");
+    }
+    tx.commit().await.map_err(internal)?;
+    let rollout = if changed.is_empty() { None } else {
+        services::deployments::redeploy_current(&state.db, &app.name, app.id, "settings_changed", &changed.join(",")).await
+            .map_err(|e| ApiError::internal(format!("rollout: {e}")))?
+    };
+    let mut resp = detail(app, settings);
+    resp.rollout_deployment_id = rollout.map(|d| d.id);
+    Ok(Json(resp))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ScaleReq {
+    pub replicas: i32,
+    /// Process type to scale (default `web`)
+    #[serde(default)] pub process: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ScaleResp {
+    pub process: String,
+    pub replicas: i32,
+    pub max_replicas: i32,
+    /// Whether a live Deployment was patched (false: stored for the next rollout)
+    pub live_patched: bool,
+}
+
+/// Scale one process type of an application (default `web`): persists the replica count and patches only
+/// `spec.replicas` of its live Deployment. `max_replicas` caps every process type.
+#[utoipa::path(put, path = "/apps/{app_name}/scale", request_body = ScaleReq, params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=ScaleResp), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=502, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, body), fields(app_name=%app_name, replicas=body.replicas, process=?body.process))]
+pub async fn scale_app(State(state): State<AppState>, Path(app_name): Path<String>, Json(body): Json<ScaleReq>) -> ApiResult<Json<ScaleResp>> {
+    let app = load_app(&state, &app_name).await?;
+    let process = body.process.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).unwrap_or_else(|| crate::k8s::WEB_PROCESS.to_string());
+    let web = process == crate::k8s::WEB_PROCESS;
+    if !web && !services::processes::valid_process_type(&process) {
+        return Err(ApiError::bad_request(format!("invalid process type '{process}'")));
+    }
+    let settings = services::apps::get_settings(&state.db, app.id).await.map_err(|e| ApiError::internal(format!("query settings: {e}")))?;
+    let max = settings.max_replicas.unwrap_or_else(services::apps::default_max_replicas);
+    if body.replicas < 0 || body.replicas > max {
+        return Err(ApiError::bad_request(format!("replicas must be between 0 and {max}")));
+    }
+    let internal = |e: sqlx::Error| ApiError::internal(format!("update replicas: {e}"));
+    // The new count is only committed once the live Deployment took it, so a failed patch leaves both unchanged.
+    let mut tx = state.db.begin().await.map_err(internal)?;
+    if web {
+        services::apps::set_replicas(&mut *tx, app.id, body.replicas).await.map_err(internal)?;
+    } else {
+        services::processes::set_process_replicas(&mut *tx, app.id, &process, body.replicas).await.map_err(internal)?;
+    }
+    let live_patched = crate::k8s::scale_deployment(&crate::k8s::process_object_name(&app.name, &process), &app.namespace, body.replicas).await
+        .map_err(|e| ApiError::new(StatusCode::BAD_GATEWAY, "kube_error", format!("scale failed: {e}")))?;
+    tx.commit().await.map_err(internal)?;
+    if let Ok(Some(current)) = services::deployments::current_release(&state.db, app.id).await {
+        let msg = if web { format!("replicas={}", body.replicas) } else { format!("process={process} replicas={}", body.replicas) };
+        services::deployments::record_event(&state.db, current.id, "scaled", Some(&msg)).await;
+    }
+    tracing::info!(app_id=%app.id, process=%process, replicas=body.replicas, live_patched, "app scaled");
+    Ok(Json(ScaleResp { process, replicas: body.replicas, max_replicas: max, live_patched }))
+}
+
+/// Process types of the app's current release: one Kubernetes Deployment each, with desired/available replicas
+#[utoipa::path(get, path = "/apps/{app_name}/processes", params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=[DeploymentProcess]), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state), fields(app_name=%app_name))]
+pub async fn list_processes(State(state): State<AppState>, Path(app_name): Path<String>) -> ApiResult<Json<Vec<DeploymentProcess>>> {
+    let app = load_app(&state, &app_name).await?;
+    let Some(current) = services::deployments::current_release(&state.db, app.id).await.map_err(|e| ApiError::internal(format!("query release: {e}")))? else {
+        return Ok(Json(Vec::new()));
+    };
+    let rows = services::processes::list_for_deployment(&state.db, current.id).await.map_err(|e| ApiError::internal(format!("query processes: {e}")))?;
+    Ok(Json(rows))
+}
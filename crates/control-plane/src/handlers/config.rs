@@ -0,0 +1,68 @@
+use axum::{Json, extract::{Path, State, Query}};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use std::collections::BTreeMap;
+use crate::{AppState, error::{ApiError, ApiResult, ApiErrorBody}, services};
+
+#[derive(Serialize, ToSchema)]
+pub struct AppConfigResp {
+    pub values: BTreeMap<String, String>,
+    /// Deployment created to roll out the change (absent when nothing changed or nothing is deployed yet)
+    pub rollout_deployment_id: Option<uuid::Uuid>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PutAppConfigReq { pub values: BTreeMap<String, String> }
+
+#[derive(Deserialize, ToSchema)]
+pub struct DeleteAppConfigQuery { pub keys: Option<String> }
+
+async fn app_id(state: &AppState, app_name: &str) -> ApiResult<uuid::Uuid> {
+    sqlx::query_scalar("SELECT id FROM applications WHERE name=$1").bind(app_name)
+        .fetch_optional(&state.db).await.map_err(|e| ApiError::internal(format!("lookup app: {e}")))?
+        .ok_or_else(|| ApiError::not_found("application not found"))
+}
+
+async fn respond(state: &AppState, app_name: &str, app_id: uuid::Uuid, changed: bool, what: &str) -> ApiResult<Json<AppConfigResp>> {
+    let rollout = if changed {
+        services::deployments::redeploy_current(&state.db, app_name, app_id, "config_changed", what).await
+            .map_err(|e| ApiError::internal(format!("rollout: {e}")))?
+    } else { None };
+    let values = services::config::get_config(&state.db, app_id).await.map_err(|e| ApiError::internal(format!("query error: {e}")))?;
+    Ok(Json(AppConfigResp { values, rollout_deployment_id: rollout.map(|d| d.id) }))
+}
+
+/// Get application config (env vars injected into the app container)
+#[utoipa::path(get, path = "/apps/{app_name}/config", params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=AppConfigResp), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state), fields(app_name=%app_name))]
+pub async fn get_app_config(State(state): State<AppState>, Path(app_name): Path<String>) -> ApiResult<Json<AppConfigResp>> {
+    let app_id = app_id(&state, &app_name).await?;
+    let values = services::config::get_config(&state.db, app_id).await.map_err(|e| ApiError::internal(format!("query error: {e}")))?;
+    Ok(Json(AppConfigResp { values, rollout_deployment_id: None }))
+}
+
+/// Set config values (merged into existing config); a change rolls out the current release again
+#[utoipa::path(put, path = "/apps/{app_name}/config", request_body = PutAppConfigReq, params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=AppConfigResp), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, body), fields(app_name=%app_name, keys=body.values.len()))]
+pub async fn put_app_config(State(state): State<AppState>, Path(app_name): Path<String>, Json(body): Json<PutAppConfigReq>) -> ApiResult<Json<AppConfigResp>> {
+    if body.values.is_empty() { return Err(ApiError::bad_request("values must not be empty")); }
+    if let Some(k) = body.values.keys().find(|k| !services::config::valid_key(k)) {
+        return Err(ApiError::bad_request(format!("invalid config key '{k}' (env var name, AETHER_ prefix reserved)")));
+    }
+    if body.values.values().any(|v| v.len() > 32 * 1024) { return Err(ApiError::bad_request("config values are limited to 32KiB")); }
+    let app_id = app_id(&state, &app_name).await?;
+    let changed = services::config::set_values(&state.db, app_id, &body.values).await.map_err(|e| ApiError::internal(format!("update config: {e}")))?;
+    let keys: Vec<&str> = body.values.keys().map(String::as_str).collect();
+    respond(&state, &app_name, app_id, changed, &format!("set {}", keys.join(","))).await
+}
+
+/// Remove config keys; a change rolls out the current release again
+#[utoipa::path(delete, path = "/apps/{app_name}/config", params(("app_name"=String, Path, description="Application name"), ("keys"=String, Query, description="Comma separated keys to remove")), responses( (status=200, body=AppConfigResp), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, q), fields(app_name=%app_name))]
+pub async fn delete_app_config(State(state): State<AppState>, Path(app_name): Path<String>, Query(q): Query<DeleteAppConfigQuery>) -> ApiResult<Json<AppConfigResp>> {
+    let keys: Vec<String> = q.keys.unwrap_or_default().split(',').map(|k| k.trim().to_string()).filter(|k| !k.is_empty()).collect();
+    if keys.is_empty() { return Err(ApiError::bad_request("keys query parameter required")); }
+    let app_id = app_id(&state, &app_name).await?;
+    let changed = services::config::unset_keys(&state.db, app_id, &keys).await.map_err(|e| ApiError::internal(format!("update config: {e}")))?;
+    respond(&state, &app_name, app_id, changed, &format!("unset {}", keys.join(","))).await
+}
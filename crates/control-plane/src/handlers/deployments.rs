@@ -14,18 +14,25 @@ pub async fn update_deployment(State(state): State<AppState>, axum::extract::Pat
         if matches!(e, sqlx::Error::RowNotFound) { return ApiError::not_found("deployment not found"); }
         ApiError::internal(format!("update error: {e}"))
     })?;
-    Ok(Json(DeploymentStatusResponse { id: dep.id, status: dep.status, digest: dep.digest, failure_reason: dep.failure_reason, artifact_url: dep.artifact_url, last_transition_at: dep.last_transition_at, signature: dep.signature }))
+    Ok(Json(status_response(&state.db, dep).await?))
 }
 use axum::{Json, http::StatusCode, extract::{State, Query}};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
-use crate::{AppState, models::Deployment, error::{ApiError, ApiResult, ApiErrorBody}, services};
+use crate::{AppState, models::{Deployment, DeploymentProcess, RolloutStrategy}, error::{ApiError, ApiResult, ApiErrorBody}, services};
 use sqlx::Row;
 // use sqlx::Row; // no longer needed after refactor
 
 #[derive(Deserialize, ToSchema)]
-pub struct CreateDeploymentRequest { pub app_name: String, pub artifact_url: String, pub signature: Option<String>, #[serde(default)] pub dev_hot: bool }
+pub struct CreateDeploymentRequest {
+    pub app_name: String,
+    pub artifact_url: String,
+    pub signature: Option<String>,
+    #[serde(default)] pub dev_hot: bool,
+    /// `rolling` (default), `blue_green` or `{"canary": {"percent": N}}` (1-99)
+    #[serde(default)] pub strategy: RolloutStrategy,
+}
 
 #[derive(Serialize, ToSchema)]
 pub struct CreateDeploymentResponse { pub id: Uuid, pub status: &'static str }
@@ -53,13 +60,13 @@ async fn verify_signature_if_present(db: &sqlx::Pool<sqlx::Postgres>, app_name:
     let Some(digest) = digest_opt else { return Err(ApiError::bad_request("signature provided but digest unavailable for verification")); };
     let sig_hex = signature.as_ref().unwrap();
     if sig_hex.len() != 128 || !sig_hex.chars().all(|c| c.is_ascii_hexdigit()) { return Err(ApiError::bad_request("signature must be 128 hex chars (ed25519)")); }
-    // Load active public keys for app
+    // Load active public keys for app (honouring not_before / expires_at)
     let row = sqlx::query("SELECT id FROM applications WHERE name=$1").bind(app_name).fetch_optional(db).await.map_err(|e| ApiError::internal(format!("lookup app: {e}")))?;
     let Some(app_id_row) = row else { return Err(ApiError::not_found("application not found")); };
     let app_id: uuid::Uuid = app_id_row.get("id");
-    let keys: Vec<(String,)> = sqlx::query_as("SELECT public_key_hex FROM public_keys WHERE app_id=$1 AND active=TRUE")
+    let keys: Vec<(String,)> = sqlx::query_as(services::keys::USABLE_KEYS_SQL)
         .bind(app_id).fetch_all(db).await.map_err(|e| ApiError::internal(format!("load keys: {e}")))?;
-    if keys.is_empty() { return Err(ApiError::bad_request("no active public keys for app to verify signature")); }
+    if keys.is_empty() { return Err(ApiError::bad_request("no active public keys (within validity window) for app to verify signature")); }
     // Perform ed25519 verification against digest bytes
     let sig_bytes = match hex::decode(sig_hex) { Ok(b) => b, Err(_) => return Err(ApiError::bad_request("invalid signature hex")) };
     use ed25519_dalek::{Signature, VerifyingKey, Verifier};
@@ -82,6 +89,9 @@ async fn verify_signature_if_present(db: &sqlx::Pool<sqlx::Postgres>, app_name:
 #[utoipa::path(post, path = "/deployments", request_body = CreateDeploymentRequest, responses( (status=201, body=CreateDeploymentResponse), (status=404, body=ApiErrorBody, description="app not found"), (status=400, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
 #[tracing::instrument(level="info", skip(state, req), fields(app_name=%req.app_name))]
 pub async fn create_deployment(State(state): State<AppState>, Json(req): Json<CreateDeploymentRequest>) -> ApiResult<(StatusCode, Json<CreateDeploymentResponse>)> {
+    if let RolloutStrategy::Canary { percent } = req.strategy {
+        if !(1..=99).contains(&percent) { return Err(ApiError::bad_request("canary percent must be between 1 and 99")); }
+    }
     let resolved_digest = resolve_digest(&state.db, &req.artifact_url).await;
     verify_signature_if_present(&state.db, &req.app_name, resolved_digest.as_deref(), &req.signature).await?;
     let deployment: Deployment = services::deployments::create_deployment(&state.db, &req.app_name, &req.artifact_url, resolved_digest.as_deref(), req.signature.as_deref())
@@ -89,24 +99,58 @@ pub async fn create_deployment(State(state): State<AppState>, Json(req): Json<Cr
             if matches!(e, sqlx::Error::RowNotFound) { return ApiError::not_found("application not found"); }
             ApiError::internal(format!("insert failure: {e}"))
         })?;
-    tracing::info!(deployment_id=%deployment.id, "deployment created");
-    // Fire-and-forget k8s apply using resolved digest (if any) with SHA256 verification.
-    let app_name = req.app_name.clone();
-    let artifact_url = req.artifact_url.clone();
-    let digest_opt = resolved_digest.clone();
-    let signature = req.signature.clone();
-    let dev_hot = req.dev_hot;
-    tokio::spawn(async move {
-        let digest = digest_opt.as_deref().unwrap_or("");
-        if let Err(e) = crate::k8s::apply_deployment(&app_name, digest, &artifact_url, "default", signature.as_deref(), dev_hot).await {
-            tracing::error!(error=%e, app=%app_name, "k8s apply failed");
-        } else {
-            tracing::info!(app=%app_name, "k8s apply scheduled");
-        }
-    });
+    if req.strategy != RolloutStrategy::Rolling {
+        services::rollouts::set_strategy(&state.db, deployment.id, req.strategy).await
+            .map_err(|e| ApiError::internal(format!("store strategy: {e}")))?;
+    }
+    tracing::info!(deployment_id=%deployment.id, strategy=req.strategy.as_str(), "deployment created");
+    services::apply_queue::enqueue(&state.db, deployment.id, req.dev_hot).await
+        .map_err(|e| ApiError::internal(format!("queue apply: {e}")))?;
     Ok((StatusCode::CREATED, Json(CreateDeploymentResponse { id: deployment.id, status: "pending" })))
 }
 
+#[derive(Deserialize, ToSchema, Default)]
+pub struct RollbackRequest { pub deployment_id: Option<Uuid>, pub digest: Option<String> }
+
+#[derive(Serialize, ToSchema)]
+pub struct RollbackResponse { pub id: Uuid, pub status: &'static str, pub rolled_back_to: Uuid, pub digest: Option<String> }
+
+/// Roll an application back to a previous running release.
+/// Without a body the most recent `running` deployment before the latest one is redeployed;
+/// `deployment_id` or `digest` pick a specific running release instead.
+#[utoipa::path(post, path = "/apps/{app_name}/rollback", request_body(content = Option<RollbackRequest>), params(("app_name" = String, Path, description = "Application name")), responses( (status=201, body=RollbackResponse), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=409, body=ApiErrorBody, description="no previous running release"), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, body), fields(app_name=%app_name))]
+pub async fn rollback_app(State(state): State<AppState>, axum::extract::Path(app_name): axum::extract::Path<String>, body: Option<Json<RollbackRequest>>) -> ApiResult<(StatusCode, Json<RollbackResponse>)> {
+    let req = body.map(|Json(b)| b).unwrap_or_default();
+    let target = match (req.deployment_id, req.digest) {
+        (Some(_), Some(_)) => return Err(ApiError::bad_request("specify either deployment_id or digest, not both")),
+        (Some(id), None) => services::deployments::RollbackTarget::Deployment(id),
+        (None, Some(d)) => {
+            let d = d.trim().to_lowercase();
+            if d.len()!=64 || !d.chars().all(|c| c.is_ascii_hexdigit()) { return Err(ApiError::bad_request("digest must be 64 hex chars")); }
+            services::deployments::RollbackTarget::Digest(d)
+        }
+        (None, None) => services::deployments::RollbackTarget::Previous,
+    };
+    let app_id: Uuid = sqlx::query_scalar("SELECT id FROM applications WHERE name=$1").bind(&app_name)
+        .fetch_optional(&state.db).await.map_err(|e| ApiError::internal(format!("lookup app: {e}")))?
+        .ok_or_else(|| ApiError::not_found("application not found"))?;
+    let source = services::deployments::find_rollback_target(&state.db, app_id, &target).await
+        .map_err(|e| ApiError::internal(format!("query error: {e}")))?
+        .ok_or_else(|| match target {
+            services::deployments::RollbackTarget::Previous => ApiError::conflict("no previous running deployment to roll back to"),
+            _ => ApiError::not_found("target running deployment not found"),
+        })?;
+    let deployment = services::deployments::create_rollback(&state.db, &app_name, &source).await.map_err(|e| {
+        if matches!(e, sqlx::Error::RowNotFound) { return ApiError::not_found("application not found"); }
+        ApiError::internal(format!("insert failure: {e}"))
+    })?;
+    tracing::info!(deployment_id=%deployment.id, source_id=%source.id, "rollback created");
+    services::apply_queue::enqueue(&state.db, deployment.id, false).await
+        .map_err(|e| ApiError::internal(format!("queue apply: {e}")))?;
+    Ok((StatusCode::CREATED, Json(RollbackResponse { id: deployment.id, status: "pending", rolled_back_to: source.id, digest: deployment.digest })))
+}
+
 #[derive(Deserialize, ToSchema)]
 pub struct DeploymentQuery { pub app_name: Option<String>, pub limit: Option<i64>, pub offset: Option<i64> }
 
@@ -139,16 +183,40 @@ pub struct DeploymentStatusResponse {
     pub artifact_url: String,
     pub last_transition_at: chrono::DateTime<chrono::Utc>,
     pub signature: Option<String>,
+    pub strategy: RolloutStrategy,
+    /// Phase of a blue/green or canary rollout: `progressing`, `awaiting_promotion`, `promoting`, `promoted` or `aborted`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollout_phase: Option<String>,
+    pub progress: DeploymentProgress,
 }
 
-#[utoipa::path(get, path="/deployments/{id}", params( ("id" = Uuid, Path, description="Deployment ID") ), responses( (status=200, body=DeploymentStatusResponse), (status=404, body=ApiErrorBody) ))]
-#[tracing::instrument(level="debug", skip(state))]
-pub async fn get_deployment(State(state): State<AppState>, axum::extract::Path(id): axum::extract::Path<Uuid>) -> ApiResult<Json<DeploymentStatusResponse>> {
-    let dep = services::deployments::get_deployment(&state.db, id).await.map_err(|e| {
-        if matches!(e, sqlx::Error::RowNotFound) { return ApiError::not_found("deployment not found"); }
-        ApiError::internal(format!("query error: {e}"))
-    })?;
-    Ok(Json(DeploymentStatusResponse {
+/// Live rollout progress as last seen by the status watcher, summed over the Deployments the release is rendered into.
+#[derive(Serialize, ToSchema, Default)]
+pub struct DeploymentProgress {
+    pub desired_replicas: i32,
+    pub updated_replicas: i32,
+    pub ready_replicas: i32,
+    pub available_replicas: i32,
+    /// One entry per process Deployment, with its pods
+    pub processes: Vec<DeploymentProcess>,
+}
+
+impl From<Vec<DeploymentProcess>> for DeploymentProgress {
+    fn from(processes: Vec<DeploymentProcess>) -> Self {
+        DeploymentProgress {
+            desired_replicas: processes.iter().map(|p| p.desired_replicas).sum(),
+            updated_replicas: processes.iter().map(|p| p.updated_replicas).sum(),
+            ready_replicas: processes.iter().map(|p| p.ready_replicas).sum(),
+            available_replicas: processes.iter().map(|p| p.available_replicas).sum(),
+            processes,
+        }
+    }
+}
+
+async fn status_response(db: &sqlx::Pool<sqlx::Postgres>, dep: Deployment) -> ApiResult<DeploymentStatusResponse> {
+    let rollout = services::rollouts::state(db, dep.id).await.map_err(|e| ApiError::internal(format!("query error: {e}")))?;
+    let processes = services::processes::list_for_deployment(db, dep.id).await.map_err(|e| ApiError::internal(format!("query processes: {e}")))?;
+    Ok(DeploymentStatusResponse {
         id: dep.id,
         status: dep.status,
         digest: dep.digest,
@@ -156,5 +224,55 @@ pub async fn get_deployment(State(state): State<AppState>, axum::extract::Path(i
         artifact_url: dep.artifact_url,
         last_transition_at: dep.last_transition_at,
         signature: dep.signature,
-    }))
+        strategy: rollout.strategy,
+        rollout_phase: rollout.phase,
+        progress: processes.into(),
+    })
+}
+
+#[utoipa::path(get, path="/deployments/{id}", params( ("id" = Uuid, Path, description="Deployment ID") ), responses( (status=200, body=DeploymentStatusResponse), (status=404, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state))]
+pub async fn get_deployment(State(state): State<AppState>, axum::extract::Path(id): axum::extract::Path<Uuid>) -> ApiResult<Json<DeploymentStatusResponse>> {
+    let dep = services::deployments::get_deployment(&state.db, id).await.map_err(|e| {
+        if matches!(e, sqlx::Error::RowNotFound) { return ApiError::not_found("deployment not found"); }
+        ApiError::internal(format!("query error: {e}"))
+    })?;
+    Ok(Json(status_response(&state.db, dep).await?))
+}
+
+/// Load a deployment with its app name (404 when missing).
+async fn deployment_with_app(db: &sqlx::Pool<sqlx::Postgres>, id: Uuid) -> ApiResult<(Deployment, String)> {
+    let dep = services::deployments::get_deployment(db, id).await.map_err(|e| {
+        if matches!(e, sqlx::Error::RowNotFound) { return ApiError::not_found("deployment not found"); }
+        ApiError::internal(format!("query error: {e}"))
+    })?;
+    let app_name: String = sqlx::query_scalar("SELECT name FROM applications WHERE id=$1").bind(dep.app_id)
+        .fetch_one(db).await.map_err(|e| ApiError::internal(format!("lookup app: {e}")))?;
+    Ok((dep, app_name))
+}
+
+/// Promote the candidate of a blue/green or canary deployment once it is ready (`awaiting_promotion`): traffic moves to
+/// it, the app's Deployments roll to the release, and the candidate is removed when they are ready.
+#[utoipa::path(post, path="/deployments/{id}/promote", params( ("id" = Uuid, Path, description="Deployment ID") ), responses( (status=202, body=DeploymentStatusResponse), (status=404, body=ApiErrorBody), (status=409, body=ApiErrorBody, description="not a candidate awaiting promotion"), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state))]
+pub async fn promote_deployment(State(state): State<AppState>, axum::extract::Path(id): axum::extract::Path<Uuid>) -> ApiResult<(StatusCode, Json<DeploymentStatusResponse>)> {
+    let (dep, app_name) = deployment_with_app(&state.db, id).await?;
+    let promoted = services::rollouts::promote(&state.db, &app_name, &dep).await.map_err(|e| ApiError::internal(format!("promote: {e}")))?;
+    if !promoted { return Err(ApiError::conflict("deployment is not a candidate awaiting promotion")); }
+    tracing::info!(deployment_id=%id, app=%app_name, "candidate promotion started");
+    let dep = services::deployments::get_deployment(&state.db, id).await.map_err(|e| ApiError::internal(format!("query error: {e}")))?;
+    Ok((StatusCode::ACCEPTED, Json(status_response(&state.db, dep).await?)))
+}
+
+/// Abort the candidate of a blue/green or canary deployment before promotion: the deployment fails with `aborted`,
+/// traffic stays on (or returns to) the serving release and the candidate is torn down.
+#[utoipa::path(post, path="/deployments/{id}/abort", params( ("id" = Uuid, Path, description="Deployment ID") ), responses( (status=202, body=DeploymentStatusResponse), (status=404, body=ApiErrorBody), (status=409, body=ApiErrorBody, description="no candidate in progress"), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state))]
+pub async fn abort_deployment(State(state): State<AppState>, axum::extract::Path(id): axum::extract::Path<Uuid>) -> ApiResult<(StatusCode, Json<DeploymentStatusResponse>)> {
+    let (dep, app_name) = deployment_with_app(&state.db, id).await?;
+    let aborted = services::rollouts::abort(&state.db, &dep).await.map_err(|e| ApiError::internal(format!("abort: {e}")))?;
+    if !aborted { return Err(ApiError::conflict("deployment has no candidate in progress")); }
+    tracing::info!(deployment_id=%id, app=%app_name, "candidate aborted");
+    let dep = services::deployments::get_deployment(&state.db, id).await.map_err(|e| ApiError::internal(format!("query error: {e}")))?;
+    Ok((StatusCode::ACCEPTED, Json(status_response(&state.db, dep).await?)))
 }
@@ -0,0 +1,64 @@
+use axum::{Json, extract::{Path, State}, http::StatusCode};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use crate::{AppState, models::Application, error::{ApiError, ApiResult, ApiErrorBody}, services::{self, domains::AppDomain}};
+
+#[derive(Serialize, ToSchema)]
+pub struct AppDomainsResp {
+    /// Generated `<app>.<AETHER_INGRESS_DOMAIN>` host (absent when no ingress domain is configured)
+    pub default_host: Option<String>,
+    pub domains: Vec<AppDomain>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AddDomainReq {
+    pub hostname: String,
+    /// Path prefix (default `/`)
+    #[serde(default)] pub path: Option<String>,
+}
+
+async fn load_app(state: &AppState, app_name: &str) -> ApiResult<Application> {
+    sqlx::query_as::<_, Application>("SELECT id, name, created_at, updated_at, namespace FROM applications WHERE name=$1")
+        .bind(app_name).fetch_optional(&state.db).await.map_err(|e| ApiError::internal(format!("query app: {e}")))?
+        .ok_or_else(|| ApiError::not_found("application not found"))
+}
+
+/// List custom domains routed to the application
+#[utoipa::path(get, path = "/apps/{app_name}/domains", params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=AppDomainsResp), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state), fields(app_name=%app_name))]
+pub async fn list_domains(State(state): State<AppState>, Path(app_name): Path<String>) -> ApiResult<Json<AppDomainsResp>> {
+    let app = load_app(&state, &app_name).await?;
+    let domains = services::domains::list_domains(&state.db, app.id).await.map_err(|e| ApiError::internal(format!("query domains: {e}")))?;
+    let default_host = services::domains::ingress_domain().map(|d| format!("{}.{d}", app.name));
+    Ok(Json(AppDomainsResp { default_host, domains }))
+}
+
+/// Route a custom hostname (and optional path prefix) to the application via its Ingress
+#[utoipa::path(post, path = "/apps/{app_name}/domains", request_body = AddDomainReq, params(("app_name"=String, Path, description="Application name")), responses( (status=201, body=AppDomain), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=409, body=ApiErrorBody, description="hostname/path already routed"), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, body), fields(app_name=%app_name, hostname=%body.hostname))]
+pub async fn add_domain(State(state): State<AppState>, Path(app_name): Path<String>, Json(body): Json<AddDomainReq>) -> ApiResult<(StatusCode, Json<AppDomain>)> {
+    let hostname = body.hostname.trim().to_ascii_lowercase();
+    if !services::domains::valid_hostname(&hostname) { return Err(ApiError::bad_request("hostname must be a fully qualified DNS name")); }
+    let path = body.path.as_deref().map(str::trim).filter(|p| !p.is_empty()).unwrap_or("/");
+    if !services::domains::valid_path(path) { return Err(ApiError::bad_request("path must start with '/'")); }
+    let app = load_app(&state, &app_name).await?;
+    let domain = services::domains::add_domain(&state.db, app.id, &hostname, path).await.map_err(|e| {
+        if let Some(db_code) = e.as_database_error().and_then(|d| d.code()) { if db_code == "23505" { return ApiError::conflict("hostname and path already routed"); } }
+        ApiError::internal(format!("insert domain: {e}"))
+    })?;
+    services::domains::spawn_sync_routes(&state.db, &app);
+    tracing::info!(app_id=%app.id, path=%domain.path, "domain added");
+    Ok((StatusCode::CREATED, Json(domain)))
+}
+
+/// Stop routing a hostname (all of its paths) to the application
+#[utoipa::path(delete, path = "/apps/{app_name}/domains/{hostname}", params(("app_name"=String, Path, description="Application name"), ("hostname"=String, Path, description="Hostname")), responses( (status=204), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state), fields(app_name=%app_name, hostname=%hostname))]
+pub async fn delete_domain(State(state): State<AppState>, Path((app_name, hostname)): Path<(String, String)>) -> ApiResult<StatusCode> {
+    let app = load_app(&state, &app_name).await?;
+    let removed = services::domains::remove_domain(&state.db, app.id, &hostname.to_ascii_lowercase()).await
+        .map_err(|e| ApiError::internal(format!("delete domain: {e}")))?;
+    if !removed { return Err(ApiError::not_found("domain not found")); }
+    services::domains::spawn_sync_routes(&state.db, &app);
+    Ok(StatusCode::NO_CONTENT)
+}
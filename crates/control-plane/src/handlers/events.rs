@@ -0,0 +1,133 @@
+use axum::{Json, extract::{Path, State, Query}};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use chrono::{DateTime, Utc};
+use crate::{AppState, models::{DeploymentEvent, ArtifactEvent, AppEvent}, error::{ApiError, ApiResult, ApiErrorBody}, services::{self, events::EventFilter, deployments::DeploymentUpdate}, event_bus::BusEvent};
+use axum::response::{IntoResponse, Response, sse::{Event, KeepAlive, Sse}};
+use futures_util::StreamExt;
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
+
+#[derive(Deserialize, ToSchema)]
+pub struct EventsQuery { pub limit: Option<i64>, pub offset: Option<i64>, pub since: Option<String>, pub until: Option<String> }
+
+fn parse_ts(name: &str, v: Option<&str>) -> ApiResult<Option<DateTime<Utc>>> {
+    match v {
+        None => Ok(None),
+        Some(s) => DateTime::parse_from_rfc3339(s).map(|t| Some(t.with_timezone(&Utc)))
+            .map_err(|_| ApiError::bad_request(format!("{name} must be an RFC3339 timestamp"))),
+    }
+}
+
+impl EventsQuery {
+    fn filter(&self) -> ApiResult<EventFilter> {
+        let since = parse_ts("since", self.since.as_deref())?;
+        let until = parse_ts("until", self.until.as_deref())?;
+        if let (Some(s), Some(u)) = (since, until) { if s > u { return Err(ApiError::bad_request("since must not be after until")); } }
+        Ok(EventFilter { since, until, limit: self.limit.unwrap_or(100).clamp(1, 1000), offset: self.offset.unwrap_or(0).max(0) })
+    }
+}
+
+fn map_err(what: &'static str) -> impl Fn(sqlx::Error) -> ApiError {
+    move |e| {
+        if matches!(e, sqlx::Error::RowNotFound) { return ApiError::not_found(format!("{what} not found")); }
+        ApiError::internal(format!("query error: {e}"))
+    }
+}
+
+/// List events recorded for a deployment (status transitions, digest updates, rollbacks), newest first
+#[utoipa::path(get, path = "/deployments/{id}/events", params( ("id" = uuid::Uuid, Path, description = "Deployment ID"), ("limit" = Option<i64>, Query, description="Max items (default 100, max 1000)"), ("offset" = Option<i64>, Query, description="Offset"), ("since" = Option<String>, Query, description="RFC3339 lower bound (inclusive)"), ("until" = Option<String>, Query, description="RFC3339 upper bound (inclusive)") ), responses( (status=200, body=[DeploymentEvent]), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state, q), fields(deployment_id=%id, limit=?q.limit, offset=?q.offset))]
+pub async fn deployment_events(State(state): State<AppState>, Path(id): Path<uuid::Uuid>, Query(q): Query<EventsQuery>) -> ApiResult<Json<Vec<DeploymentEvent>>> {
+    let f = q.filter()?;
+    let rows = services::events::list_deployment_events(&state.db, id, &f).await.map_err(map_err("deployment"))?;
+    Ok(Json(rows))
+}
+
+/// List lifecycle events recorded for an artifact, newest first
+#[utoipa::path(get, path = "/artifacts/{digest}/events", params( ("digest" = String, Path, description = "Artifact sha256 digest"), ("limit" = Option<i64>, Query, description="Max items (default 100, max 1000)"), ("offset" = Option<i64>, Query, description="Offset"), ("since" = Option<String>, Query, description="RFC3339 lower bound (inclusive)"), ("until" = Option<String>, Query, description="RFC3339 upper bound (inclusive)") ), responses( (status=200, body=[ArtifactEvent]), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state, q), fields(digest=%digest, limit=?q.limit, offset=?q.offset))]
+pub async fn artifact_events(State(state): State<AppState>, Path(digest): Path<String>, Query(q): Query<EventsQuery>) -> ApiResult<Json<Vec<ArtifactEvent>>> {
+    if digest.len()!=64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) { return Err(ApiError::bad_request("digest must be 64 hex chars")); }
+    let f = q.filter()?;
+    let rows = services::events::list_artifact_events(&state.db, &digest, &f).await.map_err(map_err("artifact"))?;
+    Ok(Json(rows))
+}
+
+/// Application timeline: deployment and artifact events merged, newest first
+#[utoipa::path(get, path = "/apps/{app_name}/events", params( ("app_name" = String, Path, description = "Application name"), ("limit" = Option<i64>, Query, description="Max items (default 100, max 1000)"), ("offset" = Option<i64>, Query, description="Offset"), ("since" = Option<String>, Query, description="RFC3339 lower bound (inclusive)"), ("until" = Option<String>, Query, description="RFC3339 upper bound (inclusive)") ), responses( (status=200, body=[AppEvent]), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state, q), fields(app_name=%app_name, limit=?q.limit, offset=?q.offset))]
+pub async fn app_events(State(state): State<AppState>, Path(app_name): Path<String>, Query(q): Query<EventsQuery>) -> ApiResult<Json<Vec<AppEvent>>> {
+    let f = q.filter()?;
+    let rows = services::events::list_app_events(&state.db, &app_name, &f).await.map_err(map_err("application"))?;
+    Ok(Json(rows))
+}
+
+/// `status` events carry status transitions, `event` events new `deployment_events` rows (SSE id = event id).
+fn sse_event(update: &DeploymentUpdate) -> Event {
+    let (name, id) = match update {
+        DeploymentUpdate::Status { .. } => ("status", None),
+        DeploymentUpdate::Event { id, .. } => ("event", Some(*id)),
+    };
+    let ev = Event::default().event(name).json_data(update).unwrap_or_else(|e| Event::default().event("error").data(e.to_string()));
+    match id { Some(id) => ev.id(id.to_string()), None => ev }
+}
+
+fn is_terminal(status: &str) -> bool { matches!(status, "running" | "failed") }
+
+fn status_snapshot(dep: &crate::models::Deployment) -> DeploymentUpdate {
+    DeploymentUpdate::Status { deployment_id: dep.id, app_id: dep.app_id, status: dep.status.clone(), failure_reason: dep.failure_reason.clone(), at: dep.last_transition_at }
+}
+
+/// Live status of a deployment as Server-Sent Events: the current status first, then status transitions and new
+/// deployment events until it is `running` or `failed` (the stream then ends)
+#[utoipa::path(get, path = "/deployments/{id}/watch", params( ("id" = uuid::Uuid, Path, description = "Deployment ID") ), responses( (status=200, description="SSE stream of `status` and `event` events", content_type = "text/event-stream"), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state), fields(deployment_id=%id))]
+pub async fn watch_deployment(State(state): State<AppState>, Path(id): Path<uuid::Uuid>) -> ApiResult<Response> {
+    // subscribe before reading the row so no transition falls between the snapshot and the stream
+    let rx = crate::event_bus::subscribe();
+    let dep = services::deployments::get_deployment(&state.db, id).await.map_err(map_err("deployment"))?;
+    let done = is_terminal(&dep.status);
+    let updates = futures_util::stream::unfold((rx, done, state.db.clone()), move |(mut rx, done, db)| async move {
+        if done { return None; }
+        loop {
+            let update = match rx.recv().await {
+                Ok(BusEvent::Deployment(u)) if u.deployment_id() == id => u,
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    // updates were dropped: resynchronise from the row
+                    tracing::warn!(skipped, deployment_id=%id, "deployment_watch_lagged");
+                    match services::deployments::get_deployment(&db, id).await { Ok(d) => status_snapshot(&d), Err(_) => return None }
+                }
+                Err(RecvError::Closed) => return None,
+            };
+            let done = matches!(&update, DeploymentUpdate::Status { status, .. } if is_terminal(status));
+            return Some((update, (rx, done, db)));
+        }
+    });
+    let events = futures_util::stream::once(async move { status_snapshot(&dep) }).chain(updates)
+        .map(|u| Ok::<_, Infallible>(sse_event(&u)));
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()).into_response())
+}
+
+/// Live deployment activity of an application as Server-Sent Events: status transitions and new deployment events of
+/// all its deployments (the stream stays open)
+#[utoipa::path(get, path = "/apps/{app_name}/events/stream", params( ("app_name" = String, Path, description = "Application name") ), responses( (status=200, description="SSE stream of `status` and `event` events", content_type = "text/event-stream"), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state), fields(app_name=%app_name))]
+pub async fn app_event_stream(State(state): State<AppState>, Path(app_name): Path<String>) -> ApiResult<Response> {
+    let rx = crate::event_bus::subscribe();
+    let app_id: uuid::Uuid = sqlx::query_scalar("SELECT id FROM applications WHERE name=$1").bind(&app_name)
+        .fetch_optional(&state.db).await.map_err(|e| ApiError::internal(format!("lookup app: {e}")))?
+        .ok_or_else(|| ApiError::not_found("application not found"))?;
+    let events = futures_util::stream::unfold(rx, move |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(BusEvent::Deployment(u)) if u.app_id() == app_id => return Some((u, rx)),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => tracing::warn!(skipped, %app_id, "app_event_stream_lagged"),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }).map(|u| Ok::<_, Infallible>(sse_event(&u)));
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()).into_response())
+}
@@ -3,3 +3,9 @@ pub mod deployments;
 pub mod uploads;
 pub mod apps;
 pub mod readiness;
+pub mod events;
+pub mod config;
+pub mod secrets;
+pub mod domains;
+pub mod runtimes;
+pub mod webhooks;
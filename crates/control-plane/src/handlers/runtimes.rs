@@ -0,0 +1,8 @@
+use axum::Json;
+use crate::runtime::{self, Runtime};
+
+/// List the runtimes apps can be pinned to (`PATCH /apps/{app_name}` `runtime`)
+#[utoipa::path(get, path = "/runtimes", responses( (status=200, body=[Runtime]) ))]
+pub async fn list_runtimes() -> Json<Vec<Runtime>> {
+    Json(runtime::RUNTIMES.to_vec())
+}
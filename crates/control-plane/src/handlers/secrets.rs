@@ -0,0 +1,79 @@
+use axum::{Json, extract::{Path, State}, http::StatusCode};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use crate::{AppState, error::{ApiError, ApiResult, ApiErrorBody}, services::{self, secrets::SecretMeta}, secrets::Keyring};
+
+#[derive(Deserialize, ToSchema)]
+pub struct PutSecretReq { pub value: String }
+
+#[derive(Serialize, ToSchema)]
+pub struct SecretChangeResp {
+    pub name: String,
+    /// Deployment created to roll out the change (absent when nothing is deployed yet)
+    pub rollout_deployment_id: Option<uuid::Uuid>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RotateMasterKeyResp { pub active_master_key_id: String, pub rewrapped: usize }
+
+pub(crate) fn keyring() -> ApiResult<Keyring> {
+    match Keyring::from_env() {
+        Ok(Some(k)) => Ok(k),
+        Ok(None) => Err(ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "secrets_unavailable", "secrets store not configured (AETHER_MASTER_KEY_FILE)")),
+        Err(e) => Err(ApiError::internal(format!("master key: {e}"))),
+    }
+}
+
+async fn app_id(state: &AppState, app_name: &str) -> ApiResult<uuid::Uuid> {
+    sqlx::query_scalar("SELECT id FROM applications WHERE name=$1").bind(app_name)
+        .fetch_optional(&state.db).await.map_err(|e| ApiError::internal(format!("lookup app: {e}")))?
+        .ok_or_else(|| ApiError::not_found("application not found"))
+}
+
+/// List secret names of an application (values are never returned)
+#[utoipa::path(get, path = "/apps/{app_name}/secrets", params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=[SecretMeta]), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state), fields(app_name=%app_name))]
+pub async fn list_secrets(State(state): State<AppState>, Path(app_name): Path<String>) -> ApiResult<Json<Vec<SecretMeta>>> {
+    let app_id = app_id(&state, &app_name).await?;
+    let rows = services::secrets::list_secrets(&state.db, app_id).await.map_err(|e| ApiError::internal(format!("query error: {e}")))?;
+    Ok(Json(rows))
+}
+
+/// Set a secret (encrypted at rest) and roll out the current release with it
+#[utoipa::path(put, path = "/apps/{app_name}/secrets/{name}", request_body = PutSecretReq, params(("app_name"=String, Path, description="Application name"), ("name"=String, Path, description="Secret (env var) name")), responses( (status=200, body=SecretChangeResp), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=503, body=ApiErrorBody, description="secrets store not configured"), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, body), fields(app_name=%app_name, secret=%name))]
+pub async fn put_secret(State(state): State<AppState>, Path((app_name, name)): Path<(String, String)>, Json(body): Json<PutSecretReq>) -> ApiResult<Json<SecretChangeResp>> {
+    if !services::config::valid_key(&name) { return Err(ApiError::bad_request("invalid secret name (env var name, AETHER_ prefix reserved)")); }
+    if body.value.len() > 32 * 1024 { return Err(ApiError::bad_request("secret values are limited to 32KiB")); }
+    let keyring = keyring()?;
+    let app_id = app_id(&state, &app_name).await?;
+    services::secrets::put_secret(&state.db, &keyring, app_id, &name, &body.value).await
+        .map_err(|e| ApiError::internal(format!("store secret: {e}")))?;
+    let rollout = services::deployments::redeploy_current(&state.db, &app_name, app_id, "secrets_changed", &format!("set {name}")).await
+        .map_err(|e| ApiError::internal(format!("rollout: {e}")))?;
+    tracing::info!(app_id=%app_id, "secret stored");
+    Ok(Json(SecretChangeResp { name, rollout_deployment_id: rollout.map(|d| d.id) }))
+}
+
+/// Delete a secret and roll out the current release without it
+#[utoipa::path(delete, path = "/apps/{app_name}/secrets/{name}", params(("app_name"=String, Path, description="Application name"), ("name"=String, Path, description="Secret name")), responses( (status=200, body=SecretChangeResp), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state), fields(app_name=%app_name, secret=%name))]
+pub async fn delete_secret(State(state): State<AppState>, Path((app_name, name)): Path<(String, String)>) -> ApiResult<Json<SecretChangeResp>> {
+    let app_id = app_id(&state, &app_name).await?;
+    let removed = services::secrets::delete_secret(&state.db, app_id, &name).await.map_err(|e| ApiError::internal(format!("delete secret: {e}")))?;
+    if !removed { return Err(ApiError::not_found("secret not found")); }
+    let rollout = services::deployments::redeploy_current(&state.db, &app_name, app_id, "secrets_changed", &format!("unset {name}")).await
+        .map_err(|e| ApiError::internal(format!("rollout: {e}")))?;
+    Ok(Json(SecretChangeResp { name, rollout_deployment_id: rollout.map(|d| d.id) }))
+}
+
+/// Re-wrap all app data keys with the active master key (first key of the keyring file)
+#[utoipa::path(post, path = "/secrets/rotate-master-key", responses( (status=200, body=RotateMasterKeyResp), (status=503, body=ApiErrorBody, description="secrets store not configured"), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state))]
+pub async fn rotate_master_key(State(state): State<AppState>) -> ApiResult<Json<RotateMasterKeyResp>> {
+    let keyring = keyring()?;
+    let rewrapped = services::secrets::rewrap_data_keys(&state.db, &keyring).await.map_err(|e| ApiError::internal(format!("rewrap: {e}")))?;
+    let active_master_key_id = keyring.active().id.clone();
+    tracing::info!(active=%active_master_key_id, rewrapped, "master key rotation");
+    Ok(Json(RotateMasterKeyResp { active_master_key_id, rewrapped }))
+}
@@ -182,7 +182,13 @@ pub async fn presign_artifact(State(state): State<AppState>, Json(req): Json<Pre
 }
 
 #[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
-pub struct CompleteRequest { pub app_name: String, pub digest: String, pub size_bytes: i64, pub signature: Option<String>, pub idempotency_key: Option<String> }
+pub struct CompleteRequest {
+    pub app_name: String, pub digest: String, pub size_bytes: i64, pub signature: Option<String>, pub idempotency_key: Option<String>,
+    /// `aether-sbom-v1` document (without the file list); used to detect the app runtime
+    #[serde(default)] #[schema(value_type = Option<Object>)] pub sbom: Option<serde_json::Value>,
+    /// Start command hints (`scripts.start`, `main`, `engines.node`, Procfile `web:`) used to start the container
+    #[serde(default)] pub metadata: Option<crate::models::ArtifactMetadata>,
+}
 
 #[derive(serde::Serialize, utoipa::ToSchema)]
 pub struct CompleteResponse { pub artifact_id: String, pub digest: String, pub duplicate: bool, pub verified: bool, pub storage_key: String, pub status: String, pub idempotency_key: Option<String> }
@@ -232,6 +238,7 @@ pub async fn complete_artifact(State(state): State<AppState>, headers: HeaderMap
     }
     if let Some((id, verified_prev, sk, status)) = &existing {
         if status == "stored" {
+            save_hints(&mut conn, &req.digest, req.sbom.as_ref(), req.metadata.as_ref()).await;
             if let Some(dh) = headers.get("X-Aether-Upload-Duration").and_then(|v| v.to_str().ok()) { if let Ok(vf)=dh.parse::<f64>() { ARTIFACT_PUT_DURATION.observe(vf); } }
             return (StatusCode::OK, Json(CompleteResponse {
                 artifact_id: id.to_string(),
@@ -302,6 +309,7 @@ pub async fn complete_artifact(State(state): State<AppState>, headers: HeaderMap
                 Ok(r) => {
                     let final_verified: bool = r.get(0);
                     let idem: Option<String> = r.get(1);
+                    save_hints(&mut conn, &req.digest, req.sbom.as_ref(), req.metadata.as_ref()).await;
                     insert_event(&mut conn, *id, "stored").await.ok();
                     retention_gc_if_needed(&mut conn, app_id).await.ok();
                     if let Some(dh) = headers.get("X-Aether-Upload-Duration").and_then(|v| v.to_str().ok()) { if let Ok(vf)=dh.parse::<f64>() { ARTIFACT_PUT_DURATION.observe(vf); } }
@@ -331,7 +339,7 @@ pub async fn complete_artifact(State(state): State<AppState>, headers: HeaderMap
                 .bind(&req.app_name)
                 .fetch_optional(pg(&mut conn)).await {
                 if let Ok(rows) = sqlx::query_scalar::<_, String>(
-                    "SELECT public_key_hex FROM public_keys WHERE app_id=$1 AND active")
+                    crate::services::keys::USABLE_KEYS_SQL)
                     .bind(app_uuid)
                     .fetch_all(pg(&mut conn)).await {
                     for pk_hex in rows {
@@ -379,6 +387,7 @@ pub async fn complete_artifact(State(state): State<AppState>, headers: HeaderMap
             ARTIFACTS_TOTAL.inc();
             COMPLETE_DURATION.observe(start.elapsed().as_secs_f64());
             if let Some(dh) = headers.get("X-Aether-Upload-Duration").and_then(|v| v.to_str().ok()) { if let Ok(vf)=dh.parse::<f64>() { ARTIFACT_PUT_DURATION.observe(vf); } }
+            save_hints(&mut conn, &req.digest, req.sbom.as_ref(), req.metadata.as_ref()).await;
             insert_event(&mut conn, id, "stored").await.ok();
             retention_gc_if_needed(&mut conn, app_id).await.ok();
             (StatusCode::OK, Json(CompleteResponse {
@@ -498,7 +507,7 @@ pub async fn upload_artifact(State(state): State<AppState>, headers: HeaderMap,
             if let Ok(Some(app_uuid)) = sqlx::query_scalar::<_, uuid::Uuid>("SELECT id FROM applications WHERE name=$1")
                 .bind(&app)
                 .fetch_optional(pg(&mut conn)).await {
-                if let Ok(rows) = sqlx::query_scalar::<_, String>("SELECT public_key_hex FROM public_keys WHERE app_id=$1 AND active")
+                if let Ok(rows) = sqlx::query_scalar::<_, String>(crate::services::keys::USABLE_KEYS_SQL)
                     .bind(app_uuid)
                     .fetch_all(pg(&mut conn)).await {
                     for pk_hex in rows {
@@ -543,10 +552,14 @@ pub async fn upload_artifact(State(state): State<AppState>, headers: HeaderMap,
 pub async fn init_artifacts_total(db: &sqlx::Pool<sqlx::Postgres>) {
     static INIT: once_cell::sync::OnceCell<()> = once_cell::sync::OnceCell::new();
     if INIT.get().is_some() { return; }
-    if let Ok(count) = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM artifacts").fetch_one(db).await { 
-        let g = once_cell::sync::Lazy::force(&ARTIFACTS_TOTAL);
-    g.set(count);
-        INIT.set(()).ok();
+    if refresh_artifacts_total(db).await { INIT.set(()).ok(); }
+}
+
+/// Recount artifacts_total from the database (e.g. after another replica stored or deleted artifacts); false if the count failed.
+pub async fn refresh_artifacts_total(db: &sqlx::Pool<sqlx::Postgres>) -> bool {
+    match sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM artifacts").fetch_one(db).await {
+        Ok(count) => { once_cell::sync::Lazy::force(&ARTIFACTS_TOTAL).set(count); true }
+        Err(_) => false,
     }
 }
 
@@ -574,13 +587,32 @@ pub async fn artifact_meta(State(state): State<AppState>, Path(digest): Path<Str
     }
 }
 
+/// Store the CLI-provided SBOM and start metadata on first completion (never overwritten afterwards: the digest pins the content).
+async fn save_hints(conn: &mut PoolConnection<sqlx::Postgres>, digest: &str, sbom: Option<&serde_json::Value>, metadata: Option<&crate::models::ArtifactMetadata>) {
+    if let Some(sbom) = sbom.filter(|v| v.is_object()) {
+        if let Err(e) = sqlx::query("UPDATE artifacts SET sbom=$2 WHERE digest=$1 AND sbom IS NULL").bind(digest).bind(sbom).execute(pg(&mut *conn)).await {
+            warn!(?e, digest, "save_sbom_failed");
+        }
+    }
+    if let Some(meta) = metadata.filter(|m| **m != crate::models::ArtifactMetadata::default()) {
+        if let Err(e) = sqlx::query("UPDATE artifacts SET metadata=$2 WHERE digest=$1 AND metadata IS NULL").bind(digest).bind(sqlx::types::Json(meta)).execute(pg(conn)).await {
+            warn!(?e, digest, "save_metadata_failed");
+        }
+    }
+}
+
 /// Insert artifact event (best-effort)
 async fn insert_event(conn: &mut PoolConnection<sqlx::Postgres>, artifact_id: Uuid, event_type: &str) -> anyhow::Result<()> {
-    let _ = sqlx::query("INSERT INTO artifact_events (artifact_id, event_type) VALUES ($1,$2)")
+    let app_id: Option<Option<Uuid>> = sqlx::query_scalar("INSERT INTO artifact_events (artifact_id, event_type) VALUES ($1,$2) RETURNING (SELECT app_id FROM artifacts WHERE id=$1)")
         .bind(artifact_id)
         .bind(event_type)
-        .execute(pg(conn)).await;
+        .fetch_one(pg(conn)).await.ok();
     ARTIFACT_EVENTS_TOTAL.inc();
+    if let Some(app_id) = app_id {
+        let update = crate::event_bus::ArtifactUpdate { artifact_id, app_id, event_type: event_type.to_string() };
+        crate::event_bus::publish(pg(conn), crate::event_bus::BusEvent::Artifact(update)).await;
+    }
+    crate::services::webhooks::artifact_event(pg(conn), artifact_id, event_type).await;
     Ok(())
 }
 
@@ -692,7 +724,12 @@ pub async fn multipart_presign_part(State(state): State<AppState>, Json(req): Js
 #[derive(serde::Deserialize, utoipa::ToSchema)]
 pub struct MultipartPartEtag { pub part_number: i32, pub etag: String }
 #[derive(serde::Deserialize, utoipa::ToSchema)]
-pub struct MultipartCompleteRequest { pub app_name: String, pub digest: String, pub upload_id: String, pub size_bytes: i64, pub parts: Vec<MultipartPartEtag>, pub signature: Option<String>, pub idempotency_key: Option<String> }
+pub struct MultipartCompleteRequest {
+    pub app_name: String, pub digest: String, pub upload_id: String, pub size_bytes: i64, pub parts: Vec<MultipartPartEtag>, pub signature: Option<String>, pub idempotency_key: Option<String>,
+    #[serde(default)] #[schema(value_type = Option<Object>)] pub sbom: Option<serde_json::Value>,
+    /// Start command hints (`scripts.start`, `main`, `engines.node`, Procfile `web:`) used to start the container
+    #[serde(default)] pub metadata: Option<crate::models::ArtifactMetadata>,
+}
 #[derive(serde::Serialize, utoipa::ToSchema)]
 pub struct MultipartCompleteResponse { pub status: String, pub storage_key: String, pub digest: String }
 
@@ -749,7 +786,7 @@ pub async fn multipart_complete(State(state): State<AppState>, Json(req): Json<M
         .bind(id)
         .bind(&req.idempotency_key)
     .fetch_one(pg(&mut conn)).await;
-    match upd { Ok(_)=> { MULTIPART_COMPLETES_TOTAL.inc(); insert_event(&mut conn, id, "stored").await.ok(); retention_gc_if_needed(&mut conn, app_id).await.ok(); (StatusCode::OK, Json(MultipartCompleteResponse { status: "stored".into(), storage_key, digest: req.digest })).into_response() }, Err(e)=> { MULTIPART_COMPLETE_FAILURES_TOTAL.inc(); error!(?e, "multipart_complete_update_failed"); ApiError::internal("db update").into_response() } }
+    match upd { Ok(_)=> { MULTIPART_COMPLETES_TOTAL.inc(); save_hints(&mut conn, &req.digest, req.sbom.as_ref(), req.metadata.as_ref()).await; insert_event(&mut conn, id, "stored").await.ok(); retention_gc_if_needed(&mut conn, app_id).await.ok(); (StatusCode::OK, Json(MultipartCompleteResponse { status: "stored".into(), storage_key, digest: req.digest })).into_response() }, Err(e)=> { MULTIPART_COMPLETE_FAILURES_TOTAL.inc(); error!(?e, "multipart_complete_update_failed"); ApiError::internal("db update").into_response() } }
 }
 
 #[utoipa::path(
@@ -0,0 +1,89 @@
+use axum::{Json, extract::{Path, Query, State}, http::StatusCode};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use crate::{AppState, error::{ApiError, ApiResult, ApiErrorBody}, services::{self, webhooks::{Webhook, WebhookDelivery}}};
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateWebhookReq {
+    pub url: String,
+    /// Event types to deliver (default: every event)
+    #[serde(default)] pub event_types: Vec<String>,
+    /// HMAC-SHA256 signing secret (generated when absent)
+    #[serde(default)] pub secret: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CreateWebhookResp {
+    pub id: uuid::Uuid,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Signing secret; only returned here
+    pub secret: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeliveriesQuery { pub limit: Option<i64> }
+
+async fn app_id(state: &AppState, app_name: &str) -> ApiResult<uuid::Uuid> {
+    sqlx::query_scalar("SELECT id FROM applications WHERE name=$1").bind(app_name)
+        .fetch_optional(&state.db).await.map_err(|e| ApiError::internal(format!("lookup app: {e}")))?
+        .ok_or_else(|| ApiError::not_found("application not found"))
+}
+
+/// List the application's webhooks (secrets are never returned)
+#[utoipa::path(get, path = "/apps/{app_name}/webhooks", params(("app_name"=String, Path, description="Application name")), responses( (status=200, body=[Webhook]), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state), fields(app_name=%app_name))]
+pub async fn list_webhooks(State(state): State<AppState>, Path(app_name): Path<String>) -> ApiResult<Json<Vec<Webhook>>> {
+    let app_id = app_id(&state, &app_name).await?;
+    let hooks = services::webhooks::list_webhooks(&state.db, app_id).await.map_err(|e| ApiError::internal(format!("query webhooks: {e}")))?;
+    Ok(Json(hooks))
+}
+
+/// Subscribe a URL to the application's events; deliveries are signed with the returned secret (stored encrypted)
+#[utoipa::path(post, path = "/apps/{app_name}/webhooks", request_body = CreateWebhookReq, params(("app_name"=String, Path, description="Application name")), responses( (status=201, body=CreateWebhookResp), (status=400, body=ApiErrorBody), (status=404, body=ApiErrorBody), (status=503, body=ApiErrorBody, description="secrets store not configured"), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state, body), fields(app_name=%app_name))]
+pub async fn create_webhook(State(state): State<AppState>, Path(app_name): Path<String>, Json(body): Json<CreateWebhookReq>) -> ApiResult<(StatusCode, Json<CreateWebhookResp>)> {
+    let url = body.url.trim();
+    if !services::webhooks::valid_url(url) { return Err(ApiError::bad_request("url must be an absolute http(s) URL")); }
+    let mut event_types = body.event_types;
+    event_types.sort();
+    event_types.dedup();
+    if let Some(bad) = event_types.iter().find(|t| !services::webhooks::EVENT_TYPES.contains(&t.as_str())) {
+        return Err(ApiError::bad_request(format!("unknown event type {bad} (expected one of {})", services::webhooks::EVENT_TYPES.join(", "))));
+    }
+    let secret = match body.secret.as_deref().map(str::trim) {
+        Some(s) if s.len() < 16 => return Err(ApiError::bad_request("secret must be at least 16 characters")),
+        Some(s) => s.to_string(),
+        None => hex::encode(crate::secrets::generate_data_key()),
+    };
+    let keyring = super::secrets::keyring()?;
+    let app_id = app_id(&state, &app_name).await?;
+    let hook = services::webhooks::create_webhook(&state.db, &keyring, app_id, url, &secret, &event_types).await
+        .map_err(|e| ApiError::internal(format!("insert webhook: {e}")))?;
+    tracing::info!(app_id=%app_id, webhook_id=%hook.id, "webhook created");
+    Ok((StatusCode::CREATED, Json(CreateWebhookResp { id: hook.id, url: hook.url, event_types: hook.event_types, active: hook.active, created_at: hook.created_at, secret })))
+}
+
+/// Delete a webhook together with its pending deliveries and delivery log
+#[utoipa::path(delete, path = "/apps/{app_name}/webhooks/{id}", params(("app_name"=String, Path, description="Application name"), ("id"=uuid::Uuid, Path, description="Webhook ID")), responses( (status=204), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="info", skip(state), fields(app_name=%app_name, webhook_id=%id))]
+pub async fn delete_webhook(State(state): State<AppState>, Path((app_name, id)): Path<(String, uuid::Uuid)>) -> ApiResult<StatusCode> {
+    let app_id = app_id(&state, &app_name).await?;
+    let removed = services::webhooks::delete_webhook(&state.db, app_id, id).await.map_err(|e| ApiError::internal(format!("delete webhook: {e}")))?;
+    if !removed { return Err(ApiError::not_found("webhook not found")); }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Delivery log of a webhook, newest first
+#[utoipa::path(get, path = "/apps/{app_name}/webhooks/{id}/deliveries", params(("app_name"=String, Path, description="Application name"), ("id"=uuid::Uuid, Path, description="Webhook ID"), ("limit"=Option<i64>, Query, description="Max items (default 100, max 1000)")), responses( (status=200, body=[WebhookDelivery]), (status=404, body=ApiErrorBody), (status=500, body=ApiErrorBody) ))]
+#[tracing::instrument(level="debug", skip(state, q), fields(app_name=%app_name, webhook_id=%id, limit=?q.limit))]
+pub async fn list_deliveries(State(state): State<AppState>, Path((app_name, id)): Path<(String, uuid::Uuid)>, Query(q): Query<DeliveriesQuery>) -> ApiResult<Json<Vec<WebhookDelivery>>> {
+    let app_id = app_id(&state, &app_name).await?;
+    let limit = q.limit.unwrap_or(100).clamp(1, 1000);
+    services::webhooks::list_deliveries(&state.db, app_id, id, limit).await
+        .map_err(|e| ApiError::internal(format!("query deliveries: {e}")))?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("webhook not found"))
+}
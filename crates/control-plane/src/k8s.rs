@@ -1,30 +1,432 @@
 use anyhow::Result;
 #[cfg(not(feature = "mock-kube"))]
-use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::{apps::v1::Deployment, batch::v1::Job, core::v1::{Pod, Secret, Service}, networking::v1::Ingress};
 #[cfg(not(feature = "mock-kube"))]
-use kube::{Api, Client, api::{PatchParams, Patch}};
+use kube::{Api, Client, api::{PatchParams, Patch, ListParams, LogParams, DeleteParams}};
+use futures_util::{stream::BoxStream, StreamExt};
 use serde_json::json;
+use crate::models::{IntOrPercent, ProbeSettings, ProbeSpec, RolloutPolicy, RolloutStrategy};
+use crate::runtime::{self, Runtime};
+
+/// Query options for pod log streaming (subset of `kubectl logs` flags).
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    pub tail_lines: Option<i64>,
+    pub since_seconds: Option<i64>,
+    /// Container name; defaults to the main `app` container.
+    pub container: Option<String>,
+    pub follow: bool,
+}
+
+/// Stream of log lines (without trailing newline) merged across all pods of an app.
+pub type LogLineStream = BoxStream<'static, Result<String>>;
+
+/// Everything needed to render and apply the Deployment of one application release.
+#[derive(Debug, Clone, Default)]
+pub struct DeploySpec {
+    pub app: String,
+    /// Deployment row this release belongs to (names the release Job).
+    pub deployment_id: uuid::Uuid,
+    pub namespace: String,
+    /// sha256 hex of the artifact (empty when unresolved; skips checksum verification)
+    pub digest: String,
+    pub artifact_url: String,
+    pub signature: Option<String>,
+    pub dev_hot: bool,
+    /// Desired replica count of the web process (`None` renders 1).
+    pub replicas: Option<i32>,
+    /// Application config rendered into the `app` container env (after the AETHER_* entries).
+    pub env: Vec<(String, String)>,
+    /// Decrypted app secrets, projected as the `<app>-secrets` Secret and referenced via `envFrom`.
+    pub secrets: SecretEnv,
+    /// Image, command and defaults of the `app` container (`None` renders [`runtime::default_runtime`]).
+    pub runtime: Option<&'static Runtime>,
+    /// Start command of the web process (`None` renders the runtime's command).
+    pub command: Option<Vec<String>>,
+    /// Non-web process types, each rendered as an `<app>-<type>` Deployment without ports, probes or Service.
+    pub processes: Vec<ProcessSpec>,
+    /// Release-phase command run as a one-off Job from the new artifact before the Deployments are applied.
+    pub release_command: Option<Vec<String>>,
+    /// Container port (`None` renders the runtime's default port); exported as `PORT` and targeted by the Service.
+    pub port: Option<i32>,
+    /// Ingress rules; no Ingress is rendered (and a stale one is removed) when empty.
+    pub routes: Vec<Route>,
+    /// `spec.ingressClassName` of the Ingress (cluster default when `None`).
+    pub ingress_class: Option<String>,
+    /// Probe overrides merged field by field over the runtime defaults.
+    pub probes: ProbeSettings,
+    /// `terminationGracePeriodSeconds` (`None` renders [`DEFAULT_TERMINATION_GRACE_SECS`]).
+    pub termination_grace_period_seconds: Option<i32>,
+    /// `preStop` sleep so the pod leaves Service endpoints before SIGTERM (`None` renders [`DEFAULT_PRE_STOP_SLEEP_SECS`], 0 disables).
+    pub pre_stop_sleep_seconds: Option<i32>,
+    /// `Rolling` applies the release in place; other strategies go through [`apply_candidate`] and [`promote_candidate`].
+    pub strategy: RolloutStrategy,
+    /// Rolling update parameters and deadlines of every Deployment of the release (unset fields render the defaults below).
+    pub rollout: RolloutPolicy,
+}
+
+pub const DEFAULT_TERMINATION_GRACE_SECS: i32 = 30;
+pub const DEFAULT_PRE_STOP_SLEEP_SECS: i32 = 5;
+pub const DEFAULT_PROGRESS_DEADLINE_SECS: i32 = 240;
+/// Status watcher timeout for releases that never become ready (`RolloutPolicy::timeout_seconds`).
+pub const DEFAULT_ROLLOUT_TIMEOUT_SECS: i32 = 300;
+/// Lines of the release Job log kept on the deployment.
+pub const RELEASE_LOG_LINES: i64 = 200;
+
+/// Seconds after which the status watcher gives up on a release of an app with this policy.
+pub fn rollout_timeout_secs(policy: &RolloutPolicy) -> i64 {
+    policy.timeout_seconds.unwrap_or(DEFAULT_ROLLOUT_TIMEOUT_SECS) as i64
+}
+
+/// Deadline of the release Job (`AETHER_RELEASE_TIMEOUT_SECS`, default 600), rendered as `activeDeadlineSeconds`.
+pub fn release_timeout_secs() -> i64 {
+    std::env::var("AETHER_RELEASE_TIMEOUT_SECS").ok().and_then(|v| v.parse::<i64>().ok()).filter(|v| *v > 0).unwrap_or(600)
+}
+
+/// Name of the release Job of a deployment (Jobs are immutable, so one per deployment).
+pub fn release_job_name(app: &str, deployment_id: uuid::Uuid) -> String {
+    format!("{app}-release-{}", &deployment_id.simple().to_string()[..8])
+}
+
+/// Result of a release Job run.
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseOutcome {
+    pub succeeded: bool,
+    /// Job failure reason (e.g. `BackoffLimitExceeded`, `DeadlineExceeded`, `timeout`)
+    pub reason: Option<String>,
+    /// Last [`RELEASE_LOG_LINES`] lines of the release container (or of the fetch init container when it failed first)
+    pub logs: Vec<String>,
+}
+
+/// Render one probe: override fields win over the default, HTTP when a path is known, TCP otherwise.
+fn render_probe(over: Option<&ProbeSpec>, default: &ProbeSpec, app_port: i32) -> Option<serde_json::Value> {
+    let o = over.cloned().unwrap_or_default();
+    if !o.enabled.or(default.enabled).unwrap_or(true) { return None; }
+    let port = o.port.or(default.port).unwrap_or(app_port);
+    let mut probe = match o.http_path.as_ref().or(default.http_path.as_ref()) {
+        Some(path) => json!({"httpGet": {"path": path, "port": port}}),
+        None => json!({"tcpSocket": {"port": port}}),
+    };
+    for (k, v) in [
+        ("initialDelaySeconds", o.initial_delay_seconds.or(default.initial_delay_seconds)),
+        ("periodSeconds", o.period_seconds.or(default.period_seconds)),
+        ("timeoutSeconds", o.timeout_seconds.or(default.timeout_seconds)),
+        ("successThreshold", o.success_threshold.or(default.success_threshold)),
+        ("failureThreshold", o.failure_threshold.or(default.failure_threshold)),
+    ] {
+        if let Some(v) = v { probe[k] = json!(v); }
+    }
+    Some(probe)
+}
+
+/// One host + path prefix routed by the app Ingress to its Service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route { pub host: String, pub path: String }
+
+/// Process type served by the app Service and Ingress; the only one rendered with ports and probes.
+pub const WEB_PROCESS: &str = "web";
+
+/// A non-web process type of a release (Procfile `worker:`, `clock:`, ...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessSpec { pub name: String, pub command: Vec<String>, pub replicas: i32 }
+
+/// Kubernetes Deployment name of a process type: the app name for `web`, `<app>-<type>` otherwise.
+pub fn process_object_name(app: &str, process: &str) -> String {
+    if process == WEB_PROCESS { app.to_string() } else { format!("{app}-{process}") }
+}
+
+/// Candidate Deployment of a blue/green or canary release: `<app>-<first 12 digest chars>` (deployment id prefix
+/// when the digest is unresolved), selected by its own `app` label so the app Service keeps serving the stable pods.
+pub fn candidate_name(spec: &DeploySpec) -> String {
+    let suffix = if valid_digest(&spec.digest) { spec.digest[..12].to_ascii_lowercase() } else { spec.deployment_id.simple().to_string()[..12].to_string() };
+    format!("{}-{suffix}", spec.app)
+}
+
+/// Service selecting only the candidate pods: `<app>-preview` (blue/green) or `<app>-canary` (canary Ingress backend).
+pub fn candidate_service_name(spec: &DeploySpec) -> String {
+    match spec.strategy { RolloutStrategy::Canary { .. } => format!("{}-canary", spec.app), _ => format!("{}-preview", spec.app) }
+}
+
+/// Secret values kept out of `Debug` output (only names are printed).
+#[derive(Clone, Default)]
+pub struct SecretEnv(pub std::collections::BTreeMap<String, String>);
+
+impl std::fmt::Debug for SecretEnv {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.debug_set().entries(self.0.keys()).finish() }
+}
+
+impl SecretEnv {
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+    /// Content hash stamped on the pod template so secret changes roll the pods.
+    fn digest(&self) -> String {
+        use sha2::{Sha256, Digest};
+        let mut h = Sha256::new();
+        for (k, v) in &self.0 { h.update(k.as_bytes()); h.update([0u8]); h.update(v.as_bytes()); h.update([0u8]); }
+        hex::encode(h.finalize())
+    }
+}
+
+/// Name of the Kubernetes Secret holding an app's secrets.
+pub fn secret_name(app: &str) -> String { format!("{app}-secrets") }
+
+/// Drift-relevant state of a workload Deployment: the digest annotation and the image and env of its `app` container.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkloadState {
+    pub name: String,
+    pub digest: Option<String>,
+    pub image: Option<String>,
+    pub env: Vec<(String, String)>,
+}
+
+impl WorkloadState {
+    /// Read from a Deployment manifest, rendered or fetched from the cluster (`valueFrom` env entries read as empty).
+    pub fn from_manifest(d: &serde_json::Value) -> Self {
+        let app = d.pointer("/spec/template/spec/containers").and_then(|c| c.as_array()).and_then(|cs| cs.iter().find(|c| c["name"] == "app"));
+        let env = app.and_then(|c| c["env"].as_array()).map(|es| es.iter()
+            .map(|e| (e["name"].as_str().unwrap_or_default().to_string(), e["value"].as_str().unwrap_or_default().to_string()))
+            .collect()).unwrap_or_default();
+        WorkloadState {
+            name: d["metadata"]["name"].as_str().unwrap_or_default().to_string(),
+            digest: d.pointer("/metadata/annotations/aether.dev~1digest").and_then(|v| v.as_str()).map(str::to_string),
+            image: app.and_then(|c| c["image"].as_str()).map(str::to_string),
+            env,
+        }
+    }
+
+    /// Fields of the live object that differ from this desired state (`digest`, `image`, `env`), or `missing`.
+    pub fn drift(&self, live: Option<&WorkloadState>) -> Vec<&'static str> {
+        let Some(live) = live else { return vec!["missing"]; };
+        let mut fields = Vec::new();
+        if live.digest != self.digest { fields.push("digest"); }
+        if live.image != self.image { fields.push("image"); }
+        if live.env != self.env { fields.push("env"); }
+        fields
+    }
+}
+
+/// Desired state of the stable workloads of a release: the web Deployment plus one per other process type.
+pub fn desired_workloads(spec: &DeploySpec) -> Vec<WorkloadState> {
+    std::iter::once(build_deployment_manifest(spec))
+        .chain(spec.processes.iter().map(|p| build_process_manifest(spec, p)))
+        .map(|m| WorkloadState::from_manifest(&m))
+        .collect()
+}
+
+/// Whether cluster calls do anything: `AETHER_DISABLE_K8S=1` turns them into no-ops (the mock cluster always counts).
+pub fn cluster_enabled() -> bool {
+    cfg!(feature = "mock-kube") || std::env::var("AETHER_DISABLE_K8S").unwrap_or_default() != "1"
+}
+
+// In-memory stand-in for cluster state: (namespace, Deployment name) -> digest of the last applied deployment.
+#[cfg(feature = "mock-kube")]
+static MOCK_APPLIED: once_cell::sync::Lazy<std::sync::Mutex<std::collections::HashMap<(String, String), String>>> = once_cell::sync::Lazy::new(Default::default);
+/// Rendered manifests of the stable workloads the mock cluster holds (read back by [`live_workloads`]).
+#[cfg(feature = "mock-kube")]
+static MOCK_OBJECTS: once_cell::sync::Lazy<std::sync::Mutex<std::collections::HashMap<(String, String), serde_json::Value>>> = once_cell::sync::Lazy::new(Default::default);
 
 #[cfg(feature = "mock-kube")]
-pub async fn apply_deployment(app: &str, digest: &str, artifact_url: &str, namespace: &str, signature: Option<&str>, dev_hot: bool) -> Result<()> {
+fn mock_store_workloads(spec: &DeploySpec) {
+    let mut objects = MOCK_OBJECTS.lock().unwrap();
+    objects.insert((spec.namespace.clone(), spec.app.clone()), build_deployment_manifest(spec));
+    for p in &spec.processes {
+        objects.insert((spec.namespace.clone(), process_object_name(&spec.app, &p.name)), build_process_manifest(spec, p));
+    }
+}
+
+#[cfg(feature = "mock-kube")]
+pub async fn apply_deployment(spec: &DeploySpec) -> Result<()> {
     // Simulate success for integration tests
-    tracing::info!(app, digest, artifact_url, namespace, signature=?signature, dev_hot, "[mock-kube] apply_deployment called");
+    tracing::info!(app=%spec.app, digest=%spec.digest, artifact_url=%spec.artifact_url, namespace=%spec.namespace, signature=?spec.signature, dev_hot=spec.dev_hot, env_count=spec.env.len(), "[mock-kube] apply_deployment called");
+    mock_store_workloads(spec);
+    let mut applied = MOCK_APPLIED.lock().unwrap();
+    applied.insert((spec.namespace.clone(), spec.app.clone()), spec.digest.clone());
+    for p in &spec.processes {
+        applied.insert((spec.namespace.clone(), process_object_name(&spec.app, &p.name)), spec.digest.clone());
+    }
     Ok(())
 }
 
-/// Apply (create or replace) a Kubernetes Deployment for an application + artifact digest.
-/// Strategy: name = app name, annotation carries digest for idempotency / change triggers.
+/// Mock release Job: succeeds unless the command contains `exit 1`; logs are synthetic.
+#[cfg(feature = "mock-kube")]
+pub async fn run_release_job(spec: &DeploySpec) -> Result<ReleaseOutcome> {
+    let command = spec.release_command.clone().unwrap_or_default().join(" ");
+    let job = release_job_name(&spec.app, spec.deployment_id);
+    tracing::info!(app=%spec.app, job=%job, command=%command, "[mock-kube] run_release_job called");
+    let succeeded = !command.contains("exit 1");
+    Ok(ReleaseOutcome {
+        succeeded,
+        reason: (!succeeded).then(|| "BackoffLimitExceeded".to_string()),
+        logs: vec![format!("[mock-kube] job={job} command={command}"), format!("[mock-kube] exit={}", if succeeded { 0 } else { 1 })],
+    })
+}
+
+#[cfg(feature = "mock-kube")]
+pub async fn apply_routing(spec: &DeploySpec) -> Result<()> {
+    tracing::info!(app=%spec.app, namespace=%spec.namespace, routes=?spec.routes, "[mock-kube] apply_routing called");
+    Ok(())
+}
+
+#[cfg(feature = "mock-kube")]
+pub async fn apply_candidate(spec: &DeploySpec) -> Result<()> {
+    tracing::info!(app=%spec.app, candidate=%candidate_name(spec), strategy=?spec.strategy, "[mock-kube] apply_candidate called");
+    MOCK_APPLIED.lock().unwrap().insert((spec.namespace.clone(), candidate_name(spec)), spec.digest.clone());
+    Ok(())
+}
+
+#[cfg(feature = "mock-kube")]
+pub async fn promote_candidate(spec: &DeploySpec) -> Result<()> {
+    tracing::info!(app=%spec.app, candidate=%candidate_name(spec), "[mock-kube] promote_candidate called");
+    mock_store_workloads(spec);
+    let mut applied = MOCK_APPLIED.lock().unwrap();
+    applied.insert((spec.namespace.clone(), spec.app.clone()), spec.digest.clone());
+    for p in &spec.processes {
+        applied.insert((spec.namespace.clone(), process_object_name(&spec.app, &p.name)), spec.digest.clone());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "mock-kube")]
+pub async fn teardown_candidate(spec: &DeploySpec) -> Result<()> {
+    tracing::info!(app=%spec.app, candidate=%candidate_name(spec), "[mock-kube] teardown_candidate called");
+    MOCK_APPLIED.lock().unwrap().remove(&(spec.namespace.clone(), candidate_name(spec)));
+    Ok(())
+}
+
+/// Set by [`mock_fail_scale`]: the mock API server rejects scale patches.
+#[cfg(feature = "mock-kube")]
+static MOCK_SCALE_FAILS: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Test hook: make subsequent [`scale_deployment`] calls fail (or succeed again).
+#[cfg(feature = "mock-kube")]
+pub fn mock_fail_scale(fail: bool) { MOCK_SCALE_FAILS.store(fail, std::sync::atomic::Ordering::SeqCst); }
+
+/// Mock scale: succeeds (returns true) only for Deployments previously applied.
+#[cfg(feature = "mock-kube")]
+pub async fn scale_deployment(name: &str, namespace: &str, replicas: i32) -> Result<bool> {
+    tracing::info!(name, namespace, replicas, "[mock-kube] scale_deployment called");
+    if MOCK_SCALE_FAILS.load(std::sync::atomic::Ordering::SeqCst) { anyhow::bail!("mock API server rejected the scale patch"); }
+    Ok(MOCK_APPLIED.lock().unwrap().contains_key(&(namespace.to_string(), name.to_string())))
+}
+
+#[cfg(feature = "mock-kube")]
+pub async fn live_workloads(namespace: &str, names: &[String]) -> Result<Vec<Option<WorkloadState>>> {
+    let objects = MOCK_OBJECTS.lock().unwrap();
+    Ok(names.iter().map(|n| objects.get(&(namespace.to_string(), n.clone())).map(WorkloadState::from_manifest)).collect())
+}
+
+/// Test hook: change a workload the mock cluster holds, as `kubectl edit` would. Returns false when it does not exist.
+#[cfg(feature = "mock-kube")]
+pub fn mock_edit_workload(namespace: &str, name: &str, edit: impl FnOnce(&mut serde_json::Value)) -> bool {
+    MOCK_OBJECTS.lock().unwrap().get_mut(&(namespace.to_string(), name.to_string())).map(edit).is_some()
+}
+
+/// Test hook: remove a workload from the mock cluster, as `kubectl delete` would.
+#[cfg(feature = "mock-kube")]
+pub fn mock_delete_workload(namespace: &str, name: &str) {
+    let key = (namespace.to_string(), name.to_string());
+    MOCK_OBJECTS.lock().unwrap().remove(&key);
+    MOCK_APPLIED.lock().unwrap().remove(&key);
+}
+
+/// Synthetic logs for apps previously passed to `apply_deployment` (empty for unknown apps).
+#[cfg(feature = "mock-kube")]
+pub async fn stream_logs(app: &str, namespace: &str, opts: &LogOptions) -> Result<LogLineStream> {
+    let Some(digest) = MOCK_APPLIED.lock().unwrap().get(&(namespace.to_string(), app.to_string())).cloned() else {
+        return Ok(futures_util::stream::empty().boxed());
+    };
+    let container = opts.container.clone().unwrap_or_else(|| "app".into());
+    let count = opts.tail_lines.unwrap_or(5).clamp(0, 100);
+    let pod = format!("{app}-mock");
+    let lines: Vec<Result<String>> = (0..count)
+        .map(|i| Ok(format!("[mock-kube] pod={pod} container={container} digest={digest} line={i}")))
+        .collect();
+    Ok(futures_util::stream::iter(lines).boxed())
+}
+
+/// Stream logs from every pod labelled `app=<app>`; lines are prefixed with the pod name when more than one pod matches.
 #[cfg(not(feature = "mock-kube"))]
-pub async fn apply_deployment(app: &str, digest: &str, artifact_url: &str, namespace: &str, signature: Option<&str>, dev_hot: bool) -> Result<()> {
+pub async fn stream_logs(app: &str, namespace: &str, opts: &LogOptions) -> Result<LogLineStream> {
+    use futures_util::AsyncBufReadExt;
     if std::env::var("AETHER_DISABLE_K8S").unwrap_or_default() == "1" {
-        tracing::info!(app, "AETHER_DISABLE_K8S=1 skipping real kube apply");
-        return Ok(());
+        tracing::info!(app, "AETHER_DISABLE_K8S=1 returning empty log stream");
+        return Ok(futures_util::stream::empty().boxed());
+    }
+    let client = Client::try_default().await?;
+    let pods: Api<Pod> = Api::namespaced(client, namespace);
+    let names: Vec<String> = pods.list(&ListParams::default().labels(&format!("app={app}"))).await?
+        .items.into_iter().filter_map(|p| p.metadata.name).collect();
+    let prefix = names.len() > 1;
+    let lp = LogParams {
+        container: Some(opts.container.clone().unwrap_or_else(|| "app".into())),
+        follow: opts.follow,
+        tail_lines: opts.tail_lines,
+        since_seconds: opts.since_seconds,
+        ..LogParams::default()
+    };
+    let mut streams = Vec::with_capacity(names.len());
+    for name in names {
+        let reader = pods.log_stream(&name, &lp).await?;
+        let lines = reader.lines().map(move |l| {
+            l.map(|line| if prefix { format!("[{name}] {line}") } else { line }).map_err(anyhow::Error::from)
+        });
+        streams.push(lines.boxed());
+    }
+    Ok(futures_util::stream::select_all(streams).boxed())
+}
+
+/// Patch only `spec.replicas` of the live Deployment `name` (see [`process_object_name`]). Returns false when the
+/// Deployment does not exist yet (the persisted count is picked up by the next apply).
+#[cfg(not(feature = "mock-kube"))]
+pub async fn scale_deployment(name: &str, namespace: &str, replicas: i32) -> Result<bool> {
+    if std::env::var("AETHER_DISABLE_K8S").unwrap_or_default() == "1" {
+        tracing::info!(name, "AETHER_DISABLE_K8S=1 skipping real kube scale");
+        return Ok(false);
     }
     let client = Client::try_default().await?;
     let api: Api<Deployment> = Api::namespaced(client, namespace);
-    let name = app;
+    let patch = json!({"spec": {"replicas": replicas}});
+    match api.patch_scale(name, &PatchParams::default(), &Patch::Merge(&patch)).await {
+        Ok(_) => Ok(true),
+        Err(kube::Error::Api(ae)) if ae.code == 404 => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Current state of the named Deployments (`None` for those that do not exist).
+#[cfg(not(feature = "mock-kube"))]
+pub async fn live_workloads(namespace: &str, names: &[String]) -> Result<Vec<Option<WorkloadState>>> {
+    let client = Client::try_default().await?;
+    let api: Api<Deployment> = Api::namespaced(client, namespace);
+    let mut states = Vec::with_capacity(names.len());
+    for name in names {
+        let live = api.get_opt(name).await?;
+        states.push(live.map(|d| WorkloadState::from_manifest(&serde_json::to_value(&d).unwrap_or_default())));
+    }
+    Ok(states)
+}
+
+/// Apply (create or replace) the Kubernetes Deployments of an application release: the web process named after the
+/// app plus one `<app>-<type>` Deployment per other process type; process Deployments dropped from the release are deleted.
+/// Annotations carry the digest for idempotency / change triggers.
+#[cfg(not(feature = "mock-kube"))]
+pub async fn apply_deployment(spec: &DeploySpec) -> Result<()> {
+    if std::env::var("AETHER_DISABLE_K8S").unwrap_or_default() == "1" {
+        tracing::info!(app=%spec.app, "AETHER_DISABLE_K8S=1 skipping real kube apply");
+        return Ok(());
+    }
+    let client = Client::try_default().await?;
+    apply_workloads(&client, spec).await?;
+    apply_routing(spec).await
+}
+
+#[cfg(not(feature = "mock-kube"))]
+async fn apply_workloads(client: &Client, spec: &DeploySpec) -> Result<()> {
+    apply_secret(client, spec).await?;
+    let api: Api<Deployment> = Api::namespaced(client.clone(), &spec.namespace);
+    let params = PatchParams::apply("aether-control-plane").force();
+    let name = spec.app.as_str();
     // Build desired deployment manifest
-    let desired = build_deployment_manifest(app, digest, artifact_url, namespace, signature, dev_hot);
+    let desired = build_deployment_manifest(spec);
     match api.get(name).await {
         Ok(_) => {
             // Server-side apply style patch to minimize diff churn
@@ -39,25 +441,427 @@ pub async fn apply_deployment(app: &str, digest: &str, artifact_url: &str, names
         }
         Err(e) => return Err(e.into())
     }
+    for p in &spec.processes {
+        api.patch(&process_object_name(name, &p.name), &params, &Patch::Apply(&build_process_manifest(spec, p))).await?;
+    }
+    prune_processes(&api, spec).await
+}
+
+/// Start the candidate side of a blue/green or canary release next to the serving Deployment: the digest-suffixed web
+/// Deployment, a Service selecting only its pods and, for canaries with routes, the weighted canary Ingress.
+/// Non-web process types keep running the stable release until promotion.
+#[cfg(not(feature = "mock-kube"))]
+pub async fn apply_candidate(spec: &DeploySpec) -> Result<()> {
+    if std::env::var("AETHER_DISABLE_K8S").unwrap_or_default() == "1" {
+        tracing::info!(app=%spec.app, "AETHER_DISABLE_K8S=1 skipping real kube candidate apply");
+        return Ok(());
+    }
+    let client = Client::try_default().await?;
+    apply_secret(&client, spec).await?;
+    let params = PatchParams::apply("aether-control-plane").force();
+    let candidate = candidate_name(spec);
+    let api: Api<Deployment> = Api::namespaced(client.clone(), &spec.namespace);
+    api.patch(&candidate, &params, &Patch::Apply(&build_candidate_manifest(spec))).await?;
+    let svc_api: Api<Service> = Api::namespaced(client.clone(), &spec.namespace);
+    let svc = candidate_service_name(spec);
+    svc_api.patch(&svc, &params, &Patch::Apply(&service_manifest(spec, &svc, &candidate))).await?;
+    if let RolloutStrategy::Canary { percent } = spec.strategy {
+        if let Some(ing) = build_canary_ingress_manifest(spec, percent) {
+            let ing_api: Api<Ingress> = Api::namespaced(client, &spec.namespace);
+            ing_api.patch(&format!("{}-canary", spec.app), &params, &Patch::Apply(&ing)).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Promote a candidate: move all traffic onto it (app Service selector for blue/green, canary weight 100 for canaries),
+/// then roll the app's own Deployments to the release. The Service is pointed back by [`teardown_candidate`] once they are ready.
+#[cfg(not(feature = "mock-kube"))]
+pub async fn promote_candidate(spec: &DeploySpec) -> Result<()> {
+    if std::env::var("AETHER_DISABLE_K8S").unwrap_or_default() == "1" {
+        tracing::info!(app=%spec.app, "AETHER_DISABLE_K8S=1 skipping real kube promotion");
+        return Ok(());
+    }
+    let client = Client::try_default().await?;
+    let params = PatchParams::apply("aether-control-plane").force();
+    match spec.strategy {
+        RolloutStrategy::BlueGreen => {
+            let svc_api: Api<Service> = Api::namespaced(client.clone(), &spec.namespace);
+            svc_api.patch(&spec.app, &params, &Patch::Apply(&service_manifest(spec, &spec.app, &candidate_name(spec)))).await?;
+        }
+        RolloutStrategy::Canary { .. } => {
+            if let Some(ing) = build_canary_ingress_manifest(spec, 100) {
+                let ing_api: Api<Ingress> = Api::namespaced(client.clone(), &spec.namespace);
+                ing_api.patch(&format!("{}-canary", spec.app), &params, &Patch::Apply(&ing)).await?;
+            }
+        }
+        RolloutStrategy::Rolling => {}
+    }
+    apply_workloads(&client, spec).await
+}
+
+/// Remove the candidate side of a release (after promotion, or on abort): route the app Service and Ingress back to
+/// the app's own Deployment, then delete the canary Ingress, the candidate Service and the candidate Deployment.
+#[cfg(not(feature = "mock-kube"))]
+pub async fn teardown_candidate(spec: &DeploySpec) -> Result<()> {
+    if std::env::var("AETHER_DISABLE_K8S").unwrap_or_default() == "1" {
+        tracing::info!(app=%spec.app, "AETHER_DISABLE_K8S=1 skipping real kube candidate teardown");
+        return Ok(());
+    }
+    apply_routing(spec).await?;
+    let client = Client::try_default().await?;
+    delete_if_exists(&Api::<Ingress>::namespaced(client.clone(), &spec.namespace), &format!("{}-canary", spec.app)).await?;
+    delete_if_exists(&Api::<Service>::namespaced(client.clone(), &spec.namespace), &candidate_service_name(spec)).await?;
+    delete_if_exists(&Api::<Deployment>::namespaced(client, &spec.namespace), &candidate_name(spec)).await?;
+    tracing::info!(app=%spec.app, candidate=%candidate_name(spec), "removed candidate deployment");
     Ok(())
 }
 
+#[cfg(not(feature = "mock-kube"))]
+async fn delete_if_exists<K>(api: &Api<K>, name: &str) -> Result<()>
+where K: kube::Resource + Clone + serde::de::DeserializeOwned + std::fmt::Debug {
+    match api.delete(name, &DeleteParams::default()).await {
+        Ok(_) => Ok(()),
+        Err(kube::Error::Api(ae)) if ae.code == 404 => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Apply the app's `<app>-secrets` Secret, or delete it once the app has no secrets left so removed values do not
+/// stay readable in the cluster.
+#[cfg(not(feature = "mock-kube"))]
+async fn apply_secret(client: &Client, spec: &DeploySpec) -> Result<()> {
+    let s_api: Api<Secret> = Api::namespaced(client.clone(), &spec.namespace);
+    match build_secret_manifest(spec) {
+        Some(secret) => {
+            let params = PatchParams::apply("aether-control-plane").force();
+            s_api.patch(&secret_name(&spec.app), &params, &Patch::Apply(&secret)).await?;
+        }
+        None => delete_if_exists(&s_api, &secret_name(&spec.app)).await?,
+    }
+    Ok(())
+}
+
+/// Run the release Job of a release and wait for it to finish (bounded by [`release_timeout_secs`]), collecting its log.
+#[cfg(not(feature = "mock-kube"))]
+pub async fn run_release_job(spec: &DeploySpec) -> Result<ReleaseOutcome> {
+    if std::env::var("AETHER_DISABLE_K8S").unwrap_or_default() == "1" {
+        tracing::info!(app=%spec.app, "AETHER_DISABLE_K8S=1 skipping real release job");
+        return Ok(ReleaseOutcome { succeeded: true, ..ReleaseOutcome::default() });
+    }
+    let Some(job) = build_release_job_manifest(spec) else { return Ok(ReleaseOutcome { succeeded: true, ..ReleaseOutcome::default() }); };
+    let client = Client::try_default().await?;
+    apply_secret(&client, spec).await?;
+    let name = release_job_name(&spec.app, spec.deployment_id);
+    let jobs: Api<Job> = Api::namespaced(client.clone(), &spec.namespace);
+    jobs.patch(&name, &PatchParams::apply("aether-control-plane").force(), &Patch::Apply(&job)).await?;
+    // activeDeadlineSeconds fails the Job server-side; the local deadline only guards against a stuck watch
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(release_timeout_secs() as u64 + 30);
+    let mut outcome = ReleaseOutcome::default();
+    loop {
+        let st = jobs.get(&name).await?.status.unwrap_or_default();
+        if st.succeeded.unwrap_or(0) > 0 { outcome.succeeded = true; break; }
+        let failed = st.conditions.iter().flatten().find(|c| c.type_ == "Failed" && c.status == "True");
+        if failed.is_some() || st.failed.unwrap_or(0) > 0 {
+            outcome.reason = Some(failed.and_then(|c| c.reason.clone()).unwrap_or_else(|| "job_failed".into()));
+            break;
+        }
+        if std::time::Instant::now() > deadline { outcome.reason = Some("timeout".into()); break; }
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    }
+    outcome.logs = release_job_logs(&client, &spec.namespace, &name).await.unwrap_or_else(|e| vec![format!("release logs unavailable: {e}")]);
+    Ok(outcome)
+}
+
+#[cfg(not(feature = "mock-kube"))]
+async fn release_job_logs(client: &Client, namespace: &str, job: &str) -> Result<Vec<String>> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let mut list = pods.list(&ListParams::default().labels(&format!("job-name={job}"))).await?.items;
+    list.sort_by_key(|p| p.metadata.creation_timestamp.clone());
+    let Some(pod) = list.last().and_then(|p| p.metadata.name.clone()) else { return Ok(Vec::new()); };
+    for container in ["release", "fetch-artifact"] {
+        let lp = LogParams { container: Some(container.into()), tail_lines: Some(RELEASE_LOG_LINES), ..LogParams::default() };
+        if let Ok(text) = pods.logs(&pod, &lp).await {
+            if !text.trim().is_empty() { return Ok(text.lines().map(str::to_string).collect()); }
+        }
+    }
+    Ok(Vec::new())
+}
+
+/// Delete the app's process Deployments whose type is no longer part of the release (e.g. removed from the Procfile).
+#[cfg(not(feature = "mock-kube"))]
+async fn prune_processes(api: &Api<Deployment>, spec: &DeploySpec) -> Result<()> {
+    let live = api.list(&ListParams::default().labels(&format!("app_name={},process", spec.app))).await?;
+    for d in live.items {
+        let process = d.metadata.labels.as_ref().and_then(|l| l.get("process")).map(String::as_str).unwrap_or(WEB_PROCESS);
+        if process == WEB_PROCESS || spec.processes.iter().any(|p| p.name == process) { continue; }
+        let Some(obj) = d.metadata.name else { continue };
+        match api.delete(&obj, &DeleteParams::default()).await {
+            Ok(_) => tracing::info!(app=%spec.app, process, "removed deployment of dropped process type"),
+            Err(kube::Error::Api(ae)) if ae.code == 404 => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Server-side apply the app Service and Ingress (deleting the Ingress once the app has no routes left).
+#[cfg(not(feature = "mock-kube"))]
+pub async fn apply_routing(spec: &DeploySpec) -> Result<()> {
+    if std::env::var("AETHER_DISABLE_K8S").unwrap_or_default() == "1" {
+        tracing::info!(app=%spec.app, "AETHER_DISABLE_K8S=1 skipping real kube routing apply");
+        return Ok(());
+    }
+    let client = Client::try_default().await?;
+    let params = PatchParams::apply("aether-control-plane").force();
+    let svc_api: Api<Service> = Api::namespaced(client.clone(), &spec.namespace);
+    svc_api.patch(&spec.app, &params, &Patch::Apply(&build_service_manifest(spec))).await?;
+    let ing_api: Api<Ingress> = Api::namespaced(client, &spec.namespace);
+    match build_ingress_manifest(spec) {
+        Some(ing) => { ing_api.patch(&spec.app, &params, &Patch::Apply(&ing)).await?; }
+        None => match ing_api.delete(&spec.app, &DeleteParams::default()).await {
+            Ok(_) => tracing::info!(app=%spec.app, "removed ingress without routes"),
+            Err(kube::Error::Api(ae)) if ae.code == 404 => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+    Ok(())
+}
+
+/// Opaque Secret carrying the app's secret values (`None` when the app has no secrets; the Secret is then deleted).
+#[allow(dead_code)] // used in tests & runtime when k8s feature active
+fn build_secret_manifest(spec: &DeploySpec) -> Option<serde_json::Value> {
+    if spec.secrets.is_empty() { return None; }
+    Some(json!({
+        "apiVersion": "v1",
+        "kind": "Secret",
+        "type": "Opaque",
+        "metadata": {
+            "name": secret_name(&spec.app),
+            "namespace": spec.namespace,
+            "labels": {"app": spec.app, "app_name": spec.app}
+        },
+        "stringData": spec.secrets.0
+    }))
+}
+
+/// ClusterIP Service on port 80 forwarding to the `http` container port.
+#[allow(dead_code)] // used in tests & runtime when k8s feature active
+fn build_service_manifest(spec: &DeploySpec) -> serde_json::Value { service_manifest(spec, &spec.app, &spec.app) }
+
+/// Service `name` selecting the pods of the Deployment labelled `app=<target>`.
+#[allow(dead_code)] // used in tests & runtime when k8s feature active
+fn service_manifest(spec: &DeploySpec, name: &str, target: &str) -> serde_json::Value {
+    let app = spec.app.as_str();
+    json!({
+        "apiVersion": "v1",
+        "kind": "Service",
+        "metadata": {
+            "name": name,
+            "namespace": spec.namespace,
+            "labels": {"app": name, "app_name": app}
+        },
+        "spec": {
+            "type": "ClusterIP",
+            "selector": {"app": target},
+            "ports": [ {"name": "http", "port": 80, "targetPort": "http", "protocol": "TCP"} ]
+        }
+    })
+}
+
+/// Ingress with one rule per host (paths grouped under it, `Prefix` match); `None` without routes.
+#[allow(dead_code)] // used in tests & runtime when k8s feature active
+fn build_ingress_manifest(spec: &DeploySpec) -> Option<serde_json::Value> {
+    if spec.routes.is_empty() { return None; }
+    let app = spec.app.as_str();
+    let mut hosts: std::collections::BTreeMap<&str, Vec<serde_json::Value>> = Default::default();
+    for r in &spec.routes {
+        hosts.entry(r.host.as_str()).or_default().push(json!({
+            "path": r.path,
+            "pathType": "Prefix",
+            "backend": {"service": {"name": app, "port": {"name": "http"}}}
+        }));
+    }
+    let rules: Vec<serde_json::Value> = hosts.into_iter().map(|(host, paths)| json!({"host": host, "http": {"paths": paths}})).collect();
+    let mut ing = json!({
+        "apiVersion": "networking.k8s.io/v1",
+        "kind": "Ingress",
+        "metadata": {
+            "name": app,
+            "namespace": spec.namespace,
+            "labels": {"app": app, "app_name": app}
+        },
+        "spec": {"rules": rules}
+    });
+    if let Some(class) = &spec.ingress_class { ing["spec"]["ingressClassName"] = json!(class); }
+    Some(ing)
+}
+
+/// `<app>-canary` Ingress mirroring the app routes onto the candidate Service, with ingress-nginx canary annotations
+/// sending `weight` percent of the requests to it; `None` without routes.
+#[allow(dead_code)] // used in tests & runtime when k8s feature active
+fn build_canary_ingress_manifest(spec: &DeploySpec, weight: u8) -> Option<serde_json::Value> {
+    let mut ing = build_ingress_manifest(spec)?;
+    let name = format!("{}-canary", spec.app);
+    let backend = candidate_service_name(spec);
+    for rule in ing["spec"]["rules"].as_array_mut()? {
+        for path in rule["http"]["paths"].as_array_mut()? { path["backend"]["service"]["name"] = json!(backend); }
+    }
+    ing["metadata"]["name"] = json!(name);
+    ing["metadata"]["labels"]["app"] = json!(name);
+    ing["metadata"]["annotations"] = json!({
+        "nginx.ingress.kubernetes.io/canary": "true",
+        "nginx.ingress.kubernetes.io/canary-weight": weight.min(100).to_string()
+    });
+    Some(ing)
+}
+
+fn valid_digest(digest: &str) -> bool { digest.len()==64 && digest.chars().all(|c| c.is_ascii_hexdigit()) }
+
+/// Env of an app container: AETHER_* release info, `PORT` when given (unless the app config sets one), then the app config.
+fn container_env(spec: &DeploySpec, port: Option<i32>) -> Vec<serde_json::Value> {
+    let mut envs: Vec<serde_json::Value> = Vec::new();
+    if valid_digest(&spec.digest) { envs.push(json!({"name":"AETHER_DIGEST","value": format!("sha256:{}", spec.digest)})); }
+    if let Some(sig) = &spec.signature { envs.push(json!({"name":"AETHER_SIGNATURE","value": sig})); }
+    if spec.dev_hot { envs.push(json!({"name":"AETHER_DEV_HOT","value": "true"})); }
+    // An explicit PORT in the app config wins over the platform-injected one.
+    if let Some(port) = port.filter(|_| !spec.env.iter().any(|(k, _)| k == "PORT")) { envs.push(json!({"name":"PORT","value": port.to_string()})); }
+    for (k, v) in &spec.env { envs.push(json!({"name": k, "value": v})); }
+    envs
+}
+
+/// Termination message of the fetch init container when the artifact does not match its digest.
+pub const CHECKSUM_MISMATCH_MARKER: &str = "checksum_mismatch";
+
+/// Init container downloading the artifact into the shared `workspace` volume, verifying its sha256 when the digest is known.
+fn fetch_init_container(spec: &DeploySpec) -> serde_json::Value {
+    let (digest, artifact_url) = (spec.digest.as_str(), spec.artifact_url.as_str());
+    let mut init_cmd = format!("set -euo pipefail; echo Fetching artifact; wget -O /workspace/app.tar.gz {artifact_url};");
+    if valid_digest(digest) {
+        init_cmd.push_str(&format!(" echo '{digest}  /workspace/app.tar.gz' | sha256sum -c - || {{ echo {CHECKSUM_MISMATCH_MARKER} > /dev/termination-log; exit 3; }};"));
+    }
+    init_cmd.push_str(" tar -xzf /workspace/app.tar.gz -C /workspace");
+    json!({
+        "name": "fetch-artifact",
+        "image": "busybox:1.36",
+        "command": ["/bin/sh","-c"],
+        "args": [init_cmd],
+        "volumeMounts": [ {"name": "workspace", "mountPath": "/workspace" } ]
+    })
+}
+
+/// One-off Job running the release command from the new artifact (same fetch-and-verify init container, env and
+/// secrets as the app); `None` without a release command. Never retried: a failure fails the deployment.
+#[allow(dead_code)] // used in tests & runtime when k8s feature active
+fn build_release_job_manifest(spec: &DeploySpec) -> Option<serde_json::Value> {
+    let command = spec.release_command.as_ref()?;
+    let app = spec.app.as_str();
+    let rt = spec.runtime.unwrap_or_else(runtime::default_runtime);
+    let labels = json!({"app": format!("{app}-release"), "app_name": app, "process": "release"});
+    let mut annotations = json!({"aether.dev/artifact-url": spec.artifact_url, "aether.dev/deployment-id": spec.deployment_id.to_string()});
+    if valid_digest(&spec.digest) { annotations["aether.dev/digest"] = json!(format!("sha256:{}", spec.digest)); }
+    let env_from = if spec.secrets.is_empty() { json!([]) } else { json!([{"secretRef": {"name": secret_name(app)}}]) };
+    Some(json!({
+        "apiVersion": "batch/v1",
+        "kind": "Job",
+        "metadata": {
+            "name": release_job_name(app, spec.deployment_id),
+            "namespace": spec.namespace,
+            "labels": labels,
+            "annotations": annotations
+        },
+        "spec": {
+            "backoffLimit": 0,
+            "activeDeadlineSeconds": release_timeout_secs(),
+            "ttlSecondsAfterFinished": 86400,
+            "template": {
+                "metadata": {"labels": labels},
+                "spec": {
+                    "restartPolicy": "Never",
+                    "volumes": [ {"name": "workspace", "emptyDir": {} } ],
+                    "initContainers": [fetch_init_container(spec)],
+                    "containers": [{
+                        "name": "release",
+                        "image": rt.image,
+                        "workingDir": rt.workdir,
+                        "command": command,
+                        "volumeMounts": [ {"name": "workspace", "mountPath": rt.workdir } ],
+                        "env": container_env(spec, None),
+                        "envFrom": env_from,
+                    }]
+                }
+            }
+        }
+    }))
+}
+
+/// Deployment of the web process (named after the app, exposes the `http` port, probed).
+#[allow(dead_code)] // used in tests & runtime when k8s feature active
+fn build_deployment_manifest(spec: &DeploySpec) -> serde_json::Value { render_deployment(spec, None) }
+
+/// Deployment of a non-web process type: same artifact, env and secrets, its own command and replicas, no ports or probes.
+#[allow(dead_code)] // used in tests & runtime when k8s feature active
+fn build_process_manifest(spec: &DeploySpec, process: &ProcessSpec) -> serde_json::Value { render_deployment(spec, Some(process)) }
+
+/// Candidate web Deployment of a blue/green or canary release: the web manifest under [`candidate_name`], with its own
+/// `app` label and selector and an `aether.dev/track=candidate` label.
 #[allow(dead_code)] // used in tests & runtime when k8s feature active
-fn build_deployment_manifest(app: &str, digest: &str, artifact_url: &str, namespace: &str, signature: Option<&str>, dev_hot: bool) -> serde_json::Value {
+fn build_candidate_manifest(spec: &DeploySpec) -> serde_json::Value {
+    let mut d = render_deployment(spec, None);
+    let name = candidate_name(spec);
+    d["metadata"]["name"] = json!(name);
+    for labels in ["/metadata/labels", "/spec/template/metadata/labels"] {
+        if let Some(labels) = d.pointer_mut(labels) {
+            labels["app"] = json!(name);
+            labels["aether.dev/track"] = json!("candidate");
+        }
+    }
+    d["spec"]["selector"]["matchLabels"]["app"] = json!(name);
+    d
+}
+
+/// `RollingUpdate` strategy of a policy: surge 25% and no unavailable pods unless configured.
+fn render_strategy(policy: &RolloutPolicy) -> serde_json::Value {
+    let amount = |v: &Option<IntOrPercent>, default: serde_json::Value| match v {
+        Some(IntOrPercent::Int(n)) => json!(n),
+        Some(IntOrPercent::Percent(p)) => json!(p),
+        None => default,
+    };
+    json!({"type": "RollingUpdate", "rollingUpdate": {
+        "maxSurge": amount(&policy.max_surge, json!("25%")),
+        "maxUnavailable": amount(&policy.max_unavailable, json!(0)),
+    }})
+}
+
+fn render_deployment(spec: &DeploySpec, process: Option<&ProcessSpec>) -> serde_json::Value {
+    let (app, digest, artifact_url, namespace) = (spec.app.as_str(), spec.digest.as_str(), spec.artifact_url.as_str(), spec.namespace.as_str());
+    let process_type = process.map_or(WEB_PROCESS, |p| p.name.as_str());
+    let web = process.is_none();
+    let name = process_object_name(app, process_type);
+    let (signature, dev_hot) = (spec.signature.as_deref(), spec.dev_hot);
     // We construct JSON for server-side apply; using structured types for full compile checks would be more verbose.
     // init container: busybox sh -c "wget/curl artifact && tar -xzf ..."
     // For PoC use wget in busybox; production could switch to distroless + sha256 verify.
-    let valid_digest = digest.len()==64 && digest.chars().all(|c| c.is_ascii_hexdigit());
+    let valid_digest = valid_digest(digest);
     let mut annotations = json!({"aether.dev/artifact-url": artifact_url});
     if valid_digest { annotations["aether.dev/digest"] = json!(format!("sha256:{digest}")); }
     if signature.is_some() { annotations["aether.dev/signature"] = json!("ed25519"); }
     if dev_hot { annotations["aether.dev/dev-hot"] = json!("true"); }
-    let labels = json!({"app": app, "app_name": app});
+    let labels = json!({"app": name, "app_name": app, "process": process_type});
+    let rt = spec.runtime.unwrap_or_else(runtime::default_runtime);
+    let port = spec.port.unwrap_or(rt.default_port);
+    let command = match process {
+        Some(p) => p.command.clone(),
+        None => spec.command.clone().unwrap_or_else(|| rt.command.iter().map(|c| c.to_string()).collect()),
+    };
     // Build env array separately to avoid complex inline code in json! macro
-    let mut envs: Vec<serde_json::Value> = Vec::new();
-    if valid_digest { envs.push(json!({"name":"AETHER_DIGEST","value": format!("sha256:{digest}")})); }
-    if let Some(sig) = signature { envs.push(json!({"name":"AETHER_SIGNATURE","value": sig})); }
-    if dev_hot { envs.push(json!({"name":"AETHER_DEV_HOT","value": "true"})); }
+    let envs = container_env(spec, web.then_some(port));
+    let ports = json!([{"name": "http", "containerPort": port, "protocol": "TCP"}]);
+    let pre_stop = spec.pre_stop_sleep_seconds.unwrap_or(DEFAULT_PRE_STOP_SLEEP_SECS);
+    let lifecycle = if pre_stop > 0 { json!({"preStop": {"exec": {"command": ["sh", "-c", format!("sleep {pre_stop}")]}}}) } else { json!({}) };
+    let env_from = if spec.secrets.is_empty() { json!([]) } else { json!([{"secretRef": {"name": secret_name(app)}}]) };
+    let mut template_meta = json!({"labels": labels.clone()});
+    if !spec.secrets.is_empty() { template_meta["annotations"] = json!({"aether.dev/secrets-hash": spec.secrets.digest()}); }
 
     // Containers differ if dev_hot enabled: add fetcher sidecar polling pod annotations for new digest
     let (init_containers, containers) = if dev_hot {
@@ -109,53 +913,65 @@ done"#;
             },
             {
                 "name": "app",
-                "image": "aether-nodejs:20-slim",
-                "workingDir": "/workspace",
-                "command": ["node","server.js"],
-                "volumeMounts": [ {"name": "workspace", "mountPath": "/workspace" } ],
+                "image": rt.image,
+                "workingDir": rt.workdir,
+                "command": command,
+                "ports": ports,
+                "volumeMounts": [ {"name": "workspace", "mountPath": rt.workdir } ],
                 "env": envs,
+                "envFrom": env_from,
             }
         ]))
     } else {
         // Non dev-hot: single app container with init container performing first fetch
-        let mut init_cmd = format!("set -euo pipefail; echo Fetching artifact; wget -O /workspace/app.tar.gz {artifact_url};");
-        if valid_digest { init_cmd.push_str(&format!(" echo '{digest}  /workspace/app.tar.gz' | sha256sum -c -;")); }
-        init_cmd.push_str(" tar -xzf /workspace/app.tar.gz -C /workspace");
-        (json!([
-            {
-                "name": "fetch-artifact",
-                "image": "busybox:1.36",
-                "command": ["/bin/sh","-c"],
-                "args": [init_cmd],
-                "volumeMounts": [ {"name": "workspace", "mountPath": "/workspace" } ]
-            }
-        ]), json!([
+        (json!([fetch_init_container(spec)]), json!([
             {
                 "name": "app",
-                "image": "aether-nodejs:20-slim",
-                "workingDir": "/workspace",
-                "command": ["node","server.js"],
-                "volumeMounts": [ {"name": "workspace", "mountPath": "/workspace" } ],
+                "image": rt.image,
+                "workingDir": rt.workdir,
+                "command": command,
+                "ports": ports,
+                "volumeMounts": [ {"name": "workspace", "mountPath": rt.workdir } ],
                 "env": envs,
+                "envFrom": env_from,
             }
         ]))
     };
 
+    let mut containers = containers;
+    if let Some(app_container) = containers.as_array_mut().and_then(|cs| cs.iter_mut().find(|c| c["name"] == "app")).filter(|_| web) {
+        let defaults = rt.default_probes();
+        for (key, default, over) in [
+            ("startupProbe", &defaults.startup, &spec.probes.startup),
+            ("readinessProbe", &defaults.readiness, &spec.probes.readiness),
+            ("livenessProbe", &defaults.liveness, &spec.probes.liveness),
+        ] {
+            if let Some(p) = render_probe(over.as_ref(), &default.clone().unwrap_or_default(), port) { app_container[key] = p; }
+        }
+        app_container["lifecycle"] = lifecycle;
+    } else if let Some(obj) = containers.as_array_mut().and_then(|cs| cs.iter_mut().find(|c| c["name"] == "app")).and_then(|c| c.as_object_mut()) {
+        obj.remove("ports");
+    }
+
     json!({
         "apiVersion": "apps/v1",
         "kind": "Deployment",
         "metadata": {
-            "name": app,
+            "name": name,
             "namespace": namespace,
             "labels": labels,
             "annotations": annotations
         },
         "spec": {
-            "replicas": 1,
-            "selector": {"matchLabels": {"app": app}},
+            "replicas": process.map_or(spec.replicas.unwrap_or(1), |p| p.replicas),
+            "strategy": render_strategy(&spec.rollout),
+            "progressDeadlineSeconds": spec.rollout.progress_deadline_seconds.unwrap_or(DEFAULT_PROGRESS_DEADLINE_SECS),
+            "minReadySeconds": spec.rollout.min_ready_seconds.unwrap_or(0),
+            "selector": {"matchLabels": {"app": name}},
             "template": {
-                "metadata": {"labels": {"app": app, "app_name": app}},
+                "metadata": template_meta,
                 "spec": {
+                    "terminationGracePeriodSeconds": spec.termination_grace_period_seconds.unwrap_or(DEFAULT_TERMINATION_GRACE_SECS),
                     "volumes": [ {"name": "workspace", "emptyDir": {} } ],
                     "initContainers": init_containers,
                     "containers": containers
@@ -167,16 +983,37 @@ done"#;
 
 #[cfg(test)]
 mod tests {
-    use super::build_deployment_manifest;
+    use super::{build_candidate_manifest, build_canary_ingress_manifest, build_deployment_manifest, build_ingress_manifest, build_process_manifest, build_release_job_manifest, build_service_manifest, service_manifest, DeploySpec, ProcessSpec, Route};
+    use crate::models::{ProbeSettings, ProbeSpec};
+
+    fn spec(dev_hot: bool) -> DeploySpec {
+        DeploySpec {
+            app: "demo".into(),
+            namespace: "default".into(),
+            digest: "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef".into(),
+            artifact_url: "https://example/artifact.tar.gz".into(),
+            dev_hot,
+            ..DeploySpec::default()
+        }
+    }
+
+    #[test]
+    fn replicas_rendered_from_spec() {
+        assert_eq!(build_deployment_manifest(&spec(false))["spec"]["replicas"], 1);
+        let mut s = spec(false);
+        s.replicas = Some(3);
+        assert_eq!(build_deployment_manifest(&s)["spec"]["replicas"], 3);
+    }
+
     #[test]
     fn manifest_contains_annotation() {
-        let v = build_deployment_manifest("demo","0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef","https://example/artifact.tar.gz","default", None, false);
+        let v = build_deployment_manifest(&spec(false));
         assert!(v["metadata"]["annotations"]["aether.dev/digest"].as_str().unwrap().starts_with("sha256:"));
     }
 
     #[test]
     fn dev_hot_manifest_has_fetcher_sidecar() {
-        let v = build_deployment_manifest("demo","0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef","https://example/artifact.tar.gz","default", None, true);
+        let v = build_deployment_manifest(&spec(true));
         assert_eq!(v["metadata"]["annotations"]["aether.dev/dev-hot"].as_str().unwrap(), "true");
         let containers = v["spec"]["template"]["spec"]["containers"].as_array().unwrap();
         assert!(containers.iter().any(|c| c["name"].as_str()==Some("fetcher")), "fetcher sidecar missing");
@@ -186,7 +1023,7 @@ mod tests {
 
     #[test]
     fn dev_hot_fetcher_script_contains_checksum_and_interval() {
-        let v = build_deployment_manifest("demo","0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef","https://example/artifact.tar.gz","default", None, true);
+        let v = build_deployment_manifest(&spec(true));
         let containers = v["spec"]["template"]["spec"]["containers"].as_array().unwrap();
         let fetcher = containers.iter().find(|c| c["name"].as_str()==Some("fetcher")).expect("fetcher not found");
         let args = fetcher["args"].as_array().unwrap();
@@ -194,4 +1031,275 @@ mod tests {
         assert!(script.contains("sha256sum -c"), "checksum verification missing");
         assert!(script.contains("AETHER_FETCH_INTERVAL_SEC"), "interval env not referenced");
     }
+
+    #[test]
+    fn app_config_rendered_into_env() {
+        let mut s = spec(false);
+        s.env = vec![("DATABASE_URL".into(), "postgres://db/app".into()), ("LOG_LEVEL".into(), "debug".into())];
+        let v = build_deployment_manifest(&s);
+        let env = v["spec"]["template"]["spec"]["containers"][0]["env"].as_array().unwrap();
+        assert_eq!(env[0]["name"], "AETHER_DIGEST");
+        assert!(env.iter().any(|e| e["name"]=="DATABASE_URL" && e["value"]=="postgres://db/app"));
+        assert_eq!(env.last().unwrap()["name"], "LOG_LEVEL");
+    }
+
+    #[test]
+    fn secrets_projected_via_env_from() {
+        let mut s = spec(false);
+        assert!(super::build_secret_manifest(&s).is_none());
+        assert!(build_deployment_manifest(&s)["spec"]["template"]["spec"]["containers"][0]["envFrom"].as_array().unwrap().is_empty());
+        s.secrets.0.insert("DB_PASSWORD".into(), "hunter2".into());
+        let secret = super::build_secret_manifest(&s).unwrap();
+        assert_eq!(secret["metadata"]["name"], "demo-secrets");
+        assert_eq!(secret["stringData"]["DB_PASSWORD"], "hunter2");
+        let v = build_deployment_manifest(&s);
+        assert_eq!(v["spec"]["template"]["spec"]["containers"][0]["envFrom"][0]["secretRef"]["name"], "demo-secrets");
+        assert!(v["spec"]["template"]["metadata"]["annotations"]["aether.dev/secrets-hash"].is_string());
+        // values never end up inline in the Deployment or in Debug output
+        assert!(!v.to_string().contains("hunter2"));
+        assert!(!format!("{s:?}").contains("hunter2"));
+    }
+
+    #[test]
+    fn service_targets_container_port() {
+        let mut s = spec(false);
+        s.port = Some(8080);
+        let svc = build_service_manifest(&s);
+        assert_eq!(svc["spec"]["type"], "ClusterIP");
+        assert_eq!(svc["spec"]["selector"]["app"], "demo");
+        assert_eq!(svc["spec"]["ports"][0]["targetPort"], "http");
+        let app = &build_deployment_manifest(&s)["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(app["ports"][0]["name"], "http");
+        assert_eq!(app["ports"][0]["containerPort"], 8080);
+        assert!(app["env"].as_array().unwrap().iter().any(|e| e["name"]=="PORT" && e["value"]=="8080"));
+        // default port, and a user-provided PORT is not duplicated
+        let mut d = spec(false);
+        d.env = vec![("PORT".into(), "5000".into())];
+        let app = &build_deployment_manifest(&d)["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(app["ports"][0]["containerPort"], 3000);
+        assert_eq!(app["env"].as_array().unwrap().iter().filter(|e| e["name"]=="PORT").count(), 1);
+    }
+
+    #[test]
+    fn ingress_rules_grouped_by_host() {
+        let mut s = spec(false);
+        assert!(build_ingress_manifest(&s).is_none(), "no routes, no ingress");
+        s.routes = vec![
+            Route { host: "demo.apps.example.com".into(), path: "/".into() },
+            Route { host: "shop.example.com".into(), path: "/api".into() },
+            Route { host: "shop.example.com".into(), path: "/static".into() },
+        ];
+        s.ingress_class = Some("nginx".into());
+        let ing = build_ingress_manifest(&s).unwrap();
+        assert_eq!(ing["spec"]["ingressClassName"], "nginx");
+        let rules = ing["spec"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["host"], "demo.apps.example.com");
+        let shop = &rules[1]["http"]["paths"];
+        assert_eq!(shop.as_array().unwrap().len(), 2);
+        assert_eq!(shop[0]["path"], "/api");
+        assert_eq!(shop[0]["pathType"], "Prefix");
+        assert_eq!(shop[0]["backend"]["service"]["name"], "demo");
+        assert_eq!(shop[0]["backend"]["service"]["port"]["name"], "http");
+    }
+
+    #[test]
+    fn runtime_selects_image_command_and_port() {
+        let v = build_deployment_manifest(&spec(false));
+        let app = &v["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(app["image"], "aether-nodejs:20-slim");
+        assert_eq!(app["command"], serde_json::json!(["node", "server.js"]));
+        let mut s = spec(false);
+        s.runtime = crate::runtime::get("static");
+        let v = build_deployment_manifest(&s);
+        let app = &v["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(app["image"], "nginx:1.27-alpine");
+        assert_eq!(app["ports"][0]["containerPort"], 80);
+        assert_eq!(app["volumeMounts"][0]["mountPath"], "/usr/share/nginx/html");
+        assert_eq!(app["readinessProbe"]["httpGet"]["path"], "/");
+        // the init container still extracts into the shared volume
+        assert!(v["spec"]["template"]["spec"]["initContainers"][0]["args"][0].as_str().unwrap().contains("-C /workspace"));
+        s.runtime = crate::runtime::get("python3.12");
+        s.port = Some(5000);
+        s.command = Some(vec!["gunicorn".into(), "app:app".into()]);
+        let app = &build_deployment_manifest(&s)["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(app["image"], "python:3.12-slim");
+        assert_eq!(app["command"], serde_json::json!(["gunicorn", "app:app"]));
+        assert_eq!(app["ports"][0]["containerPort"], 5000);
+    }
+
+    #[test]
+    fn node_probe_and_termination_defaults() {
+        let v = build_deployment_manifest(&spec(false));
+        let pod = &v["spec"]["template"]["spec"];
+        assert_eq!(pod["terminationGracePeriodSeconds"], 30);
+        let app = &pod["containers"][0];
+        for key in ["startupProbe", "readinessProbe", "livenessProbe"] {
+            assert_eq!(app[key]["tcpSocket"]["port"], 3000, "{key} should default to a TCP check");
+        }
+        assert_eq!(app["startupProbe"]["failureThreshold"], 60);
+        assert_eq!(app["lifecycle"]["preStop"]["exec"]["command"][2], "sleep 5");
+        // the dev-hot fetcher sidecar is never probed
+        let dev = build_deployment_manifest(&spec(true));
+        let containers = dev["spec"]["template"]["spec"]["containers"].as_array().unwrap();
+        let fetcher = containers.iter().find(|c| c["name"] == "fetcher").unwrap();
+        assert!(fetcher.get("readinessProbe").is_none());
+        assert!(containers.iter().find(|c| c["name"] == "app").unwrap()["readinessProbe"].is_object());
+    }
+
+    #[test]
+    fn probe_overrides_merge_over_defaults() {
+        let mut s = spec(false);
+        s.port = Some(8080);
+        s.probes = ProbeSettings {
+            readiness: Some(ProbeSpec { http_path: Some("/ready".into()), failure_threshold: Some(6), ..ProbeSpec::default() }),
+            liveness: Some(ProbeSpec { enabled: Some(false), ..ProbeSpec::default() }),
+            startup: Some(ProbeSpec { port: Some(9000), ..ProbeSpec::default() }),
+        };
+        s.termination_grace_period_seconds = Some(60);
+        s.pre_stop_sleep_seconds = Some(0);
+        let v = build_deployment_manifest(&s);
+        let app = &v["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(app["readinessProbe"]["httpGet"]["path"], "/ready");
+        assert_eq!(app["readinessProbe"]["httpGet"]["port"], 8080);
+        assert_eq!(app["readinessProbe"]["failureThreshold"], 6);
+        assert_eq!(app["readinessProbe"]["periodSeconds"], 5, "unset fields keep the default");
+        assert!(app.get("livenessProbe").is_none());
+        assert_eq!(app["startupProbe"]["tcpSocket"]["port"], 9000);
+        assert!(app["lifecycle"].get("preStop").is_none());
+        assert_eq!(v["spec"]["template"]["spec"]["terminationGracePeriodSeconds"], 60);
+    }
+
+    #[test]
+    fn process_types_render_separate_deployments() {
+        let mut s = spec(false);
+        s.replicas = Some(2);
+        let worker = ProcessSpec { name: "worker".into(), command: vec!["sh".into(), "-c".into(), "exec node worker.js".into()], replicas: 3 };
+        s.processes = vec![worker.clone()];
+        let v = build_process_manifest(&s, &worker);
+        assert_eq!(v["metadata"]["name"], "demo-worker");
+        assert_eq!(v["metadata"]["labels"]["app_name"], "demo");
+        assert_eq!(v["metadata"]["labels"]["process"], "worker");
+        assert_eq!(v["spec"]["replicas"], 3);
+        // distinct selector so the web Service (selector app=demo) never routes to worker pods
+        assert_eq!(v["spec"]["selector"]["matchLabels"]["app"], "demo-worker");
+        assert_eq!(v["spec"]["template"]["metadata"]["labels"]["app"], "demo-worker");
+        let app = &v["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(app["command"][2], "exec node worker.js");
+        assert!(app.get("ports").is_none() && app.get("readinessProbe").is_none() && app.get("lifecycle").is_none());
+        assert!(!app["env"].as_array().unwrap().iter().any(|e| e["name"] == "PORT"));
+        assert_eq!(v["metadata"]["annotations"]["aether.dev/digest"], build_deployment_manifest(&s)["metadata"]["annotations"]["aether.dev/digest"]);
+        // the web Deployment keeps its name, selector and replica count
+        let web = build_deployment_manifest(&s);
+        assert_eq!(web["metadata"]["name"], "demo");
+        assert_eq!(web["metadata"]["labels"]["process"], "web");
+        assert_eq!(web["spec"]["selector"]["matchLabels"], serde_json::json!({"app": "demo"}));
+        assert_eq!(web["spec"]["replicas"], 2);
+        assert_eq!(super::process_object_name("demo", "web"), "demo");
+    }
+
+    #[test]
+    fn release_job_reuses_fetch_init_container() {
+        let mut s = spec(false);
+        assert!(build_release_job_manifest(&s).is_none(), "no release command, no job");
+        s.deployment_id = uuid::Uuid::parse_str("0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0").unwrap();
+        s.release_command = Some(vec!["sh".into(), "-c".into(), "exec node migrate.js".into()]);
+        s.env = vec![("DATABASE_URL".into(), "postgres://db/app".into())];
+        let job = build_release_job_manifest(&s).unwrap();
+        assert_eq!(job["kind"], "Job");
+        assert_eq!(job["metadata"]["name"], "demo-release-0f1e2d3c");
+        assert_eq!(job["spec"]["backoffLimit"], 0);
+        let pod = &job["spec"]["template"]["spec"];
+        assert_eq!(pod["restartPolicy"], "Never");
+        let deployment = build_deployment_manifest(&s);
+        assert_eq!(pod["initContainers"][0], deployment["spec"]["template"]["spec"]["initContainers"][0]);
+        assert!(pod["initContainers"][0]["args"][0].as_str().unwrap().contains("sha256sum -c"));
+        assert!(pod["initContainers"][0]["args"][0].as_str().unwrap().contains("echo checksum_mismatch > /dev/termination-log"));
+        let release = &pod["containers"][0];
+        assert_eq!(release["command"][2], "exec node migrate.js");
+        assert_eq!(release["image"], "aether-nodejs:20-slim");
+        let env = release["env"].as_array().unwrap();
+        assert!(env.iter().any(|e| e["name"] == "DATABASE_URL") && !env.iter().any(|e| e["name"] == "PORT"));
+        assert!(release.get("readinessProbe").is_none());
+    }
+
+    #[test]
+    fn candidate_runs_beside_the_serving_deployment() {
+        let mut s = spec(false);
+        s.strategy = crate::models::RolloutStrategy::Canary { percent: 20 };
+        s.routes = vec![Route { host: "demo.example.com".into(), path: "/".into() }];
+        let name = super::candidate_name(&s);
+        assert_eq!(name, "demo-0123456789ab");
+        let d = build_candidate_manifest(&s);
+        assert_eq!(d["metadata"]["name"], "demo-0123456789ab");
+        assert_eq!(d["spec"]["selector"]["matchLabels"], serde_json::json!({"app": "demo-0123456789ab"}));
+        let labels = &d["spec"]["template"]["metadata"]["labels"];
+        assert_eq!(labels["app"], "demo-0123456789ab");
+        assert_eq!(labels["app_name"], "demo");
+        assert_eq!(labels["aether.dev/track"], "candidate");
+        assert_eq!(d["metadata"]["annotations"]["aether.dev/digest"], build_deployment_manifest(&s)["metadata"]["annotations"]["aether.dev/digest"]);
+        // the app Service keeps selecting the serving pods until promotion
+        assert_eq!(build_service_manifest(&s)["spec"]["selector"]["app"], "demo");
+        assert_eq!(service_manifest(&s, "demo", &name)["spec"]["selector"]["app"], "demo-0123456789ab");
+        assert_eq!(super::candidate_service_name(&s), "demo-canary");
+        let ing = build_canary_ingress_manifest(&s, 20).unwrap();
+        assert_eq!(ing["metadata"]["name"], "demo-canary");
+        assert_eq!(ing["metadata"]["annotations"]["nginx.ingress.kubernetes.io/canary"], "true");
+        assert_eq!(ing["metadata"]["annotations"]["nginx.ingress.kubernetes.io/canary-weight"], "20");
+        assert_eq!(ing["spec"]["rules"][0]["http"]["paths"][0]["backend"]["service"]["name"], "demo-canary");
+        assert_eq!(build_ingress_manifest(&s).unwrap()["spec"]["rules"][0]["http"]["paths"][0]["backend"]["service"]["name"], "demo");
+        s.routes.clear();
+        assert!(build_canary_ingress_manifest(&s, 20).is_none());
+        s.strategy = crate::models::RolloutStrategy::BlueGreen;
+        assert_eq!(super::candidate_service_name(&s), "demo-preview");
+        s.digest.clear();
+        s.deployment_id = uuid::Uuid::parse_str("0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0").unwrap();
+        assert_eq!(super::candidate_name(&s), "demo-0f1e2d3c4b5a");
+    }
+
+    #[test]
+    fn rollout_policy_rendered_into_every_deployment() {
+        use crate::models::{IntOrPercent, RolloutPolicy};
+        let mut s = spec(false);
+        let v = build_deployment_manifest(&s);
+        assert_eq!(v["spec"]["strategy"], serde_json::json!({"type": "RollingUpdate", "rollingUpdate": {"maxSurge": "25%", "maxUnavailable": 0}}));
+        assert_eq!(v["spec"]["progressDeadlineSeconds"], 240);
+        assert_eq!(v["spec"]["minReadySeconds"], 0);
+        assert_eq!(super::rollout_timeout_secs(&s.rollout), 300);
+        s.rollout = RolloutPolicy {
+            progress_deadline_seconds: Some(120),
+            min_ready_seconds: Some(10),
+            max_surge: Some(IntOrPercent::Int(2)),
+            max_unavailable: Some(IntOrPercent::Percent("50%".into())),
+            timeout_seconds: Some(900),
+        };
+        let worker = ProcessSpec { name: "worker".into(), command: vec!["node".into(), "worker.js".into()], replicas: 1 };
+        for v in [build_deployment_manifest(&s), build_process_manifest(&s, &worker), build_candidate_manifest(&s)] {
+            assert_eq!(v["spec"]["strategy"]["rollingUpdate"], serde_json::json!({"maxSurge": 2, "maxUnavailable": "50%"}));
+            assert_eq!(v["spec"]["progressDeadlineSeconds"], 120);
+            assert_eq!(v["spec"]["minReadySeconds"], 10);
+        }
+        assert_eq!(super::rollout_timeout_secs(&s.rollout), 900);
+    }
+
+    #[test]
+    fn drift_compares_digest_image_and_env() {
+        let mut s = spec(false);
+        s.env = vec![("MODE".into(), "prod".into())];
+        s.processes = vec![ProcessSpec { name: "worker".into(), command: vec!["node".into(), "worker.js".into()], replicas: 1 }];
+        let desired = super::desired_workloads(&s);
+        assert_eq!(desired.iter().map(|w| w.name.as_str()).collect::<Vec<_>>(), ["demo", "demo-worker"]);
+        let web = &desired[0];
+        assert_eq!(web.digest.as_deref(), Some(format!("sha256:{}", s.digest).as_str()));
+        assert!(web.env.contains(&("MODE".into(), "prod".into())));
+        assert!(web.drift(Some(web)).is_empty());
+        assert_eq!(web.drift(None), ["missing"]);
+
+        let mut live = build_deployment_manifest(&s);
+        live["metadata"]["annotations"]["aether.dev/digest"] = serde_json::json!("sha256:edited");
+        live["spec"]["template"]["spec"]["containers"][0]["env"].as_array_mut().unwrap().pop();
+        assert_eq!(web.drift(Some(&super::WorkloadState::from_manifest(&live))), ["digest", "env"]);
+        live["spec"]["template"]["spec"]["containers"][0]["image"] = serde_json::json!("nginx:latest");
+        assert_eq!(web.drift(Some(&super::WorkloadState::from_manifest(&live))), ["digest", "image", "env"]);
+    }
 }
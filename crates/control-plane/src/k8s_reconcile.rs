@@ -0,0 +1,90 @@
+//! Desired-state reconciler: periodically compares the release each app should be serving (its latest deployment, when
+//! that one is `running`) with the live Deployments and re-applies the release when an object was deleted or its digest
+//! annotation, image or env drifted (e.g. after `kubectl edit`). Releases still rolling out are left to the apply
+//! queue and the status watcher.
+use once_cell::sync::Lazy;
+use sqlx::{Pool, Postgres, Row};
+
+/// Wakes the reconciler ahead of its next pass (e.g. after the status watcher re-listed).
+static WAKE: Lazy<tokio::sync::Notify> = Lazy::new(tokio::sync::Notify::new);
+
+/// Ask for a reconciliation pass as soon as possible.
+pub fn request_pass() { WAKE.notify_one(); }
+
+/// A live workload that no longer matches its release.
+#[derive(Debug, Clone)]
+pub struct Drift {
+    pub deployment_id: uuid::Uuid,
+    pub app: String,
+    pub object: String,
+    /// `missing`, or the drifted fields (`digest`, `image`, `env`)
+    pub fields: Vec<&'static str>,
+}
+
+/// One pass over every app; returns the drift found (each drifted release was re-applied). Errors of one app are
+/// logged and do not stop the pass.
+pub async fn reconcile_once(db: &Pool<Postgres>) -> anyhow::Result<Vec<Drift>> {
+    if !crate::k8s::cluster_enabled() { return Ok(Vec::new()); }
+    let rows = sqlx::query("SELECT DISTINCT ON (d.app_id) d.id, d.status, a.name, COALESCE(q.dev_hot, FALSE) AS dev_hot
+        FROM deployments d JOIN applications a ON a.id = d.app_id
+        LEFT JOIN deployment_apply_queue q ON q.deployment_id = d.id
+        WHERE d.status <> 'failed' ORDER BY d.app_id, d.created_at DESC")
+        .fetch_all(db).await?;
+    let mut drift = Vec::new();
+    for row in rows {
+        if row.get::<String, _>("status") != "running" { continue; }
+        let (id, app): (uuid::Uuid, String) = (row.get("id"), row.get("name"));
+        match reconcile_release(db, id, &app, row.get("dev_hot")).await {
+            Ok(found) => drift.extend(found),
+            Err(e) => tracing::warn!(error=%e, deployment_id=%id, app=%app, "reconcile failed"),
+        }
+    }
+    Ok(drift)
+}
+
+/// Compare the stable workloads of a running release with the cluster and re-apply it on drift, recording a `drift`
+/// event per object and `drift_repaired` / `drift_repair_failed` for the re-apply.
+async fn reconcile_release(db: &Pool<Postgres>, id: uuid::Uuid, app: &str, dev_hot: bool) -> anyhow::Result<Vec<Drift>> {
+    let dep = crate::services::deployments::get_deployment(db, id).await?;
+    let spec = crate::services::deployments::build_spec(db, app, &dep, dev_hot).await?;
+    let desired = crate::k8s::desired_workloads(&spec);
+    let names: Vec<String> = desired.iter().map(|w| w.name.clone()).collect();
+    let live = crate::k8s::live_workloads(&spec.namespace, &names).await?;
+    let drift: Vec<Drift> = desired.iter().zip(&live)
+        .filter_map(|(want, have)| {
+            let fields = want.drift(have.as_ref());
+            (!fields.is_empty()).then(|| Drift { deployment_id: id, app: app.to_string(), object: want.name.clone(), fields })
+        })
+        .collect();
+    if drift.is_empty() { return Ok(drift); }
+    for d in &drift {
+        for field in &d.fields { crate::telemetry::RECONCILE_DRIFT.with_label_values(&[field]).inc(); }
+        let fields = d.fields.join(",");
+        crate::services::deployments::record_event(db, id, "drift", Some(&format!("object={} fields={fields}", d.object))).await;
+        tracing::warn!(deployment_id=%id, app, object=%d.object, fields, "workload drift detected");
+    }
+    match crate::k8s::apply_deployment(&spec).await {
+        Ok(()) => {
+            crate::telemetry::RECONCILE_REPAIRS.with_label_values(&["repaired"]).inc();
+            crate::services::deployments::record_event(db, id, "drift_repaired", None).await;
+        }
+        Err(e) => {
+            crate::telemetry::RECONCILE_REPAIRS.with_label_values(&["failed"]).inc();
+            crate::services::deployments::record_event(db, id, "drift_repair_failed", Some(&e.to_string())).await;
+            tracing::warn!(error=%e, deployment_id=%id, app, "drift repair failed");
+        }
+    }
+    Ok(drift)
+}
+
+/// Reconcile every `AETHER_RECONCILE_INTERVAL_SECS` (default 60), or earlier when [`request_pass`] is called.
+pub async fn run_reconciler(db: Pool<Postgres>) {
+    let interval = std::env::var("AETHER_RECONCILE_INTERVAL_SECS").ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(60);
+    loop {
+        tokio::select! {
+            _ = WAKE.notified() => {}
+            _ = tokio::time::sleep(std::time::Duration::from_secs(interval.max(5))) => {}
+        }
+        if let Err(e) = reconcile_once(&db).await { tracing::warn!(error=%e, "reconcile_pass_failed"); }
+    }
+}
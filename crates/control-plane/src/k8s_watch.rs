@@ -1,63 +1,266 @@
 use sqlx::Pool;
 use kube::{Client, api::{ListParams, ResourceExt}, Api};
 use kube_runtime::watcher::{watcher, Config, Event};
-use futures_util::StreamExt;
+use futures_util::{stream::BoxStream, StreamExt};
 use sqlx::Row;
 use k8s_openapi::api::apps::v1::Deployment as K8sDeployment;
-use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::api::core::v1::{Event as K8sEvent, Pod};
 use chrono::Utc;
+use crate::models::PodProgress;
+
+/// Namespaces to watch: `AETHER_WATCH_NAMESPACES` (comma separated) restricts the watch, otherwise
+/// Aether-labelled Deployments are watched cluster-wide so every per-app namespace is covered.
+fn watch_namespaces() -> Option<Vec<String>> {
+    let raw = std::env::var("AETHER_WATCH_NAMESPACES").ok()?;
+    let list: Vec<String> = raw.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+    if list.is_empty() { None } else { Some(list) }
+}
+
+/// One watch per namespace of [`watch_namespaces`] (or a cluster-wide one), merged.
+fn watch_streams<K>(client: &Client, cfg: Config) -> futures_util::stream::SelectAll<BoxStream<'static, kube_runtime::watcher::Result<Event<K>>>>
+where
+    K: kube::Resource<Scope = k8s_openapi::NamespaceResourceScope> + Clone + std::fmt::Debug + serde::de::DeserializeOwned + Send + 'static,
+    K::DynamicType: Default,
+{
+    let streams = match watch_namespaces() {
+        Some(nss) => nss.iter().map(|ns| watcher(Api::<K>::namespaced(client.clone(), ns), cfg.clone()).boxed()).collect::<Vec<_>>(),
+        None => vec![watcher(Api::<K>::all(client.clone()), cfg).boxed()],
+    };
+    futures_util::stream::select_all(streams)
+}
 
 pub async fn run_deployment_status_watcher(db: Pool<sqlx::Postgres>) {
     let client = match Client::try_default().await {
         Ok(c) => c,
         Err(e) => { tracing::warn!(error=%e, "K8s client init failed"); return; }
     };
-    let d_api: Api<K8sDeployment> = Api::namespaced(client.clone(), "default");
-    let stream = watcher(d_api, Config::default());
-    futures_util::pin_mut!(stream);
+    let mut stream = watch_streams::<K8sDeployment>(&client, Config::default().labels("app_name"));
     while let Some(ev) = stream.next().await {
         match ev {
-            Ok(Event::Applied(d_obj)) => {
-                let app_name = d_obj.name_any();
-                let status = d_obj.status.clone();
-                let available = status.as_ref().and_then(|s| s.available_replicas).unwrap_or(0);
-                // Find pending deployment in DB
-                if let Ok(Some(row)) = sqlx::query("SELECT d.id, d.created_at FROM deployments d JOIN applications a ON a.id = d.app_id WHERE a.name = $1 AND d.status = 'pending' LIMIT 1")
-                    .bind(&app_name).fetch_optional(&db).await {
-                        let dep_id: uuid::Uuid = row.get("id");
-                        let created_at: chrono::DateTime<chrono::Utc> = row.get("created_at");
-                        if available >= 1 {
-                            crate::services::deployments::mark_running(&db, dep_id).await;
-                            tracing::info!(deployment_id=%dep_id, app=%app_name, "deployment running (watch)");
-                            continue;
-                        }
-                        // Failure heuristics
-                        let mut failed_reason: Option<String> = None;
-                        if let Some(st) = status {
-                            if let Some(conds) = st.conditions {
-                                for c in conds { if c.type_=="Progressing" && c.status=="False" { failed_reason = Some(c.reason.unwrap_or_else(|| "progress_failed".into())); break; } }
-                            }
-                        }
-                        // Pod-level inspection for init container failures
-                        if failed_reason.is_none() {
-                            let p_api: Api<Pod> = Api::namespaced(client.clone(), "default");
-                            if let Ok(pods) = p_api.list(&ListParams::default().labels(&format!("app={}", app_name))).await {
-                                'podloop: for p in pods { if let Some(ps) = p.status { if let Some(ics) = ps.init_container_statuses { for ics in ics { if let Some(state) = ics.state { if let Some(term) = state.terminated { if term.exit_code != 0 { failed_reason = Some(format!("init:{}:{}", ics.name, term.reason.unwrap_or_else(|| term.exit_code.to_string()))); break 'podloop; } } } } } } }
-                            }
-                        }
-                        // Timeout heuristic (>300s)
-                        if failed_reason.is_none()
-                            && Utc::now().signed_duration_since(created_at).num_seconds() > 300
-                        {
-                            failed_reason = Some("timeout".into());
-                        }
-                        if let Some(rsn) = failed_reason { crate::services::deployments::mark_failed(&db, dep_id, &rsn).await; tracing::warn!(deployment_id=%dep_id, app=%app_name, reason=%rsn, "deployment failed (watch)"); }
-                }
-            }
+            Ok(Event::Applied(d_obj)) => handle_applied(&db, &client, d_obj).await,
+            // (re)list after a start or a dropped watch: updates missed meanwhile are in this backlog, and objects
+            // deleted meanwhile are absent from it, so the reconciler checks the cluster too
             Ok(Event::Restarted(objs)) => {
-                for d_obj in objs { let app_name = d_obj.name_any(); /* ignore restarted backlog for simplicity */ let _ = app_name; }
+                tracing::info!(objects=objs.len(), "deployment watch (re)started");
+                for d_obj in objs { handle_applied(&db, &client, d_obj).await; }
+                crate::k8s_reconcile::request_pass();
             }
             _ => {}
         }
     }
-}
\ No newline at end of file
+}
+
+/// Pending deployment an observed Deployment object belongs to.
+struct PendingRelease {
+    id: uuid::Uuid,
+    /// Creation, or start of the promotion, of the release (timeout reference)
+    since: chrono::DateTime<Utc>,
+    process: String,
+    phase: Option<String>,
+    /// Rollout policy of the app (watcher timeout)
+    policy: crate::models::RolloutPolicy,
+}
+
+/// Objects are matched through the Deployments recorded for each release, so the serving Deployment and the candidate of a
+/// blue/green or canary rollout are told apart; releases recorded before process tracking match by app name.
+async fn pending_for_object(db: &Pool<sqlx::Postgres>, object_name: &str, app_name: &str, process: &str, namespace: &str) -> Option<PendingRelease> {
+    let tracked = sqlx::query("SELECT d.id, d.last_transition_at, dp.process_type, d.rollout_phase, a.rollout_policy FROM deployment_processes dp
+        JOIN deployments d ON d.id = dp.deployment_id JOIN applications a ON a.id = d.app_id
+        WHERE dp.object_name = $1 AND a.name = $2 AND a.namespace = $3 AND d.status = 'pending' ORDER BY d.created_at DESC LIMIT 1")
+        .bind(object_name).bind(app_name).bind(namespace).fetch_optional(db).await.ok().flatten();
+    let row = match tracked {
+        Some(row) => row,
+        None => sqlx::query("SELECT d.id, d.last_transition_at, $3::text AS process_type, d.rollout_phase, a.rollout_policy FROM deployments d JOIN applications a ON a.id = d.app_id
+            WHERE a.name = $1 AND a.namespace = $2 AND d.status = 'pending'
+              AND NOT EXISTS (SELECT 1 FROM deployment_processes dp WHERE dp.deployment_id = d.id) LIMIT 1")
+            .bind(app_name).bind(namespace).bind(process).fetch_optional(db).await.ok().flatten()?,
+    };
+    let policy: sqlx::types::Json<crate::models::RolloutPolicy> = row.get("rollout_policy");
+    Some(PendingRelease { id: row.get("id"), since: row.get("last_transition_at"), process: row.get("process_type"), phase: row.get("rollout_phase"), policy: policy.0 })
+}
+
+/// One release spans a Deployment per process type (or a single candidate Deployment for blue/green and canary
+/// rollouts): each object is matched to its pending deployment, and the release is ready once every tracked
+/// Deployment is (any of them failing fails it).
+async fn handle_applied(db: &Pool<sqlx::Postgres>, client: &Client, d_obj: K8sDeployment) {
+    let object_name = d_obj.name_any();
+    let labels = d_obj.labels();
+    let app_name = labels.get("app_name").cloned().unwrap_or_else(|| object_name.clone());
+    let label_process = labels.get("process").cloned().unwrap_or_else(|| crate::k8s::WEB_PROCESS.to_string());
+    let namespace = d_obj.namespace().unwrap_or_else(|| "default".into());
+    let status = d_obj.status.clone();
+    let available = status.as_ref().and_then(|s| s.available_replicas).unwrap_or(0);
+    let updated = status.as_ref().and_then(|s| s.updated_replicas).unwrap_or(0);
+    let ready = status.as_ref().and_then(|s| s.ready_replicas).unwrap_or(0);
+    let desired = d_obj.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+    // Find pending deployment in DB (app must live in the namespace the object was seen in)
+    if let Some(PendingRelease { id: dep_id, since, process, phase, policy }) = pending_for_object(db, &object_name, &app_name, &label_process, &namespace).await {
+            let p_api: Api<Pod> = Api::namespaced(client.clone(), &namespace);
+            let pods = p_api.list(&ListParams::default().labels(&format!("app={}", object_name))).await.map(|l| l.items).unwrap_or_default();
+            let progress: Vec<PodProgress> = pods.iter().map(pod_progress).collect();
+            if let Err(e) = crate::services::processes::observe_progress(db, dep_id, &process, updated, ready, &progress).await {
+                tracing::warn!(error=%e, deployment_id=%dep_id, "recording rollout progress failed");
+            }
+            let all_ready = match crate::services::processes::observe(db, dep_id, &process, desired, available).await {
+                Ok(Some(all_ready)) => all_ready,
+                // release applied before process tracking: the web Deployment alone decides
+                Ok(None) => process == crate::k8s::WEB_PROCESS && available >= 1,
+                Err(e) => { tracing::warn!(error=%e, deployment_id=%dep_id, "recording process state failed"); false }
+            };
+            if all_ready {
+                crate::services::rollouts::on_ready(db, dep_id).await;
+                tracing::debug!(deployment_id=%dep_id, app=%app_name, namespace=%namespace, object=%object_name, "release ready (watch)");
+                return;
+            }
+            if desired == 0 { return; }
+            // Pod-level inspection first: crash-looping or unpullable new pods fail the release even while pods of the
+            // previous one are still available
+            let restarts = crash_loop_restarts();
+            let mut failed_reason = pods.iter().find_map(|p| pod_failure(p, restarts));
+            if failed_reason.is_none() && progress.iter().any(|p| !p.ready) {
+                let e_api: Api<K8sEvent> = Api::namespaced(client.clone(), &namespace);
+                if let Ok(events) = e_api.list(&ListParams::default().fields("type=Warning")).await {
+                    failed_reason = events.items.iter()
+                        .filter(|e| e.involved_object.kind.as_deref() == Some("Pod") && progress.iter().any(|p| e.involved_object.name.as_deref() == Some(p.name.as_str())))
+                        .find_map(|e| event_failure(e, restarts)).map(str::to_string);
+                }
+            }
+            if failed_reason.is_none() && available >= 1 { return; } // this process is fine, others are still rolling out
+            // Kubernetes reports ProgressDeadlineExceeded after the policy's progressDeadlineSeconds
+            if failed_reason.is_none() {
+                if let Some(conds) = status.and_then(|st| st.conditions) {
+                    for c in conds { if c.type_=="Progressing" && c.status=="False" { failed_reason = Some(c.reason.unwrap_or_else(|| "progress_failed".into())); break; } }
+                }
+            }
+            // Timeout heuristic (policy timeout since creation or promotion); a ready candidate waits for promotion as long as it takes
+            if failed_reason.is_none()
+                && phase.as_deref() != Some(crate::services::rollouts::PHASE_AWAITING_PROMOTION)
+                && Utc::now().signed_duration_since(since).num_seconds() > crate::k8s::rollout_timeout_secs(&policy)
+            {
+                failed_reason = Some("timeout".into());
+            }
+            if let Some(mut rsn) = failed_reason {
+                if process != crate::k8s::WEB_PROCESS { rsn = format!("{process}:{rsn}"); }
+                crate::services::rollouts::on_failed(db, dep_id, &rsn).await;
+                tracing::warn!(deployment_id=%dep_id, app=%app_name, object=%object_name, process=%process, reason=%rsn, "deployment failed (watch)");
+            }
+    }
+}
+
+/// Progress of one pod as reported for its main `app` container (the first container when there is none).
+pub fn pod_progress(pod: &Pod) -> PodProgress {
+    let status = pod.status.as_ref();
+    let containers = status.and_then(|s| s.container_statuses.as_deref()).unwrap_or_default();
+    let main = containers.iter().find(|c| c.name == "app").or(containers.first());
+    let terminated = main.and_then(|c| {
+        c.state.as_ref().and_then(|s| s.terminated.as_ref())
+            .or_else(|| c.last_state.as_ref().and_then(|s| s.terminated.as_ref()))
+    });
+    PodProgress {
+        name: pod.name_any(),
+        phase: status.and_then(|s| s.phase.clone()).unwrap_or_else(|| "Unknown".into()),
+        ready: status.and_then(|s| s.conditions.as_ref())
+            .is_some_and(|cs| cs.iter().any(|c| c.type_ == "Ready" && c.status == "True")),
+        restart_count: main.map_or(0, |c| c.restart_count),
+        last_termination_reason: terminated.map(|t| t.reason.clone().unwrap_or_else(|| format!("exit:{}", t.exit_code))),
+    }
+}
+
+/// Restarts of a main container in `CrashLoopBackOff` before the release fails (`AETHER_CRASH_LOOP_RESTARTS`, default 3).
+fn crash_loop_restarts() -> i32 {
+    std::env::var("AETHER_CRASH_LOOP_RESTARTS").ok().and_then(|v| v.parse::<i32>().ok()).unwrap_or(3).max(1)
+}
+
+/// Failure reason of a pod: `checksum_mismatch` (artifact verification in the fetch init container), `image_pull`,
+/// `oom_killed` or `crash_loop` (main container crash-looping for `restarts` restarts, OOM kills told apart by the last
+/// termination), or `init:<container>:<reason>` for other init container failures. `None` while the pod looks healthy.
+pub fn pod_failure(pod: &Pod, restarts: i32) -> Option<String> {
+    let status = pod.status.as_ref()?;
+    for ics in status.init_container_statuses.iter().flatten() {
+        let state = ics.state.as_ref();
+        if let Some(term) = state.and_then(|s| s.terminated.as_ref()).filter(|t| t.exit_code != 0) {
+            if term.message.as_deref().is_some_and(|m| m.contains(crate::k8s::CHECKSUM_MISMATCH_MARKER)) {
+                return Some("checksum_mismatch".into());
+            }
+            return Some(format!("init:{}:{}", ics.name, term.reason.clone().unwrap_or_else(|| term.exit_code.to_string())));
+        }
+        if state.and_then(|s| s.waiting.as_ref()).and_then(|w| w.reason.as_deref()).is_some_and(image_pull_reason) {
+            return Some("image_pull".into());
+        }
+    }
+    let containers = status.container_statuses.as_deref().unwrap_or_default();
+    let main = containers.iter().find(|c| c.name == "app").or(containers.first())?;
+    let waiting = main.state.as_ref().and_then(|s| s.waiting.as_ref()).and_then(|w| w.reason.as_deref());
+    if waiting.is_some_and(image_pull_reason) { return Some("image_pull".into()); }
+    if waiting == Some("CrashLoopBackOff") && main.restart_count >= restarts {
+        let oom = main.last_state.as_ref().and_then(|s| s.terminated.as_ref()).and_then(|t| t.reason.as_deref()) == Some("OOMKilled");
+        return Some(if oom { "oom_killed" } else { "crash_loop" }.into());
+    }
+    None
+}
+
+fn image_pull_reason(reason: &str) -> bool {
+    matches!(reason, "ImagePullBackOff" | "ErrImagePull" | "InvalidImageName" | "ErrImageNeverPull")
+}
+
+/// Failure reason signalled by a Warning event about a pod: `image_pull` for pull failures, `crash_loop` once the
+/// container restart back-off was reported `restarts` times.
+pub fn event_failure(event: &K8sEvent, restarts: i32) -> Option<&'static str> {
+    if event.type_.as_deref() != Some("Warning") { return None; }
+    let message = event.message.as_deref().unwrap_or_default().to_ascii_lowercase();
+    match event.reason.as_deref()? {
+        "Failed" | "BackOff" if message.contains("pull") && message.contains("image") => Some("image_pull"),
+        "ErrImagePull" | "ImagePullBackOff" => Some("image_pull"),
+        "BackOff" if message.contains("restarting failed container") && event.count.unwrap_or(1) >= restarts => Some("crash_loop"),
+        _ => None,
+    }
+}
+
+/// Watch Warning events and store those about Aether-managed pods, ReplicaSets and Deployments on the deployment
+/// currently tracking the object (see [`ingest_event`]).
+pub async fn run_event_watcher(db: Pool<sqlx::Postgres>) {
+    let client = match Client::try_default().await {
+        Ok(c) => c,
+        Err(e) => { tracing::warn!(error=%e, "K8s client init failed"); return; }
+    };
+    let mut stream = watch_streams::<K8sEvent>(&client, Config::default().fields("type=Warning"));
+    while let Some(ev) = stream.next().await {
+        // re-listed events after a restart are deduplicated by UID
+        let events = match ev {
+            Ok(Event::Applied(e)) => vec![e],
+            Ok(Event::Restarted(es)) => es,
+            _ => continue,
+        };
+        for e in &events {
+            if let Err(err) = ingest_event(&db, e).await { tracing::warn!(error=%err, "k8s event ingest failed"); }
+        }
+    }
+}
+
+/// Deployment object an event is about: Deployments by name, ReplicaSets (`<deployment>-<hash>`) and pods
+/// (`<deployment>-<hash>-<suffix>`) by stripping the generated suffixes.
+pub fn event_object_name(kind: &str, name: &str) -> Option<String> {
+    let suffixes = match kind { "Deployment" => 0, "ReplicaSet" => 1, "Pod" => 2, _ => return None };
+    let mut name = name;
+    for _ in 0..suffixes { name = name.rsplit_once('-')?.0; }
+    Some(name.to_string())
+}
+
+/// Store a Warning event as a `k8s_event` of the latest deployment tracking its object in the event's namespace.
+/// Returns whether a row was written (other events, unmanaged objects and known UIDs are skipped).
+pub async fn ingest_event(db: &Pool<sqlx::Postgres>, event: &K8sEvent) -> anyhow::Result<bool> {
+    if event.type_.as_deref() != Some("Warning") { return Ok(false); }
+    let obj = &event.involved_object;
+    let (Some(uid), Some(kind), Some(name)) = (event.metadata.uid.as_deref(), obj.kind.as_deref(), obj.name.as_deref()) else { return Ok(false); };
+    let Some(object_name) = event_object_name(kind, name) else { return Ok(false); };
+    let namespace = obj.namespace.as_deref().or(event.metadata.namespace.as_deref()).unwrap_or("default");
+    let dep_id: Option<uuid::Uuid> = sqlx::query_scalar("SELECT d.id FROM deployment_processes dp
+        JOIN deployments d ON d.id = dp.deployment_id JOIN applications a ON a.id = d.app_id
+        WHERE dp.object_name = $1 AND a.namespace = $2 ORDER BY d.created_at DESC LIMIT 1")
+        .bind(&object_name).bind(namespace).fetch_optional(db).await?;
+    let Some(dep_id) = dep_id else { return Ok(false); };
+    let reason = event.reason.clone().unwrap_or_default();
+    let message = format!("{reason}: {}", event.message.as_deref().unwrap_or_default());
+    let source = crate::services::deployments::K8sEventSource { uid: uid.to_string(), kind: kind.to_string(), name: name.to_string(), reason };
+    Ok(crate::services::deployments::record_k8s_event(db, dep_id, &source, &message).await?)
+}
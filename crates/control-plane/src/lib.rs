@@ -8,6 +8,10 @@ pub mod storage;
 pub mod test_support;
 pub mod k8s; // Kubernetes integration (Issue 04)
 pub mod k8s_watch;
+pub mod k8s_reconcile; // Desired-state reconciler repairing drifted workloads
+pub mod event_bus; // Cross-replica fan-out of deployment / artifact events (Postgres LISTEN/NOTIFY)
+pub mod secrets; // Envelope encryption for app secrets
+pub mod runtime; // Runtime registry (image / command / defaults per runtime id)
 #[cfg(feature = "dev-hot-ingest")]
 pub mod dev_hot_ingest; // New module for hot ingest development (feature-gated)
 
@@ -33,9 +37,16 @@ pub struct AppState { pub db: Pool<Postgres> }
         handlers::apps::create_app,
         handlers::apps::list_apps,
         handlers::apps::app_deployments,
+        handlers::apps::get_app,
+        handlers::apps::patch_app,
+        handlers::apps::scale_app,
+        handlers::apps::list_processes,
         handlers::deployments::create_deployment,
     handlers::deployments::list_deployments,
         handlers::deployments::get_deployment,
+        handlers::deployments::rollback_app,
+        handlers::deployments::promote_deployment,
+        handlers::deployments::abort_deployment,
     handlers::uploads::upload_artifact,
     handlers::uploads::list_artifacts,
     handlers::uploads::presign_artifact,
@@ -45,6 +56,29 @@ pub struct AppState { pub db: Pool<Postgres> }
     handlers::uploads::multipart_presign_part,
     handlers::uploads::multipart_complete,
     handlers::apps::add_public_key,
+        handlers::apps::list_public_keys,
+        handlers::apps::delete_public_key,
+        handlers::apps::rotate_public_key,
+        handlers::events::deployment_events,
+        handlers::events::artifact_events,
+        handlers::events::app_events,
+        handlers::events::watch_deployment,
+        handlers::events::app_event_stream,
+        handlers::config::get_app_config,
+        handlers::config::put_app_config,
+        handlers::config::delete_app_config,
+        handlers::secrets::list_secrets,
+        handlers::secrets::put_secret,
+        handlers::secrets::delete_secret,
+        handlers::secrets::rotate_master_key,
+        handlers::webhooks::list_webhooks,
+        handlers::webhooks::create_webhook,
+        handlers::webhooks::delete_webhook,
+        handlers::webhooks::list_deliveries,
+        handlers::domains::list_domains,
+        handlers::domains::add_domain,
+        handlers::domains::delete_domain,
+        handlers::runtimes::list_runtimes,
     ),
     components(schemas(error::ApiErrorBody)),
     tags( (name = "aether", description = "Aether Control Plane API") )
@@ -101,6 +135,29 @@ pub fn build_router(state: AppState) -> Router {
                 tokio::time::sleep(std::time::Duration::from_secs(interval.max(30))).await;
             }
         });
+        // Event bus listener: re-broadcast events published by other replicas
+        crate::event_bus::spawn_listener(&state.db);
+        // Webhook delivery worker (persistent queue, retried with backoff)
+        let db_hooks = state.db.clone();
+        tokio::spawn(async move {
+            let interval = std::env::var("AETHER_WEBHOOK_INTERVAL_SECS").ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(2);
+            let client = crate::services::webhooks::http_client();
+            loop {
+                match crate::services::webhooks::deliver_due(&db_hooks, &client).await {
+                    Ok(n) if n > 0 => continue,
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!(error=%e, "webhook_delivery_batch_failed"),
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(interval.max(1))).await;
+            }
+        });
+        // Kubernetes apply queue workers
+        let workers = std::env::var("AETHER_APPLY_WORKERS").ok().and_then(|v| v.parse::<usize>().ok()).unwrap_or(2);
+        for _ in 0..workers.max(1) {
+            tokio::spawn(crate::services::apply_queue::run_worker(state.db.clone()));
+        }
+        // Desired-state reconciler
+        tokio::spawn(crate::k8s_reconcile::run_reconciler(state.db.clone()));
     } else {
         tracing::info!("background_tasks_disabled");
     }
@@ -110,6 +167,7 @@ pub fn build_router(state: AppState) -> Router {
         tokio::spawn(async move {
             crate::k8s_watch::run_deployment_status_watcher(db_status).await;
         });
+        tokio::spawn(crate::k8s_watch::run_event_watcher(state.db.clone()));
     }
     Router::new()
         .route("/health", get(health))
@@ -118,6 +176,10 @@ pub fn build_router(state: AppState) -> Router {
         .route("/metrics", get(metrics_handler))
     .route("/deployments", post(create_deployment).get(list_deployments))
     .route("/deployments/:id", get(get_deployment).patch(handlers::deployments::update_deployment))
+    .route("/deployments/:id/events", get(handlers::events::deployment_events))
+    .route("/deployments/:id/watch", get(handlers::events::watch_deployment))
+    .route("/deployments/:id/promote", post(handlers::deployments::promote_deployment))
+    .route("/deployments/:id/abort", post(handlers::deployments::abort_deployment))
     .route("/artifacts", post(upload_artifact).get(list_artifacts))
     .route("/artifacts/presign", post(presign_artifact))
     .route("/artifacts/complete", post(complete_artifact))
@@ -126,11 +188,30 @@ pub fn build_router(state: AppState) -> Router {
     .route("/artifacts/multipart/complete", post(multipart_complete))
     .route("/artifacts/:digest", axum::routing::head(head_artifact))
     .route("/artifacts/:digest/meta", get(handlers::uploads::artifact_meta))
+    .route("/artifacts/:digest/events", get(handlers::events::artifact_events))
+        .route("/runtimes", get(handlers::runtimes::list_runtimes))
         .route("/apps", post(create_app))
         .route("/apps", get(list_apps))
+        .route("/apps/:app_name", get(handlers::apps::get_app).patch(handlers::apps::patch_app))
+        .route("/apps/:app_name/scale", axum::routing::put(handlers::apps::scale_app))
+        .route("/apps/:app_name/processes", get(handlers::apps::list_processes))
         .route("/apps/:app_name/deployments", get(app_deployments))
         .route("/apps/:app_name/logs", get(app_logs))
-        .route("/apps/:app_name/public-keys", post(add_public_key))
+        .route("/apps/:app_name/events", get(handlers::events::app_events))
+        .route("/apps/:app_name/events/stream", get(handlers::events::app_event_stream))
+        .route("/apps/:app_name/config", get(handlers::config::get_app_config).put(handlers::config::put_app_config).delete(handlers::config::delete_app_config))
+        .route("/apps/:app_name/secrets", get(handlers::secrets::list_secrets))
+        .route("/apps/:app_name/secrets/:name", axum::routing::put(handlers::secrets::put_secret).delete(handlers::secrets::delete_secret))
+        .route("/secrets/rotate-master-key", post(handlers::secrets::rotate_master_key))
+        .route("/apps/:app_name/webhooks", get(handlers::webhooks::list_webhooks).post(handlers::webhooks::create_webhook))
+        .route("/apps/:app_name/webhooks/:id", axum::routing::delete(handlers::webhooks::delete_webhook))
+        .route("/apps/:app_name/webhooks/:id/deliveries", get(handlers::webhooks::list_deliveries))
+        .route("/apps/:app_name/domains", get(handlers::domains::list_domains).post(handlers::domains::add_domain))
+        .route("/apps/:app_name/domains/:hostname", axum::routing::delete(handlers::domains::delete_domain))
+        .route("/apps/:app_name/rollback", post(handlers::deployments::rollback_app))
+        .route("/apps/:app_name/public-keys", post(add_public_key).get(handlers::apps::list_public_keys))
+        .route("/apps/:app_name/public-keys/rotate", post(handlers::apps::rotate_public_key))
+        .route("/apps/:app_name/public-keys/:id", axum::routing::delete(handlers::apps::delete_public_key))
     .route("/openapi.json", get(|| async move { axum::Json(openapi.clone()) }))
         .route("/swagger", get(swagger_ui))
         .with_state(state)
@@ -283,6 +364,30 @@ mod tests {
         assert_eq!(v["code"], "conflict");
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn create_app_with_namespace() {
+    let pool = crate::test_support::test_pool().await;
+        sqlx::query("DELETE FROM deployments").execute(&pool).await.ok();
+        sqlx::query("DELETE FROM applications").execute(&pool).await.ok();
+    let app_router = build_router(AppState { db: pool });
+        let post = |body: serde_json::Value| Request::builder().method("POST").uri("/apps")
+            .header("content-type","application/json")
+            .body(Body::from(body.to_string())).unwrap();
+        let res = app_router.clone().oneshot(post(json!({"name":"nsapp","namespace":"team-a"}))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let v: serde_json::Value = serde_json::from_slice(&axum::body::to_bytes(res.into_body(), 1024).await.unwrap()).unwrap();
+        assert_eq!(v["namespace"], "team-a");
+        let res = app_router.clone().oneshot(post(json!({"name":"plain"}))).await.unwrap();
+        let v: serde_json::Value = serde_json::from_slice(&axum::body::to_bytes(res.into_body(), 1024).await.unwrap()).unwrap();
+        assert_eq!(v["namespace"], "default");
+        let res = app_router.clone().oneshot(post(json!({"name":"bad","namespace":"Team_A"}))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res = app_router.oneshot(Request::builder().uri("/apps").body(Body::empty()).unwrap()).await.unwrap();
+        let v: serde_json::Value = serde_json::from_slice(&axum::body::to_bytes(res.into_body(), 4096).await.unwrap()).unwrap();
+        assert!(v.as_array().unwrap().iter().any(|a| a["name"]=="nsapp" && a["namespace"]=="team-a"));
+    }
+
     #[test]
     fn normalize_path_property() {
         use crate::telemetry::normalize_path;
@@ -290,5 +395,6 @@ mod tests {
         assert_eq!(normalize_path("/deployments/123"), "/deployments/:id");
         assert_eq!(normalize_path("/deployments/550e8400-e29b-41d4-a716-446655440000"), "/deployments/:id");
         assert_eq!(normalize_path("/apps/myapp/deployments"), "/apps/:app_name/deployments");
+        assert_eq!(normalize_path("/artifacts/0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef/events"), "/artifacts/:digest/events");
     }
 }
@@ -4,7 +4,7 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 #[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
-pub struct Application { pub id: Uuid, pub name: String, pub created_at: DateTime<Utc>, pub updated_at: DateTime<Utc> }
+pub struct Application { pub id: Uuid, pub name: String, pub created_at: DateTime<Utc>, pub updated_at: DateTime<Utc>, pub namespace: String }
 
 #[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
 pub struct Deployment {
@@ -36,3 +36,204 @@ pub struct Artifact {
 	pub idempotency_key: Option<String>,
 	pub multipart_upload_id: Option<String>,
 }
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DeploymentEvent {
+	pub id: i64,
+	pub deployment_id: Uuid,
+	pub event_type: String,
+	pub message: Option<String>,
+	pub created_at: DateTime<Utc>,
+	/// Kind of the Kubernetes object a `k8s_event` is about (`Pod`, `ReplicaSet`, `Deployment`)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub source_kind: Option<String>,
+	/// Name of that object
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub source_name: Option<String>,
+	/// Kubernetes event reason (e.g. `FailedScheduling`, `BackOff`, `FailedMount`)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub source_reason: Option<String>,
+}
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ArtifactEvent {
+	pub id: i64,
+	pub artifact_id: Uuid,
+	pub event_type: String,
+	pub created_at: DateTime<Utc>,
+}
+
+/// Entry of the merged per-application timeline (deployment + artifact events).
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AppEvent {
+	/// `deployment` or `artifact`
+	pub source: String,
+	pub deployment_id: Option<Uuid>,
+	pub digest: Option<String>,
+	pub event_type: String,
+	pub message: Option<String>,
+	pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct PublicKey {
+	pub id: Uuid,
+	pub app_id: Uuid,
+	pub public_key_hex: String,
+	pub active: bool,
+	pub created_at: DateTime<Utc>,
+	pub not_before: Option<DateTime<Utc>>,
+	pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Per-application deployment settings stored on the `applications` row.
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AppSettings {
+	pub replicas: i32,
+	/// Per-app cap for scaling (falls back to AETHER_MAX_REPLICAS when unset)
+	pub max_replicas: Option<i32>,
+	/// Runtime id from the registry (`GET /runtimes`); detected from the artifact SBOM when unset
+	pub runtime: Option<String>,
+	/// Port the app listens on (exported as `PORT`, targeted by the Service); runtime default when unset
+	pub port: Option<i32>,
+	/// Probe overrides; unset probes and fields use the runtime defaults
+	#[schema(value_type = ProbeSettings)]
+	pub probes: sqlx::types::Json<ProbeSettings>,
+	/// Pod `terminationGracePeriodSeconds` (runtime default when unset)
+	pub termination_grace_period_seconds: Option<i32>,
+	/// Seconds the `preStop` hook sleeps so endpoints drain before SIGTERM (runtime default when unset)
+	pub pre_stop_sleep_seconds: Option<i32>,
+	/// Shell command run as a one-off Job from the new artifact before each rollout; the Procfile `release:` entry when unset
+	pub release_command: Option<String>,
+	/// Roll back to the last running release when the status watcher fails a deployment
+	pub auto_rollback: bool,
+	/// Rolling update strategy, deadlines and watcher timeout; unset fields use the platform defaults
+	#[schema(value_type = RolloutPolicy)]
+	pub rollout_policy: sqlx::types::Json<RolloutPolicy>,
+}
+
+/// How to start an artifact, as recorded by the CLI from `package.json` and the `Procfile`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct ArtifactMetadata {
+	/// `scripts.start` of package.json
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub start_script: Option<String>,
+	/// `main` of package.json
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub main: Option<String>,
+	/// `engines.node` range of package.json (selects the Node image)
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub engines_node: Option<String>,
+	/// Command of the `web:` process in the Procfile (takes precedence over package.json)
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub procfile_web: Option<String>,
+	/// Every Procfile entry by process type (`worker`, `clock`, ...); each non-web type runs as its own Deployment
+	#[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")] pub processes: std::collections::BTreeMap<String, String>,
+}
+
+/// One Kubernetes Deployment a release was rendered into, with the rollout state reported by the status watcher.
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DeploymentProcess {
+	/// `web`, `worker`, `clock`, ...
+	pub process_type: String,
+	/// Name of the Kubernetes Deployment (`<app>` for web, `<app>-<type>` otherwise)
+	pub object_name: String,
+	pub desired_replicas: i32,
+	/// Replicas already running the release's pod template
+	pub updated_replicas: i32,
+	pub ready_replicas: i32,
+	pub available_replicas: i32,
+	pub ready: bool,
+	/// Pods of the Deployment as last seen by the status watcher
+	#[schema(value_type = Vec<PodProgress>)]
+	pub pods: sqlx::types::Json<Vec<PodProgress>>,
+	pub updated_at: DateTime<Utc>,
+}
+
+/// State of one pod of a process Deployment (main `app` container).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, ToSchema)]
+pub struct PodProgress {
+	pub name: String,
+	/// Pod phase: `Pending`, `Running`, `Succeeded`, `Failed` or `Unknown`
+	pub phase: String,
+	pub ready: bool,
+	pub restart_count: i32,
+	/// Reason of the container's last termination (e.g. `OOMKilled`, `Error`)
+	pub last_termination_reason: Option<String>,
+}
+
+/// How a deployment reaches traffic. `rolling` updates the app's Deployment in place; `blue_green` and `canary` start a
+/// digest-suffixed candidate Deployment next to it, which takes over on promote and is torn down on abort.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloutStrategy {
+	#[default]
+	Rolling,
+	/// Candidate reachable through the `<app>-preview` Service; promote switches the app Service selector to it
+	BlueGreen,
+	/// `percent` of Ingress traffic goes to the candidate (weighted canary Ingress) until promote
+	Canary { percent: u8 },
+}
+
+impl RolloutStrategy {
+	/// Value of the `deployments.strategy` column.
+	pub fn as_str(&self) -> &'static str {
+		match self { Self::Rolling => "rolling", Self::BlueGreen => "blue_green", Self::Canary { .. } => "canary" }
+	}
+
+	pub fn from_db(strategy: &str, canary_percent: Option<i32>) -> Self {
+		match strategy {
+			"blue_green" => Self::BlueGreen,
+			"canary" => Self::Canary { percent: canary_percent.unwrap_or(10).clamp(1, 99) as u8 },
+			_ => Self::Rolling,
+		}
+	}
+
+	pub fn canary_percent(&self) -> Option<i32> {
+		match self { Self::Canary { percent } => Some(*percent as i32), _ => None }
+	}
+}
+
+/// Rollout policy of an application, rendered into its Deployments and used by the status watcher.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct RolloutPolicy {
+	/// `progressDeadlineSeconds`: Kubernetes reports `ProgressDeadlineExceeded` after this long without progress (default 240)
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub progress_deadline_seconds: Option<i32>,
+	/// `minReadySeconds` a new pod must stay ready before it counts as available (default 0)
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub min_ready_seconds: Option<i32>,
+	/// `rollingUpdate.maxSurge`: pods or a percentage such as `"25%"` (default `25%`)
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub max_surge: Option<IntOrPercent>,
+	/// `rollingUpdate.maxUnavailable`: pods or a percentage (default 0, no capacity loss during rollouts)
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub max_unavailable: Option<IntOrPercent>,
+	/// Seconds after which the status watcher fails a deployment that is still not ready (default 300)
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub timeout_seconds: Option<i32>,
+}
+
+/// Kubernetes `IntOrString` for surge / unavailability: a pod count or a percentage string.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(untagged)]
+pub enum IntOrPercent {
+	Int(i32),
+	Percent(String),
+}
+
+/// Liveness, readiness and startup probe overrides of an application.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct ProbeSettings {
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub liveness: Option<ProbeSpec>,
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub readiness: Option<ProbeSpec>,
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub startup: Option<ProbeSpec>,
+}
+
+/// One probe; every unset field falls back to the runtime default for that probe.
+/// Without `http_path` the probe is a TCP socket check on the port.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, ToSchema)]
+pub struct ProbeSpec {
+	/// `false` removes the probe entirely
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub enabled: Option<bool>,
+	/// HTTP GET path (e.g. `/healthz`); TCP check when unset
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub http_path: Option<String>,
+	/// Port to probe (default: the app port)
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub port: Option<i32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub initial_delay_seconds: Option<i32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub period_seconds: Option<i32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub timeout_seconds: Option<i32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub success_threshold: Option<i32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")] pub failure_threshold: Option<i32>,
+}
@@ -0,0 +1,184 @@
+//! Runtime registry: maps a runtime id (`node20`, `python3.12`, `static`, ...) to the image, start command,
+//! default port and probe defaults used to render the `app` container.
+use serde::Serialize;
+use utoipa::ToSchema;
+use crate::models::{ArtifactMetadata, ProbeSettings, ProbeSpec};
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Runtime {
+    pub id: &'static str,
+    /// Ecosystem reported by the artifact SBOM (`node`, `python`, `static`)
+    pub ecosystem: &'static str,
+    pub image: &'static str,
+    pub command: &'static [&'static str],
+    /// Directory the artifact is extracted into inside the `app` container
+    pub workdir: &'static str,
+    pub default_port: i32,
+    /// HTTP path used by the default readiness/liveness probes; TCP checks when `None`
+    pub health_path: Option<&'static str>,
+    /// Startup probe failure threshold (x2s period) before liveness takes over
+    pub startup_failure_threshold: i32,
+}
+
+const PYTHON_START: &str = "if [ -f requirements.txt ]; then pip install --no-cache-dir -q -r requirements.txt; fi; exec python app.py";
+
+pub static RUNTIMES: &[Runtime] = &[
+    Runtime { id: "node18", ecosystem: "node", image: "aether-nodejs:18-slim", command: &["node", "server.js"], workdir: "/workspace", default_port: 3000, health_path: None, startup_failure_threshold: 60 },
+    Runtime { id: "node20", ecosystem: "node", image: "aether-nodejs:20-slim", command: &["node", "server.js"], workdir: "/workspace", default_port: 3000, health_path: None, startup_failure_threshold: 60 },
+    Runtime { id: "node22", ecosystem: "node", image: "aether-nodejs:22-slim", command: &["node", "server.js"], workdir: "/workspace", default_port: 3000, health_path: None, startup_failure_threshold: 60 },
+    Runtime { id: "python3.12", ecosystem: "python", image: "python:3.12-slim", command: &["sh", "-c", PYTHON_START], workdir: "/workspace", default_port: 8000, health_path: None, startup_failure_threshold: 90 },
+    Runtime { id: "static", ecosystem: "static", image: "nginx:1.27-alpine", command: &["nginx", "-g", "daemon off;"], workdir: "/usr/share/nginx/html", default_port: 80, health_path: Some("/"), startup_failure_threshold: 15 },
+];
+
+/// Node major versions with a registered image, newest last.
+const NODE_MAJORS: &[(u32, &str)] = &[(18, "node18"), (20, "node20"), (22, "node22")];
+
+pub fn get(id: &str) -> Option<&'static Runtime> { RUNTIMES.iter().find(|r| r.id == id) }
+
+/// Runtime for apps that neither pin one nor ship a recognisable SBOM (`AETHER_DEFAULT_RUNTIME`, default `node20`).
+pub fn default_runtime() -> &'static Runtime {
+    std::env::var("AETHER_DEFAULT_RUNTIME").ok().and_then(|id| get(id.trim())).unwrap_or(&RUNTIMES[1])
+}
+
+/// Pick a runtime from an `aether-sbom-v1` document: its `ecosystem` (or, for older SBOMs, marker files in
+/// `files`), narrowed by `engines.node` for Node apps. `None` when nothing identifies the project.
+pub fn detect_from_sbom(sbom: &serde_json::Value) -> Option<&'static Runtime> {
+    let ecosystem = sbom.get("ecosystem").and_then(|e| e.as_str()).map(str::to_string).or_else(|| {
+        let files: Vec<&str> = sbom.get("files")?.as_array()?.iter().filter_map(|f| f.get("path")?.as_str()).collect();
+        let has = |name: &str| files.iter().any(|p| p.trim_start_matches("./") == name);
+        if has("package.json") { Some("node".into()) }
+        else if has("requirements.txt") || has("pyproject.toml") { Some("python".into()) }
+        else if has("index.html") { Some("static".into()) }
+        else { None }
+    })?;
+    match ecosystem.as_str() {
+        "node" => {
+            let range = sbom.get("engines").and_then(|e| e.get("node")).and_then(|n| n.as_str());
+            let id = range.and_then(node_runtime_for_range).unwrap_or_else(|| default_node().id);
+            get(id)
+        }
+        other => RUNTIMES.iter().find(|r| r.ecosystem == other),
+    }
+}
+
+/// Node runtime for an `engines.node` range; `None` when no registered major satisfies it.
+pub fn node_for_engines(range: &str) -> Option<&'static Runtime> { node_runtime_for_range(range).and_then(get) }
+
+fn default_node() -> &'static Runtime {
+    Some(default_runtime()).filter(|r| r.ecosystem == "node").unwrap_or(&RUNTIMES[1])
+}
+
+/// Newest registered Node major satisfying a (simplified) `engines.node` range such as `18.x`, `>=20`, `^22.1`, `20 || 22`.
+fn node_runtime_for_range(range: &str) -> Option<&'static str> {
+    let satisfies = |major: u32, clause: &str| -> bool {
+        clause.split_whitespace().all(|c| {
+            let c = c.trim_start_matches('v');
+            let (op, ver) = match c.find(|ch: char| ch.is_ascii_digit()) { Some(i) => c.split_at(i), None => return c == "*" || c.eq_ignore_ascii_case("x") };
+            let Ok(want) = ver.split('.').next().unwrap_or("").parse::<u32>() else { return false; };
+            match op.trim_start_matches('v') {
+                ">=" => major >= want,
+                ">" => major > want,
+                "<=" => major <= want,
+                "<" => major < want,
+                "" | "=" | "^" | "~" => major == want,
+                _ => false,
+            }
+        })
+    };
+    NODE_MAJORS.iter().rev().find(|(major, _)| range.split("||").any(|clause| satisfies(*major, clause.trim()))).map(|(_, id)| *id)
+}
+
+/// Container command running a Procfile entry through the shell (so `$PORT` and friends expand).
+pub fn procfile_command(cmd: &str) -> Vec<String> {
+    vec!["sh".to_string(), "-c".to_string(), format!("exec {}", cmd.trim())]
+}
+
+impl Runtime {
+    /// Container command for an artifact: the Procfile `web:` process, else (Node runtimes) `scripts.start`
+    /// with the project's `node_modules/.bin` on PATH, else `node <main>`; `None` keeps the runtime default.
+    pub fn start_command(&self, meta: &ArtifactMetadata) -> Option<Vec<String>> {
+        let web = meta.procfile_web.as_deref().or_else(|| meta.processes.get("web").map(String::as_str));
+        if let Some(web) = web.filter(|c| !c.trim().is_empty()) {
+            return Some(procfile_command(web));
+        }
+        if self.ecosystem != "node" { return None; }
+        if let Some(start) = meta.start_script.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            return Some(vec!["sh".to_string(), "-c".to_string(), format!("export PATH=\"{}/node_modules/.bin:$PATH\"; exec {start}", self.workdir)]);
+        }
+        meta.main.as_deref().map(str::trim).filter(|m| !m.is_empty()).map(|main| vec!["node".to_string(), main.to_string()])
+    }
+
+    /// Probe defaults of this runtime: the startup probe covers the boot, then readiness/liveness check the port
+    /// (or `health_path` over HTTP).
+    pub fn default_probes(&self) -> ProbeSettings {
+        let base = |period, timeout, failure| ProbeSpec {
+            http_path: self.health_path.map(str::to_string),
+            period_seconds: Some(period),
+            timeout_seconds: Some(timeout),
+            failure_threshold: Some(failure),
+            ..ProbeSpec::default()
+        };
+        ProbeSettings {
+            startup: Some(base(2, 1, self.startup_failure_threshold)),
+            readiness: Some(base(5, 2, 3)),
+            liveness: Some(base(10, 2, 3)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn registry_ids_unique() {
+        let mut ids: Vec<&str> = RUNTIMES.iter().map(|r| r.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), RUNTIMES.len());
+        assert_eq!(get("python3.12").unwrap().default_port, 8000);
+        assert!(get("ruby").is_none());
+    }
+
+    #[test]
+    fn detects_runtime_from_sbom() {
+        assert_eq!(detect_from_sbom(&json!({"ecosystem": "node", "engines": {"node": ">=18 <21"}})).unwrap().id, "node20");
+        assert_eq!(detect_from_sbom(&json!({"ecosystem": "node", "engines": {"node": "18.x"}})).unwrap().id, "node18");
+        assert_eq!(detect_from_sbom(&json!({"ecosystem": "node", "engines": {"node": "^22.3.0"}})).unwrap().id, "node22");
+        assert_eq!(detect_from_sbom(&json!({"ecosystem": "node", "engines": {"node": "16 || 18"}})).unwrap().id, "node18");
+        assert_eq!(detect_from_sbom(&json!({"ecosystem": "node", "engines": {"node": "14"}})).unwrap().id, "node20", "unsupported range falls back");
+        assert_eq!(detect_from_sbom(&json!({"ecosystem": "python"})).unwrap().id, "python3.12");
+        // SBOMs without `ecosystem`: marker files
+        assert_eq!(detect_from_sbom(&json!({"files": [{"path": "./index.html"}, {"path": "./style.css"}]})).unwrap().id, "static");
+        assert_eq!(detect_from_sbom(&json!({"files": [{"path": "package.json"}]})).unwrap().id, "node20");
+        assert!(detect_from_sbom(&json!({"files": [{"path": "main.go"}]})).is_none());
+    }
+
+    #[test]
+    fn start_command_precedence() {
+        let node = get("node20").unwrap();
+        let mut meta = ArtifactMetadata { main: Some("dist/index.js".into()), ..Default::default() };
+        assert_eq!(node.start_command(&meta).unwrap(), ["node", "dist/index.js"]);
+        meta.start_script = Some("next start -p $PORT".into());
+        let cmd = node.start_command(&meta).unwrap();
+        assert_eq!(cmd[..2], ["sh", "-c"]);
+        assert!(cmd[2].contains("/workspace/node_modules/.bin") && cmd[2].ends_with("exec next start -p $PORT"));
+        meta.procfile_web = Some("node cluster.js".into());
+        assert_eq!(node.start_command(&meta).unwrap()[2], "exec node cluster.js");
+        // package.json fields mean nothing to other runtimes; the Procfile does
+        let py = get("python3.12").unwrap();
+        assert!(py.start_command(&ArtifactMetadata { main: Some("x.js".into()), ..Default::default() }).is_none());
+        assert_eq!(py.start_command(&meta).unwrap()[2], "exec node cluster.js");
+        assert!(node.start_command(&ArtifactMetadata::default()).is_none());
+        let full = ArtifactMetadata { processes: [("web".to_string(), "gunicorn app:app".to_string())].into(), ..Default::default() };
+        assert_eq!(py.start_command(&full).unwrap()[2], "exec gunicorn app:app");
+    }
+
+    #[test]
+    fn static_runtime_probes_over_http() {
+        let p = get("static").unwrap().default_probes();
+        assert_eq!(p.readiness.unwrap().http_path.as_deref(), Some("/"));
+        assert!(get("node20").unwrap().default_probes().liveness.unwrap().http_path.is_none());
+    }
+}
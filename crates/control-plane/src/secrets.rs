@@ -0,0 +1,147 @@
+//! Envelope encryption for app secrets.
+//!
+//! Each application owns a random AES-256-GCM data key. The data key is stored wrapped (encrypted)
+//! by a master key read from a local keyring file (`AETHER_MASTER_KEY_FILE`); secret values are
+//! encrypted with the data key. The keyring holds one 64-hex key per line, the first line being the
+//! active master key and later lines retired keys still accepted for unwrapping until rotation
+//! re-wraps every data key with the active one.
+use aes_gcm::{Aes256Gcm, Key, Nonce, aead::{Aead, AeadCore, KeyInit, OsRng, Payload}};
+use anyhow::{anyhow, Context, Result};
+use sha2::{Sha256, Digest};
+
+const NONCE_LEN: usize = 12;
+
+/// A 256-bit master key with a short stable id (first 8 bytes of its SHA-256, hex).
+#[derive(Clone)]
+pub struct MasterKey { pub id: String, key: [u8; 32] }
+
+impl std::fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.debug_struct("MasterKey").field("id", &self.id).finish_non_exhaustive() }
+}
+
+impl MasterKey {
+    pub fn from_bytes(key: [u8; 32]) -> Self {
+        let id = hex::encode(&Sha256::digest(key)[..8]);
+        Self { id, key }
+    }
+    fn cipher(&self) -> Aes256Gcm { Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key)) }
+}
+
+/// Ordered master keys: `active()` wraps, any key unwraps a data key tagged with its id.
+#[derive(Debug, Clone)]
+pub struct Keyring { keys: Vec<MasterKey> }
+
+impl Keyring {
+    pub fn new(keys: Vec<MasterKey>) -> Result<Self> {
+        if keys.is_empty() { return Err(anyhow!("master keyring is empty")); }
+        Ok(Self { keys })
+    }
+
+    /// Parse keyring file contents (one 64-hex key per line, `#` comments and blank lines ignored).
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut keys = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') { continue; }
+            let bytes = hex::decode(line).with_context(|| format!("keyring line {}: invalid hex", i + 1))?;
+            let key: [u8; 32] = bytes.try_into().map_err(|_| anyhow!("keyring line {}: master key must be 32 bytes", i + 1))?;
+            keys.push(MasterKey::from_bytes(key));
+        }
+        Self::new(keys)
+    }
+
+    /// Load from `AETHER_MASTER_KEY_FILE`; `Ok(None)` when the secrets store is not configured.
+    /// Read on every call so an operator can rotate the file without restarting the control plane.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(path) = std::env::var("AETHER_MASTER_KEY_FILE") else { return Ok(None); };
+        if path.trim().is_empty() { return Ok(None); }
+        let contents = std::fs::read_to_string(&path).with_context(|| format!("read master key file {path}"))?;
+        Self::parse(&contents).map(Some)
+    }
+
+    pub fn active(&self) -> &MasterKey { &self.keys[0] }
+
+    fn find(&self, id: &str) -> Option<&MasterKey> { self.keys.iter().find(|k| k.id == id) }
+
+    /// Wrap a data key with the active master key; returns (nonce || ciphertext, master key id).
+    pub fn wrap(&self, data_key: &[u8; 32]) -> Result<(Vec<u8>, String)> {
+        let mk = self.active();
+        Ok((seal_with(&mk.cipher(), data_key, mk.id.as_bytes())?, mk.id.clone()))
+    }
+
+    /// Unwrap a data key previously wrapped by the master key `master_key_id`.
+    pub fn unwrap(&self, wrapped: &[u8], master_key_id: &str) -> Result<[u8; 32]> {
+        let mk = self.find(master_key_id).ok_or_else(|| anyhow!("master key {master_key_id} not in keyring"))?;
+        let plain = open_with(&mk.cipher(), wrapped, mk.id.as_bytes())?;
+        plain.try_into().map_err(|_| anyhow!("unwrapped data key has wrong length"))
+    }
+}
+
+/// Fresh random data key.
+pub fn generate_data_key() -> [u8; 32] { Aes256Gcm::generate_key(OsRng).into() }
+
+/// Encrypt a secret value with a data key; `aad` binds the ciphertext to its owner (app id + name).
+pub fn seal(data_key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    seal_with(&Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(data_key)), plaintext, aad)
+}
+
+pub fn open(data_key: &[u8; 32], sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    open_with(&Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(data_key)), sealed, aad)
+}
+
+fn seal_with(cipher: &Aes256Gcm, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ct = cipher.encrypt(&nonce, Payload { msg: plaintext, aad }).map_err(|_| anyhow!("encryption failed"))?;
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&ct);
+    Ok(out)
+}
+
+fn open_with(cipher: &Aes256Gcm, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() <= NONCE_LEN { return Err(anyhow!("ciphertext too short")); }
+    let (nonce, ct) = sealed.split_at(NONCE_LEN);
+    cipher.decrypt(Nonce::from_slice(nonce), Payload { msg: ct, aad }).map_err(|_| anyhow!("decryption failed (wrong key or tampered data)"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyring(lines: &[[u8; 32]]) -> Keyring {
+        Keyring::parse(&lines.iter().map(hex::encode).collect::<Vec<_>>().join("\n")).unwrap()
+    }
+
+    #[test]
+    fn envelope_roundtrip_and_rotation() {
+        let old = keyring(&[[1u8; 32]]);
+        let dk = generate_data_key();
+        let (wrapped, id) = old.wrap(&dk).unwrap();
+        let sealed = seal(&dk, b"s3cr3t", b"app/DB_PASSWORD").unwrap();
+        // rotated keyring: new active key, old key retained for unwrapping
+        let rotated = keyring(&[[2u8; 32], [1u8; 32]]);
+        let dk2 = rotated.unwrap(&wrapped, &id).unwrap();
+        let (rewrapped, new_id) = rotated.wrap(&dk2).unwrap();
+        assert_ne!(new_id, id);
+        let only_new = keyring(&[[2u8; 32]]);
+        let dk3 = only_new.unwrap(&rewrapped, &new_id).unwrap();
+        assert_eq!(open(&dk3, &sealed, b"app/DB_PASSWORD").unwrap(), b"s3cr3t");
+        assert!(only_new.unwrap(&wrapped, &id).is_err(), "retired key must be gone");
+    }
+
+    #[test]
+    fn tampering_and_wrong_aad_rejected() {
+        let dk = generate_data_key();
+        let mut sealed = seal(&dk, b"value", b"a").unwrap();
+        assert!(open(&dk, &sealed, b"b").is_err());
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0xff;
+        assert!(open(&dk, &sealed, b"a").is_err());
+    }
+
+    #[test]
+    fn keyring_parse_rejects_bad_lines() {
+        assert!(Keyring::parse("# only comments\n\n").is_err());
+        assert!(Keyring::parse("abcd").is_err());
+        assert!(Keyring::parse(&format!("# active\n{}\n", hex::encode([7u8; 32]))).is_ok());
+    }
+}
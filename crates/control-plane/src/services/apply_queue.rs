@@ -0,0 +1,128 @@
+//! Durable outbox for Kubernetes applies. Creating a deployment queues a row in `deployment_apply_queue`; background
+//! workers build the spec, run the release phase and apply the objects, retrying failures with exponential backoff.
+//! Once the attempts are exhausted the deployment is failed with `apply_failed:<error>`, so a restart or a flaky API
+//! server no longer leaves deployments pending forever.
+use once_cell::sync::Lazy;
+use sqlx::{Pool, Postgres, Row};
+
+/// Wakes idle workers when an apply is queued.
+static WAKE: Lazy<tokio::sync::Notify> = Lazy::new(tokio::sync::Notify::new);
+
+/// Queue the apply of a freshly created deployment (no-op when it is already queued).
+pub async fn enqueue(pool: &Pool<Postgres>, deployment_id: uuid::Uuid, dev_hot: bool) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO deployment_apply_queue (deployment_id, dev_hot) VALUES ($1,$2) ON CONFLICT (deployment_id) DO NOTHING")
+        .bind(deployment_id).bind(dev_hot).execute(pool).await?;
+    WAKE.notify_one();
+    Ok(())
+}
+
+/// Delay before retrying after `attempts` failed attempts: `AETHER_APPLY_BACKOFF_BASE_SECS` (default 5) doubled per
+/// attempt, capped at `AETHER_APPLY_BACKOFF_MAX_SECS` (default 300).
+pub fn backoff_secs(attempts: i32) -> i64 {
+    let base = std::env::var("AETHER_APPLY_BACKOFF_BASE_SECS").ok().and_then(|v| v.parse::<i64>().ok()).unwrap_or(5).max(1);
+    let cap = std::env::var("AETHER_APPLY_BACKOFF_MAX_SECS").ok().and_then(|v| v.parse::<i64>().ok()).unwrap_or(300).max(base);
+    base.saturating_mul(1i64 << (attempts - 1).clamp(0, 20)).min(cap)
+}
+
+fn max_attempts() -> i32 {
+    std::env::var("AETHER_APPLY_MAX_ATTEMPTS").ok().and_then(|v| v.parse::<i32>().ok()).unwrap_or(5).max(1)
+}
+
+/// What an attempt did when it did not fail.
+enum Applied {
+    /// Objects applied to the cluster.
+    Done,
+    /// Nothing to apply: the deployment left `pending` or its release phase failed (and failed the deployment).
+    Skipped,
+}
+
+/// Claim one due entry and apply it. The row is leased (pushed into the future) so other workers skip it while the
+/// attempt runs; the outcome then completes it, schedules a retry or fails it together with its deployment.
+/// Returns the number of entries processed (0 when nothing is due).
+pub async fn process_due(pool: &Pool<Postgres>) -> anyhow::Result<usize> {
+    let row = sqlx::query("UPDATE deployment_apply_queue SET next_attempt_at = now() + interval '15 minutes'
+        WHERE id IN (
+            SELECT id FROM deployment_apply_queue WHERE status='pending' AND next_attempt_at <= now()
+            ORDER BY next_attempt_at LIMIT 1 FOR UPDATE SKIP LOCKED)
+        RETURNING id, deployment_id, dev_hot, release_done, attempts")
+        .fetch_optional(pool).await?;
+    let Some(row) = row else { refresh_depth(pool).await; return Ok(0); };
+    let id: uuid::Uuid = row.get("id");
+    let deployment_id: uuid::Uuid = row.get("deployment_id");
+    let attempts = row.get::<i32, _>("attempts") + 1;
+    let res = apply(pool, id, deployment_id, row.get("dev_hot"), row.get("release_done")).await;
+    let outcome = match &res {
+        Ok(Applied::Done) => "applied",
+        Ok(Applied::Skipped) => "skipped",
+        Err(_) if attempts >= max_attempts() => "failed",
+        Err(_) => "retry",
+    };
+    let error = res.as_ref().err().map(|e| format!("{e:#}"));
+    let status = match outcome { "retry" => "pending", "failed" => "failed", _ => "done" };
+    sqlx::query("UPDATE deployment_apply_queue SET status=$2, attempts=$3, last_error=$4,
+            next_attempt_at = now() + ($5::bigint * interval '1 second'),
+            completed_at = CASE WHEN $2='pending' THEN NULL ELSE now() END
+        WHERE id=$1")
+        .bind(id).bind(status).bind(attempts).bind(&error).bind(backoff_secs(attempts))
+        .execute(pool).await?;
+    crate::telemetry::APPLY_ATTEMPTS.with_label_values(&[outcome]).inc();
+    if let Some(e) = &error {
+        tracing::warn!(%deployment_id, attempts, error=%e, outcome, "k8s apply failed");
+        if outcome == "failed" {
+            crate::services::deployments::mark_failed(pool, deployment_id, &format!("apply_failed:{e}")).await;
+        } else {
+            crate::services::deployments::record_event(pool, deployment_id, "apply_retry", Some(&format!("attempt={attempts} error={e}"))).await;
+        }
+    }
+    refresh_depth(pool).await;
+    Ok(1)
+}
+
+/// One apply attempt into the app's namespace using the resolved digest (if any). The release phase runs at most once
+/// per deployment; retries after it succeeded go straight to the apply. Blue/green and canary deployments only start
+/// their candidate Deployment (see [`crate::services::rollouts`]).
+async fn apply(pool: &Pool<Postgres>, id: uuid::Uuid, deployment_id: uuid::Uuid, dev_hot: bool, release_done: bool) -> anyhow::Result<Applied> {
+    let dep = crate::services::deployments::get_deployment(pool, deployment_id).await?;
+    if dep.status != "pending" { return Ok(Applied::Skipped); }
+    let app_name: String = sqlx::query_scalar("SELECT name FROM applications WHERE id=$1").bind(dep.app_id).fetch_one(pool).await?;
+    let spec = crate::services::deployments::build_spec(pool, &app_name, &dep, dev_hot).await?;
+    let candidate = spec.strategy != crate::models::RolloutStrategy::Rolling;
+    let recorded = if candidate {
+        crate::services::processes::record_candidate(pool, dep.id, &spec).await
+    } else {
+        crate::services::processes::record_processes(pool, dep.id, &spec).await
+    };
+    if let Err(e) = recorded {
+        tracing::warn!(error=%e, deployment_id=%dep.id, "recording deployment processes failed");
+    }
+    if !release_done {
+        if !crate::services::deployments::run_release_phase(pool, dep.id, &spec).await { return Ok(Applied::Skipped); }
+        sqlx::query("UPDATE deployment_apply_queue SET release_done=TRUE WHERE id=$1").bind(id).execute(pool).await?;
+    }
+    if candidate { crate::k8s::apply_candidate(&spec).await? } else { crate::k8s::apply_deployment(&spec).await? }
+    tracing::info!(app=%app_name, deployment_id=%dep.id, "k8s apply scheduled");
+    Ok(Applied::Done)
+}
+
+/// Update the queue depth gauge (entries still pending, including scheduled retries).
+pub async fn refresh_depth(pool: &Pool<Postgres>) {
+    if let Ok(n) = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM deployment_apply_queue WHERE status='pending'").fetch_one(pool).await {
+        crate::telemetry::APPLY_QUEUE_DEPTH.set(n);
+    }
+}
+
+/// Worker loop: drain due entries, then wait for a new one or the next poll (`AETHER_APPLY_INTERVAL_SECS`, default 2).
+pub async fn run_worker(pool: Pool<Postgres>) {
+    let interval = std::env::var("AETHER_APPLY_INTERVAL_SECS").ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(2);
+    loop {
+        match process_due(&pool).await {
+            Ok(n) if n > 0 => continue,
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error=%e, "apply_queue_batch_failed"),
+        }
+        tokio::select! {
+            _ = WAKE.notified() => {}
+            _ = tokio::time::sleep(std::time::Duration::from_secs(interval.max(1))) => {}
+        }
+    }
+}
@@ -1,13 +1,135 @@
-use sqlx::{Pool, Postgres};
-use crate::models::Application;
+use sqlx::{PgExecutor, Pool, Postgres};
+use crate::models::{Application, AppSettings, IntOrPercent, ProbeSettings, ProbeSpec, RolloutPolicy};
 
-pub async fn create_app(pool: &Pool<Postgres>, name: &str) -> Result<Application, sqlx::Error> {
-    sqlx::query_as::<_, Application>("INSERT INTO applications (name) VALUES ($1) RETURNING id, name, created_at, updated_at")
+/// Namespace used when `POST /apps` omits one (`AETHER_NAMESPACE`, default `default`).
+pub fn default_namespace() -> String {
+    std::env::var("AETHER_NAMESPACE").ok().filter(|v| !v.trim().is_empty()).unwrap_or_else(|| "default".to_string())
+}
+
+/// Kubernetes namespace names are DNS-1123 labels.
+pub fn valid_namespace(ns: &str) -> bool {
+    !ns.is_empty() && ns.len() <= 63
+        && ns.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !ns.starts_with('-') && !ns.ends_with('-')
+}
+
+pub async fn create_app(pool: &Pool<Postgres>, name: &str, namespace: &str) -> Result<Application, sqlx::Error> {
+    sqlx::query_as::<_, Application>("INSERT INTO applications (name, namespace) VALUES ($1,$2) RETURNING id, name, created_at, updated_at, namespace")
         .bind(name)
+        .bind(namespace)
         .fetch_one(pool).await
 }
 
 pub async fn list_apps(pool: &Pool<Postgres>) -> Result<Vec<Application>, sqlx::Error> {
-    sqlx::query_as::<_, Application>("SELECT id, name, created_at, updated_at FROM applications ORDER BY created_at DESC")
+    sqlx::query_as::<_, Application>("SELECT id, name, created_at, updated_at, namespace FROM applications ORDER BY created_at DESC")
         .fetch_all(pool).await
 }
+
+/// Namespace of an application, `None` if the application does not exist.
+pub async fn namespace_for(pool: &Pool<Postgres>, app_name: &str) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT namespace FROM applications WHERE name=$1").bind(app_name).fetch_optional(pool).await
+}
+
+/// Platform-wide replica cap used when an app has no `max_replicas` (`AETHER_MAX_REPLICAS`, default 20).
+pub fn default_max_replicas() -> i32 {
+    std::env::var("AETHER_MAX_REPLICAS").ok().and_then(|v| v.parse::<i32>().ok()).filter(|v| *v >= 0).unwrap_or(20)
+}
+
+const SETTINGS_COLS: &str = "replicas, max_replicas, runtime, port, probes, termination_grace_period_seconds, pre_stop_sleep_seconds, release_command, auto_rollback, rollout_policy";
+
+pub async fn get_settings<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("SELECT {SETTINGS_COLS} FROM applications WHERE id=$1"))
+        .bind(app_id).fetch_one(ex).await
+}
+
+pub async fn set_replicas<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, replicas: i32) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("UPDATE applications SET replicas=$2, updated_at=now() WHERE id=$1 RETURNING {SETTINGS_COLS}"))
+        .bind(app_id).bind(replicas).fetch_one(ex).await
+}
+
+pub async fn set_max_replicas<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, max_replicas: Option<i32>) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("UPDATE applications SET max_replicas=$2, updated_at=now() WHERE id=$1 RETURNING {SETTINGS_COLS}"))
+        .bind(app_id).bind(max_replicas).fetch_one(ex).await
+}
+
+pub async fn set_port<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, port: Option<i32>) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("UPDATE applications SET port=$2, updated_at=now() WHERE id=$1 RETURNING {SETTINGS_COLS}"))
+        .bind(app_id).bind(port).fetch_one(ex).await
+}
+
+pub async fn set_probes<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, probes: &ProbeSettings) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("UPDATE applications SET probes=$2, updated_at=now() WHERE id=$1 RETURNING {SETTINGS_COLS}"))
+        .bind(app_id).bind(sqlx::types::Json(probes)).fetch_one(ex).await
+}
+
+pub async fn set_rollout_policy<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, policy: &RolloutPolicy) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("UPDATE applications SET rollout_policy=$2, updated_at=now() WHERE id=$1 RETURNING {SETTINGS_COLS}"))
+        .bind(app_id).bind(sqlx::types::Json(policy)).fetch_one(ex).await
+}
+
+pub async fn set_termination<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, grace_period_seconds: Option<i32>, pre_stop_sleep_seconds: Option<i32>) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("UPDATE applications SET termination_grace_period_seconds=$2, pre_stop_sleep_seconds=$3, updated_at=now() WHERE id=$1 RETURNING {SETTINGS_COLS}"))
+        .bind(app_id).bind(grace_period_seconds).bind(pre_stop_sleep_seconds).fetch_one(ex).await
+}
+
+/// Check a rollout policy against the Kubernetes constraints (progress deadline above minReady, surge and
+/// unavailability not both zero); `Err` carries a user-facing message.
+pub fn validate_rollout_policy(policy: &RolloutPolicy) -> Result<(), String> {
+    fn amount(field: &str, v: &Option<IntOrPercent>) -> Result<Option<bool>, String> {
+        match v {
+            None => Ok(None),
+            Some(IntOrPercent::Int(n)) if *n >= 0 => Ok(Some(*n > 0)),
+            Some(IntOrPercent::Percent(p)) => match p.strip_suffix('%').and_then(|n| n.parse::<u8>().ok()).filter(|n| *n <= 100) {
+                Some(n) => Ok(Some(n > 0)),
+                None => Err(format!("{field} must be a pod count or a percentage like \"25%\"")),
+            },
+            Some(_) => Err(format!("{field} must be >= 0")),
+        }
+    }
+    if policy.progress_deadline_seconds.is_some_and(|v| v < 1) { return Err("progress_deadline_seconds must be >= 1".into()); }
+    if policy.min_ready_seconds.is_some_and(|v| v < 0) { return Err("min_ready_seconds must be >= 0".into()); }
+    if policy.timeout_seconds.is_some_and(|v| v < 1) { return Err("timeout_seconds must be >= 1".into()); }
+    let deadline = policy.progress_deadline_seconds.unwrap_or(crate::k8s::DEFAULT_PROGRESS_DEADLINE_SECS);
+    if policy.min_ready_seconds.unwrap_or(0) >= deadline {
+        return Err(format!("min_ready_seconds must be below progress_deadline_seconds ({deadline})"));
+    }
+    let surge = amount("max_surge", &policy.max_surge)?.unwrap_or(true);
+    let unavailable = amount("max_unavailable", &policy.max_unavailable)?.unwrap_or(false);
+    if !surge && !unavailable { return Err("max_surge and max_unavailable cannot both be zero".into()); }
+    Ok(())
+}
+
+/// Check probe overrides against the Kubernetes constraints; `Err` carries a user-facing message.
+pub fn validate_probes(probes: &ProbeSettings) -> Result<(), String> {
+    fn check(kind: &str, p: &ProbeSpec) -> Result<(), String> {
+        if let Some(path) = &p.http_path {
+            if !path.starts_with('/') || path.chars().any(char::is_whitespace) { return Err(format!("{kind}.http_path must be an absolute path")); }
+        }
+        if p.port.is_some_and(|v| !(1..=65535).contains(&v)) { return Err(format!("{kind}.port must be between 1 and 65535")); }
+        if p.initial_delay_seconds.is_some_and(|v| v < 0) { return Err(format!("{kind}.initial_delay_seconds must be >= 0")); }
+        for (field, v) in [("period_seconds", p.period_seconds), ("timeout_seconds", p.timeout_seconds), ("success_threshold", p.success_threshold), ("failure_threshold", p.failure_threshold)] {
+            if v.is_some_and(|v| v < 1) { return Err(format!("{kind}.{field} must be >= 1")); }
+        }
+        if kind != "readiness" && p.success_threshold.is_some_and(|v| v != 1) { return Err(format!("{kind}.success_threshold must be 1")); }
+        Ok(())
+    }
+    for (kind, p) in [("liveness", &probes.liveness), ("readiness", &probes.readiness), ("startup", &probes.startup)] {
+        if let Some(p) = p { check(kind, p)?; }
+    }
+    Ok(())
+}
+
+pub async fn set_release_command<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, command: Option<&str>) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("UPDATE applications SET release_command=$2, updated_at=now() WHERE id=$1 RETURNING {SETTINGS_COLS}"))
+        .bind(app_id).bind(command).fetch_one(ex).await
+}
+
+pub async fn set_auto_rollback<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, enabled: bool) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("UPDATE applications SET auto_rollback=$2, updated_at=now() WHERE id=$1 RETURNING {SETTINGS_COLS}"))
+        .bind(app_id).bind(enabled).fetch_one(ex).await
+}
+
+pub async fn set_runtime<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, runtime: Option<&str>) -> Result<AppSettings, sqlx::Error> {
+    sqlx::query_as::<_, AppSettings>(&format!("UPDATE applications SET runtime=$2, updated_at=now() WHERE id=$1 RETURNING {SETTINGS_COLS}"))
+        .bind(app_id).bind(runtime).fetch_one(ex).await
+}
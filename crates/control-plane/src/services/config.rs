@@ -0,0 +1,39 @@
+use sqlx::{Pool, Postgres};
+use std::collections::BTreeMap;
+
+/// Env var names: `[A-Za-z_][A-Za-z0-9_]*`; the `AETHER_` prefix is reserved for platform-injected values.
+pub fn valid_key(k: &str) -> bool {
+    let mut chars = k.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && k.len() <= 256
+        && !k.to_ascii_uppercase().starts_with("AETHER_")
+}
+
+/// Config of an application keyed by name (sorted).
+pub async fn get_config(pool: &Pool<Postgres>, app_id: uuid::Uuid) -> Result<BTreeMap<String, String>, sqlx::Error> {
+    let rows: Vec<(String, String)> = sqlx::query_as("SELECT key, value FROM app_config WHERE app_id=$1 ORDER BY key")
+        .bind(app_id).fetch_all(pool).await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Upsert the given keys; returns whether anything actually changed.
+pub async fn set_values(pool: &Pool<Postgres>, app_id: uuid::Uuid, values: &BTreeMap<String, String>) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut changed = false;
+    for (k, v) in values {
+        let res = sqlx::query("INSERT INTO app_config (app_id, key, value) VALUES ($1,$2,$3)
+            ON CONFLICT (app_id, key) DO UPDATE SET value=EXCLUDED.value, updated_at=now() WHERE app_config.value <> EXCLUDED.value")
+            .bind(app_id).bind(k).bind(v).execute(&mut *tx).await?;
+        changed |= res.rows_affected() > 0;
+    }
+    tx.commit().await?;
+    Ok(changed)
+}
+
+/// Remove the given keys; returns whether anything was removed.
+pub async fn unset_keys(pool: &Pool<Postgres>, app_id: uuid::Uuid, keys: &[String]) -> Result<bool, sqlx::Error> {
+    let res = sqlx::query("DELETE FROM app_config WHERE app_id=$1 AND key = ANY($2)")
+        .bind(app_id).bind(keys).execute(pool).await?;
+    Ok(res.rows_affected() > 0)
+}
@@ -4,14 +4,36 @@ pub async fn update_deployment_digest(pool: &Pool<Postgres>, id: uuid::Uuid, dig
         .bind(id)
         .bind(digest)
         .fetch_one(pool).await?;
-    let _ = sqlx::query("INSERT INTO deployment_events (deployment_id, event_type, message) VALUES ($1,'rollout',$2)")
-        .bind(id)
-        .bind(digest)
-        .execute(pool).await;
+    record_event(pool, id, "rollout", Some(digest)).await;
     Ok(dep)
 }
 use sqlx::{Pool, Postgres, Row};
 use crate::models::Deployment;
+use crate::event_bus::BusEvent;
+
+/// Live change of a deployment, published on the [`crate::event_bus`] for the SSE streams
+/// (`GET /deployments/{id}/watch`, `GET /apps/{app_name}/events/stream`): status transitions and every new
+/// `deployment_events` row.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeploymentUpdate {
+    Status { deployment_id: uuid::Uuid, app_id: uuid::Uuid, status: String, failure_reason: Option<String>, at: chrono::DateTime<chrono::Utc> },
+    Event { id: i64, deployment_id: uuid::Uuid, app_id: uuid::Uuid, event_type: String, message: Option<String>, created_at: chrono::DateTime<chrono::Utc> },
+}
+
+impl DeploymentUpdate {
+    pub fn deployment_id(&self) -> uuid::Uuid {
+        match self { Self::Status { deployment_id, .. } | Self::Event { deployment_id, .. } => *deployment_id }
+    }
+    pub fn app_id(&self) -> uuid::Uuid {
+        match self { Self::Status { app_id, .. } | Self::Event { app_id, .. } => *app_id }
+    }
+}
+
+async fn publish_status(pool: &Pool<Postgres>, id: uuid::Uuid, app_id: uuid::Uuid, status: &str, failure_reason: Option<&str>) {
+    let update = DeploymentUpdate::Status { deployment_id: id, app_id, status: status.to_string(), failure_reason: failure_reason.map(str::to_string), at: chrono::Utc::now() };
+    crate::event_bus::publish(pool, BusEvent::Deployment(update)).await;
+}
 
 /// List deployments for an application.
 /// Returns `sqlx::Error::RowNotFound` if the application does not exist.
@@ -37,13 +59,229 @@ pub async fn create_deployment(pool: &Pool<Postgres>, app_name: &str, artifact_u
     let rec = sqlx::query("SELECT id FROM applications WHERE name = $1")
         .bind(app_name).fetch_optional(pool).await?;
     let app_id: uuid::Uuid = rec.ok_or(sqlx::Error::RowNotFound)?.get("id");
-    sqlx::query_as::<_, Deployment>("INSERT INTO deployments (app_id, artifact_url, status, digest, signature) VALUES ($1,$2,$3,$4,$5) RETURNING id, app_id, artifact_url, status, created_at, digest, failure_reason, last_transition_at, signature")
+    let dep = sqlx::query_as::<_, Deployment>("INSERT INTO deployments (app_id, artifact_url, status, digest, signature) VALUES ($1,$2,$3,$4,$5) RETURNING id, app_id, artifact_url, status, created_at, digest, failure_reason, last_transition_at, signature")
         .bind(app_id)
         .bind(artifact_url)
         .bind("pending")
         .bind(digest)
         .bind(signature)
-        .fetch_one(pool).await
+        .fetch_one(pool).await?;
+    publish_status(pool, dep.id, app_id, "pending", None).await;
+    Ok(dep)
+}
+
+/// Record a deployment audit event (best-effort, errors ignored like the status transitions) and publish it on the event bus.
+pub async fn record_event(pool: &Pool<Postgres>, id: uuid::Uuid, event_type: &str, message: Option<&str>) {
+    let row = sqlx::query("INSERT INTO deployment_events (deployment_id, event_type, message) VALUES ($1,$2,$3)
+        RETURNING id, created_at, (SELECT app_id FROM deployments WHERE id=$1) AS app_id")
+        .bind(id)
+        .bind(event_type)
+        .bind(message)
+        .fetch_one(pool).await;
+    if let Ok(row) = row { publish_event(pool, id, &row, event_type, message).await; }
+}
+
+/// Kubernetes object and event a `k8s_event` row comes from.
+#[derive(Debug, Clone)]
+pub struct K8sEventSource {
+    /// Event UID (one row per Kubernetes event, however often it repeats)
+    pub uid: String,
+    pub kind: String,
+    pub name: String,
+    pub reason: String,
+}
+
+/// Record a Kubernetes event as a `k8s_event` and publish it, once per event UID. Returns whether it was new.
+pub async fn record_k8s_event(pool: &Pool<Postgres>, id: uuid::Uuid, source: &K8sEventSource, message: &str) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query("INSERT INTO deployment_events (deployment_id, event_type, message, source_kind, source_name, source_reason, source_uid)
+        VALUES ($1,'k8s_event',$2,$3,$4,$5,$6) ON CONFLICT (source_uid) WHERE source_uid IS NOT NULL DO NOTHING
+        RETURNING id, created_at, (SELECT app_id FROM deployments WHERE id=$1) AS app_id")
+        .bind(id).bind(message).bind(&source.kind).bind(&source.name).bind(&source.reason).bind(&source.uid)
+        .fetch_optional(pool).await?;
+    let Some(row) = row else { return Ok(false); };
+    publish_event(pool, id, &row, "k8s_event", Some(message)).await;
+    Ok(true)
+}
+
+async fn publish_event(pool: &Pool<Postgres>, id: uuid::Uuid, row: &sqlx::postgres::PgRow, event_type: &str, message: Option<&str>) {
+    let update = DeploymentUpdate::Event {
+        id: row.get("id"),
+        deployment_id: id,
+        app_id: row.get("app_id"),
+        event_type: event_type.to_string(),
+        message: message.map(str::to_string),
+        created_at: row.get("created_at"),
+    };
+    crate::event_bus::publish(pool, BusEvent::Deployment(update)).await;
+}
+
+/// Assemble the k8s apply input for a deployment row: app namespace, runtime and start command, process types, release command, replicas, port, probes, rollout strategy and policy, routes, config env and decrypted secrets come from the DB.
+pub async fn build_spec(pool: &Pool<Postgres>, app_name: &str, dep: &Deployment, dev_hot: bool) -> anyhow::Result<crate::k8s::DeploySpec> {
+    let namespace = crate::services::apps::namespace_for(pool, app_name).await?.unwrap_or_else(crate::services::apps::default_namespace);
+    let env = crate::services::config::get_config(pool, dep.app_id).await?.into_iter().collect();
+    let secrets = crate::k8s::SecretEnv(crate::services::secrets::load_values(pool, dep.app_id).await?);
+    let settings = crate::services::apps::get_settings(pool, dep.app_id).await?;
+    let routes = crate::services::domains::routes_for(pool, dep.app_id, app_name).await?;
+    let (sbom, meta) = artifact_hints(pool, dep.digest.as_deref()).await?;
+    let runtime = resolve_runtime(settings.runtime.as_deref(), sbom.as_ref(), meta.as_ref())?;
+    let command = meta.as_ref().and_then(|m| runtime.start_command(m));
+    let process_replicas = crate::services::processes::process_replicas(pool, dep.app_id).await?;
+    let processes = meta.as_ref().map(|m| crate::services::processes::process_specs(m, &process_replicas)).unwrap_or_default();
+    let release_command = settings.release_command.as_deref()
+        .or_else(|| meta.as_ref()?.processes.get(crate::services::processes::RELEASE_PROCESS).map(String::as_str))
+        .filter(|c| !c.trim().is_empty())
+        .map(crate::runtime::procfile_command);
+    let strategy = crate::services::rollouts::state(pool, dep.id).await?.strategy;
+    Ok(crate::k8s::DeploySpec {
+        app: app_name.to_string(),
+        deployment_id: dep.id,
+        namespace,
+        digest: dep.digest.clone().unwrap_or_default(),
+        artifact_url: dep.artifact_url.clone(),
+        signature: dep.signature.clone(),
+        dev_hot,
+        replicas: Some(settings.replicas),
+        env,
+        secrets,
+        runtime: Some(runtime),
+        command,
+        processes,
+        release_command,
+        port: settings.port,
+        routes,
+        ingress_class: crate::services::domains::ingress_class(),
+        probes: settings.probes.0,
+        termination_grace_period_seconds: settings.termination_grace_period_seconds,
+        pre_stop_sleep_seconds: settings.pre_stop_sleep_seconds,
+        strategy,
+        rollout: settings.rollout_policy.0,
+    })
+}
+
+/// SBOM and start metadata the CLI attached to an artifact (both `None` for unknown digests or older uploads).
+pub async fn artifact_hints(pool: &Pool<Postgres>, digest: Option<&str>) -> Result<(Option<serde_json::Value>, Option<crate::models::ArtifactMetadata>), sqlx::Error> {
+    let Some(d) = digest else { return Ok((None, None)); };
+    let row: Option<(Option<serde_json::Value>, Option<sqlx::types::Json<crate::models::ArtifactMetadata>>)> =
+        sqlx::query_as("SELECT sbom, metadata FROM artifacts WHERE digest=$1").bind(d).fetch_optional(pool).await?;
+    Ok(row.map(|(sbom, meta)| (sbom, meta.map(|m| m.0))).unwrap_or_default())
+}
+
+/// Runtime of a release: the app's pinned runtime, else the Node image matching the artifact's `engines.node`,
+/// else the one detected from the artifact SBOM, else the platform default.
+pub fn resolve_runtime(pinned: Option<&str>, sbom: Option<&serde_json::Value>, meta: Option<&crate::models::ArtifactMetadata>) -> anyhow::Result<&'static crate::runtime::Runtime> {
+    if let Some(id) = pinned {
+        return crate::runtime::get(id).ok_or_else(|| anyhow::anyhow!("unknown runtime '{id}'"));
+    }
+    if let Some(rt) = meta.and_then(|m| m.engines_node.as_deref()).and_then(crate::runtime::node_for_engines) { return Ok(rt); }
+    if let Some(rt) = sbom.and_then(crate::runtime::detect_from_sbom) { return Ok(rt); }
+    Ok(crate::runtime::default_runtime())
+}
+
+/// Run the release Job of a deployment (if its spec has a release command) before anything is rolled out. The Job log
+/// is kept as `release_log` events; on failure the deployment is marked failed with `release_failed` and `false` returned.
+pub async fn run_release_phase(pool: &Pool<Postgres>, id: uuid::Uuid, spec: &crate::k8s::DeploySpec) -> bool {
+    let Some(command) = &spec.release_command else { return true; };
+    record_event(pool, id, "release_started", Some(&command.join(" "))).await;
+    let outcome = crate::k8s::run_release_job(spec).await.unwrap_or_else(|e| crate::k8s::ReleaseOutcome {
+        reason: Some(format!("error: {e}")),
+        ..crate::k8s::ReleaseOutcome::default()
+    });
+    for line in &outcome.logs { record_event(pool, id, "release_log", Some(line)).await; }
+    if outcome.succeeded {
+        record_event(pool, id, "release_succeeded", None).await;
+        return true;
+    }
+    let reason = outcome.reason.as_deref().unwrap_or("unknown");
+    record_event(pool, id, "release_log", Some(&format!("release job failed: {reason}"))).await;
+    tracing::warn!(deployment_id=%id, app=%spec.app, reason, "release phase failed");
+    mark_failed(pool, id, "release_failed").await;
+    false
+}
+
+/// Latest deployment of an app that has not failed (the release currently rolling out or serving).
+pub async fn current_release(pool: &Pool<Postgres>, app_id: uuid::Uuid) -> Result<Option<Deployment>, sqlx::Error> {
+    sqlx::query_as::<_, Deployment>("SELECT id, app_id, artifact_url, status, created_at, digest, failure_reason, last_transition_at, signature FROM deployments WHERE app_id=$1 AND status <> 'failed' ORDER BY created_at DESC LIMIT 1")
+        .bind(app_id).fetch_optional(pool).await
+}
+
+/// Roll out the current release again as a new deployment (e.g. after a config change), recording `event_type` on it.
+/// `Ok(None)` when the app has nothing deployed yet.
+pub async fn redeploy_current(pool: &Pool<Postgres>, app_name: &str, app_id: uuid::Uuid, event_type: &str, message: &str) -> Result<Option<Deployment>, sqlx::Error> {
+    let Some(src) = current_release(pool, app_id).await? else { return Ok(None); };
+    let dep = create_deployment(pool, app_name, &src.artifact_url, src.digest.as_deref(), src.signature.as_deref()).await?;
+    record_event(pool, dep.id, event_type, Some(message)).await;
+    crate::services::apply_queue::enqueue(pool, dep.id, false).await?;
+    Ok(Some(dep))
+}
+
+/// Which release a rollback should restore.
+#[derive(Debug, Clone)]
+pub enum RollbackTarget {
+    /// Most recent `running` deployment other than the app's latest deployment.
+    Previous,
+    Deployment(uuid::Uuid),
+    Digest(String),
+}
+
+/// Resolve the `running` deployment a rollback should redeploy. `Ok(None)` when no candidate matches.
+pub async fn find_rollback_target(pool: &Pool<Postgres>, app_id: uuid::Uuid, target: &RollbackTarget) -> Result<Option<Deployment>, sqlx::Error> {
+    const COLS: &str = "id, app_id, artifact_url, status, created_at, digest, failure_reason, last_transition_at, signature";
+    match target {
+        RollbackTarget::Previous => sqlx::query_as::<_, Deployment>(&format!(
+            "SELECT {COLS} FROM deployments WHERE app_id=$1 AND status='running'
+               AND id <> (SELECT id FROM deployments WHERE app_id=$1 ORDER BY created_at DESC LIMIT 1)
+             ORDER BY created_at DESC LIMIT 1"))
+            .bind(app_id).fetch_optional(pool).await,
+        RollbackTarget::Deployment(id) => sqlx::query_as::<_, Deployment>(&format!(
+            "SELECT {COLS} FROM deployments WHERE app_id=$1 AND id=$2 AND status='running'"))
+            .bind(app_id).bind(id).fetch_optional(pool).await,
+        RollbackTarget::Digest(d) => sqlx::query_as::<_, Deployment>(&format!(
+            "SELECT {COLS} FROM deployments WHERE app_id=$1 AND digest=$2 AND status='running' ORDER BY created_at DESC LIMIT 1"))
+            .bind(app_id).bind(d).fetch_optional(pool).await,
+    }
+}
+
+/// Create a new pending deployment reusing the source deployment's artifact, digest and signature,
+/// and record a `rollback` event on it pointing at the source.
+pub async fn create_rollback(pool: &Pool<Postgres>, app_name: &str, source: &Deployment) -> Result<Deployment, sqlx::Error> {
+    let dep = create_deployment(pool, app_name, &source.artifact_url, source.digest.as_deref(), source.signature.as_deref()).await?;
+    let msg = format!("from={} digest={}", source.id, source.digest.as_deref().unwrap_or("-"));
+    record_event(pool, dep.id, "rollback", Some(&msg)).await;
+    Ok(dep)
+}
+
+/// Automatic rollback after the status watcher failed a deployment (apps with `auto_rollback` only): redeploy the
+/// latest release that was running before it, linking both through `auto_rollback` events and `auto_rollback_of`.
+/// Skipped when the failed deployment is itself an automatic rollback (loop guard), when a newer deployment exists,
+/// or when nothing ran before it.
+pub async fn auto_rollback(pool: &Pool<Postgres>, failed_id: uuid::Uuid) -> Result<Option<Deployment>, sqlx::Error> {
+    let Some(row) = sqlx::query("SELECT a.name, a.auto_rollback, d.app_id, d.created_at, d.auto_rollback_of FROM deployments d JOIN applications a ON a.id = d.app_id WHERE d.id=$1")
+        .bind(failed_id).fetch_optional(pool).await? else { return Ok(None); };
+    if !row.get::<bool, _>("auto_rollback") { return Ok(None); }
+    let (app_name, app_id, created_at): (String, uuid::Uuid, chrono::DateTime<chrono::Utc>) = (row.get("name"), row.get("app_id"), row.get("created_at"));
+    let rollback_of: Option<uuid::Uuid> = row.get("auto_rollback_of");
+    let skip = |outcome: &'static str| async move {
+        crate::telemetry::DEPLOYMENT_AUTO_ROLLBACKS.with_label_values(&[outcome]).inc();
+        record_event(pool, failed_id, "auto_rollback_skipped", Some(outcome)).await;
+        tracing::warn!(deployment_id=%failed_id, outcome, "automatic rollback skipped");
+        Ok(None)
+    };
+    if rollback_of.is_some() { return skip("loop_guard").await; }
+    let newer: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM deployments WHERE app_id=$1 AND created_at > $2)")
+        .bind(app_id).bind(created_at).fetch_one(pool).await?;
+    if newer { return skip("superseded").await; }
+    let target = sqlx::query_as::<_, Deployment>("SELECT id, app_id, artifact_url, status, created_at, digest, failure_reason, last_transition_at, signature FROM deployments
+        WHERE app_id=$1 AND status='running' AND created_at < $2 ORDER BY created_at DESC LIMIT 1")
+        .bind(app_id).bind(created_at).fetch_optional(pool).await?;
+    let Some(target) = target else { return skip("no_target").await; };
+    let dep = create_deployment(pool, &app_name, &target.artifact_url, target.digest.as_deref(), target.signature.as_deref()).await?;
+    sqlx::query("UPDATE deployments SET auto_rollback_of=$2 WHERE id=$1").bind(dep.id).bind(failed_id).execute(pool).await?;
+    record_event(pool, failed_id, "auto_rollback", Some(&format!("rollback_deployment={} target={}", dep.id, target.id))).await;
+    record_event(pool, dep.id, "auto_rollback", Some(&format!("failed={} from={} digest={}", failed_id, target.id, target.digest.as_deref().unwrap_or("-")))).await;
+    crate::telemetry::DEPLOYMENT_AUTO_ROLLBACKS.with_label_values(&["triggered"]).inc();
+    tracing::warn!(deployment_id=%failed_id, rollback_id=%dep.id, target_id=%target.id, app=%app_name, "automatic rollback triggered");
+    crate::services::apply_queue::enqueue(pool, dep.id, false).await?;
+    Ok(Some(dep))
 }
 
 pub async fn list_deployments(pool: &Pool<Postgres>) -> Result<Vec<Deployment>, sqlx::Error> {
@@ -58,12 +296,11 @@ pub async fn get_deployment(pool: &Pool<Postgres>, id: uuid::Uuid) -> Result<Dep
 }
 
 pub async fn mark_running(pool: &Pool<Postgres>, id: uuid::Uuid) {
-    let _ = sqlx::query("UPDATE deployments SET status='running', failure_reason=NULL, last_transition_at=now() WHERE id=$1")
-        .bind(id)
-        .execute(pool).await;
-    let _ = sqlx::query("INSERT INTO deployment_events (deployment_id, event_type, message) VALUES ($1,'running',NULL)")
+    let app_id: Option<uuid::Uuid> = sqlx::query_scalar("UPDATE deployments SET status='running', failure_reason=NULL, last_transition_at=now() WHERE id=$1 RETURNING app_id")
         .bind(id)
-        .execute(pool).await;
+        .fetch_optional(pool).await.ok().flatten();
+    if let Some(app_id) = app_id { publish_status(pool, id, app_id, "running", None).await; }
+    record_event(pool, id, "running", None).await;
     // Metrics: increment running, record time-to-running
     crate::telemetry::DEPLOYMENT_STATUS.with_label_values(&["running"]).inc();
     if let Ok(row) = sqlx::query("SELECT created_at FROM deployments WHERE id=$1").bind(id).fetch_one(pool).await {
@@ -71,19 +308,31 @@ pub async fn mark_running(pool: &Pool<Postgres>, id: uuid::Uuid) {
         let secs = (chrono::Utc::now() - created_at).num_seconds() as f64;
     crate::telemetry::DEPLOYMENT_TIME_TO_RUNNING.observe(secs);
     }
+    crate::services::webhooks::deployment_event(pool, id, "running").await;
+}
+
+/// Metrics label of a failure reason. Reasons may carry a process prefix (`worker:crash_loop`) or details
+/// (`apply_failed:<error>`, `init:<container>:<reason>`).
+pub fn failure_label(reason: &str) -> &'static str {
+    const LABELS: &[&str] = &["crash_loop", "image_pull", "oom_killed", "checksum_mismatch", "timeout", "release_failed", "apply_failed"];
+    let parts: Vec<&str> = reason.split(':').collect();
+    if let Some(label) = parts.iter().find_map(|p| LABELS.iter().find(|l| *l == p)) { return label; }
+    if parts.contains(&"init") { return "init_failed"; }
+    if parts.contains(&"ProgressDeadlineExceeded") { return "progress_deadline"; }
+    "other"
 }
 
 pub async fn mark_failed(pool: &Pool<Postgres>, id: uuid::Uuid, reason: &str) {
-    let _ = sqlx::query("UPDATE deployments SET status='failed', failure_reason=$2, last_transition_at=now() WHERE id=$1")
-        .bind(id)
-        .bind(reason)
-        .execute(pool).await;
-    let _ = sqlx::query("INSERT INTO deployment_events (deployment_id, event_type, message) VALUES ($1,'failed',$2)")
+    let app_id: Option<uuid::Uuid> = sqlx::query_scalar("UPDATE deployments SET status='failed', failure_reason=$2, last_transition_at=now() WHERE id=$1 RETURNING app_id")
         .bind(id)
         .bind(reason)
-        .execute(pool).await;
+        .fetch_optional(pool).await.ok().flatten();
+    if let Some(app_id) = app_id { publish_status(pool, id, app_id, "failed", Some(reason)).await; }
+    record_event(pool, id, "failed", Some(reason)).await;
     // Metrics: increment failed
     crate::telemetry::DEPLOYMENT_STATUS.with_label_values(&["failed"]).inc();
+    crate::telemetry::DEPLOYMENT_FAILURES.with_label_values(&[failure_label(reason)]).inc();
+    crate::services::webhooks::deployment_event(pool, id, "failed").await;
 }
 
 /// GC failed deployments that are older than ttl_secs and superseded by a newer running deployment for the same app.
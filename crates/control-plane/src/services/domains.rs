@@ -0,0 +1,82 @@
+use sqlx::{Pool, Postgres};
+use chrono::{DateTime, Utc};
+use crate::k8s::Route;
+
+/// Custom hostname (and path prefix) routed to an application.
+#[derive(sqlx::FromRow, serde::Serialize, utoipa::ToSchema, Debug, Clone)]
+pub struct AppDomain { pub hostname: String, pub path: String, pub created_at: DateTime<Utc> }
+
+/// DNS-1123 subdomain with at least two labels, optionally a leading `*.` wildcard.
+pub fn valid_hostname(host: &str) -> bool {
+    let bare = host.strip_prefix("*.").unwrap_or(host);
+    host.len() <= 253 && bare.contains('.')
+        && bare.split('.').all(|l| !l.is_empty() && l.len() <= 63
+            && l.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            && !l.starts_with('-') && !l.ends_with('-'))
+}
+
+/// Absolute path prefix without whitespace, query or fragment.
+pub fn valid_path(path: &str) -> bool {
+    path.starts_with('/') && path.len() <= 1024 && !path.chars().any(|c| c.is_whitespace() || c == '?' || c == '#')
+}
+
+/// Base domain for generated `<app>.<domain>` hosts (`AETHER_INGRESS_DOMAIN`); unset disables generated routes.
+pub fn ingress_domain() -> Option<String> {
+    std::env::var("AETHER_INGRESS_DOMAIN").ok().map(|d| d.trim().trim_start_matches('.').to_string()).filter(|d| !d.is_empty())
+}
+
+/// Ingress class for app Ingresses (`AETHER_INGRESS_CLASS`, cluster default when unset).
+pub fn ingress_class() -> Option<String> {
+    std::env::var("AETHER_INGRESS_CLASS").ok().filter(|c| !c.trim().is_empty())
+}
+
+pub async fn list_domains(pool: &Pool<Postgres>, app_id: uuid::Uuid) -> Result<Vec<AppDomain>, sqlx::Error> {
+    sqlx::query_as::<_, AppDomain>("SELECT hostname, path, created_at FROM app_domains WHERE app_id=$1 ORDER BY hostname, path")
+        .bind(app_id).fetch_all(pool).await
+}
+
+/// Fails with a unique violation (23505) when the host + path is already routed (to any app).
+pub async fn add_domain(pool: &Pool<Postgres>, app_id: uuid::Uuid, hostname: &str, path: &str) -> Result<AppDomain, sqlx::Error> {
+    sqlx::query_as::<_, AppDomain>("INSERT INTO app_domains (app_id, hostname, path) VALUES ($1,$2,$3) RETURNING hostname, path, created_at")
+        .bind(app_id).bind(hostname).bind(path).fetch_one(pool).await
+}
+
+/// Remove every path of `hostname` for the app; returns whether anything was removed.
+pub async fn remove_domain(pool: &Pool<Postgres>, app_id: uuid::Uuid, hostname: &str) -> Result<bool, sqlx::Error> {
+    let res = sqlx::query("DELETE FROM app_domains WHERE app_id=$1 AND hostname=$2").bind(app_id).bind(hostname).execute(pool).await?;
+    Ok(res.rows_affected() > 0)
+}
+
+/// Ingress routes of an app: the generated `<app>.<AETHER_INGRESS_DOMAIN>` host (if configured) followed by custom domains.
+pub async fn routes_for(pool: &Pool<Postgres>, app_id: uuid::Uuid, app_name: &str) -> Result<Vec<Route>, sqlx::Error> {
+    let mut routes: Vec<Route> = ingress_domain().map(|d| Route { host: format!("{app_name}.{d}"), path: "/".into() }).into_iter().collect();
+    routes.extend(list_domains(pool, app_id).await?.into_iter().map(|d| Route { host: d.hostname, path: d.path }));
+    Ok(routes)
+}
+
+/// Fire-and-forget re-apply of the app Service/Ingress after its domains changed (no new release needed).
+pub fn spawn_sync_routes(pool: &Pool<Postgres>, app: &crate::models::Application) {
+    let pool = pool.clone();
+    let app = app.clone();
+    tokio::spawn(async move {
+        let spec = async {
+            let settings = crate::services::apps::get_settings(&pool, app.id).await?;
+            let routes = routes_for(&pool, app.id, &app.name).await?;
+            Ok::<_, sqlx::Error>(crate::k8s::DeploySpec {
+                app: app.name.clone(),
+                namespace: app.namespace.clone(),
+                runtime: settings.runtime.as_deref().and_then(crate::runtime::get),
+                port: settings.port,
+                routes,
+                ingress_class: ingress_class(),
+                ..Default::default()
+            })
+        }.await;
+        match spec {
+            Ok(spec) => if let Err(e) = crate::k8s::apply_routing(&spec).await {
+                tracing::error!(error=%e, app=%app.name, "k8s routing apply failed");
+            },
+            Err(e) => tracing::error!(error=%e, app=%app.name, "routing spec lookup failed"),
+        }
+    });
+}
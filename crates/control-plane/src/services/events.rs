@@ -0,0 +1,53 @@
+use sqlx::{Pool, Postgres};
+use chrono::{DateTime, Utc};
+use crate::models::{DeploymentEvent, ArtifactEvent, AppEvent};
+
+/// Pagination + time window shared by the event history queries (bounds inclusive).
+#[derive(Debug, Clone)]
+pub struct EventFilter {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Events of a single deployment, newest first. `RowNotFound` if the deployment does not exist.
+pub async fn list_deployment_events(pool: &Pool<Postgres>, deployment_id: uuid::Uuid, f: &EventFilter) -> Result<Vec<DeploymentEvent>, sqlx::Error> {
+    let exists: Option<uuid::Uuid> = sqlx::query_scalar("SELECT id FROM deployments WHERE id=$1")
+        .bind(deployment_id).fetch_optional(pool).await?;
+    if exists.is_none() { return Err(sqlx::Error::RowNotFound); }
+    sqlx::query_as::<_, DeploymentEvent>("SELECT id, deployment_id, event_type, message, created_at, source_kind, source_name, source_reason FROM deployment_events
+        WHERE deployment_id=$1 AND ($2::timestamptz IS NULL OR created_at >= $2) AND ($3::timestamptz IS NULL OR created_at <= $3)
+        ORDER BY created_at DESC, id DESC LIMIT $4 OFFSET $5")
+        .bind(deployment_id).bind(f.since).bind(f.until).bind(f.limit).bind(f.offset)
+        .fetch_all(pool).await
+}
+
+/// Events of an artifact by digest, newest first. `RowNotFound` if the artifact does not exist.
+pub async fn list_artifact_events(pool: &Pool<Postgres>, digest: &str, f: &EventFilter) -> Result<Vec<ArtifactEvent>, sqlx::Error> {
+    let artifact_id: uuid::Uuid = sqlx::query_scalar("SELECT id FROM artifacts WHERE digest=$1")
+        .bind(digest).fetch_optional(pool).await?.ok_or(sqlx::Error::RowNotFound)?;
+    sqlx::query_as::<_, ArtifactEvent>("SELECT id, artifact_id, event_type, created_at FROM artifact_events
+        WHERE artifact_id=$1 AND ($2::timestamptz IS NULL OR created_at >= $2) AND ($3::timestamptz IS NULL OR created_at <= $3)
+        ORDER BY created_at DESC, id DESC LIMIT $4 OFFSET $5")
+        .bind(artifact_id).bind(f.since).bind(f.until).bind(f.limit).bind(f.offset)
+        .fetch_all(pool).await
+}
+
+/// Application timeline merging events of its deployments and artifacts, newest first.
+/// `RowNotFound` if the application does not exist.
+pub async fn list_app_events(pool: &Pool<Postgres>, app_name: &str, f: &EventFilter) -> Result<Vec<AppEvent>, sqlx::Error> {
+    let app_id: uuid::Uuid = sqlx::query_scalar("SELECT id FROM applications WHERE name=$1")
+        .bind(app_name).fetch_optional(pool).await?.ok_or(sqlx::Error::RowNotFound)?;
+    sqlx::query_as::<_, AppEvent>("SELECT source, deployment_id, digest, event_type, message, created_at FROM (
+            SELECT 'deployment' AS source, e.deployment_id, d.digest::text AS digest, e.event_type, e.message, e.created_at, e.id
+              FROM deployment_events e JOIN deployments d ON d.id = e.deployment_id WHERE d.app_id=$1
+            UNION ALL
+            SELECT 'artifact' AS source, NULL::uuid, a.digest::text, e.event_type, NULL::text, e.created_at, e.id
+              FROM artifact_events e JOIN artifacts a ON a.id = e.artifact_id WHERE a.app_id=$1
+        ) t
+        WHERE ($2::timestamptz IS NULL OR created_at >= $2) AND ($3::timestamptz IS NULL OR created_at <= $3)
+        ORDER BY created_at DESC, id DESC LIMIT $4 OFFSET $5")
+        .bind(app_id).bind(f.since).bind(f.until).bind(f.limit).bind(f.offset)
+        .fetch_all(pool).await
+}
@@ -0,0 +1,50 @@
+use sqlx::{Pool, Postgres};
+use chrono::{DateTime, Utc};
+use crate::models::PublicKey;
+
+/// Keys currently usable for signature verification: active and inside their validity window.
+/// Shared by deployment creation and the artifact upload paths (`$1` = app id).
+pub const USABLE_KEYS_SQL: &str = "SELECT public_key_hex FROM public_keys WHERE app_id=$1 AND active
+    AND (not_before IS NULL OR not_before <= now()) AND (expires_at IS NULL OR expires_at > now())";
+
+const COLS: &str = "id, app_id, public_key_hex, active, created_at, not_before, expires_at";
+
+/// Default overlap during which a rotated-out key stays valid (`AETHER_KEY_ROTATION_GRACE_SECS`, default 24h).
+pub fn default_rotation_grace_secs() -> i64 {
+    std::env::var("AETHER_KEY_ROTATION_GRACE_SECS").ok().and_then(|v| v.parse::<i64>().ok()).filter(|v| *v >= 0).unwrap_or(86_400)
+}
+
+async fn app_id(pool: &Pool<Postgres>, app_name: &str) -> Result<uuid::Uuid, sqlx::Error> {
+    sqlx::query_scalar("SELECT id FROM applications WHERE name=$1").bind(app_name)
+        .fetch_optional(pool).await?.ok_or(sqlx::Error::RowNotFound)
+}
+
+/// All keys of an application (including inactive / expired), newest first. `RowNotFound` if the app does not exist.
+pub async fn list_keys(pool: &Pool<Postgres>, app_name: &str) -> Result<Vec<PublicKey>, sqlx::Error> {
+    let app_id = app_id(pool, app_name).await?;
+    sqlx::query_as::<_, PublicKey>(&format!("SELECT {COLS} FROM public_keys WHERE app_id=$1 ORDER BY created_at DESC"))
+        .bind(app_id).fetch_all(pool).await
+}
+
+/// Revoke a key (`active=false`). `RowNotFound` if the app or key does not exist.
+pub async fn deactivate_key(pool: &Pool<Postgres>, app_name: &str, key_id: uuid::Uuid) -> Result<PublicKey, sqlx::Error> {
+    let app_id = app_id(pool, app_name).await?;
+    sqlx::query_as::<_, PublicKey>(&format!("UPDATE public_keys SET active=FALSE WHERE app_id=$1 AND id=$2 RETURNING {COLS}"))
+        .bind(app_id).bind(key_id).fetch_one(pool).await
+}
+
+/// Add (or reactivate) `public_key_hex` and schedule every other active key of the app to expire at `old_expires_at`
+/// (keys already expiring earlier keep their deadline). Returns the new key and the keys scheduled to expire.
+pub async fn rotate_key(pool: &Pool<Postgres>, app_name: &str, public_key_hex: &str, not_before: Option<DateTime<Utc>>, old_expires_at: DateTime<Utc>) -> Result<(PublicKey, Vec<PublicKey>), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let app_id: uuid::Uuid = sqlx::query_scalar("SELECT id FROM applications WHERE name=$1").bind(app_name)
+        .fetch_optional(&mut *tx).await?.ok_or(sqlx::Error::RowNotFound)?;
+    let new_key = sqlx::query_as::<_, PublicKey>(&format!("INSERT INTO public_keys (app_id, public_key_hex, active, not_before, expires_at) VALUES ($1,$2,TRUE,$3,NULL)
+        ON CONFLICT (app_id, public_key_hex) DO UPDATE SET active=TRUE, not_before=EXCLUDED.not_before, expires_at=NULL RETURNING {COLS}"))
+        .bind(app_id).bind(public_key_hex).bind(not_before).fetch_one(&mut *tx).await?;
+    let expiring = sqlx::query_as::<_, PublicKey>(&format!("UPDATE public_keys SET expires_at = LEAST(COALESCE(expires_at, $3), $3)
+        WHERE app_id=$1 AND id<>$2 AND active AND (expires_at IS NULL OR expires_at > now()) RETURNING {COLS}"))
+        .bind(app_id).bind(new_key.id).bind(old_expires_at).fetch_all(&mut *tx).await?;
+    tx.commit().await?;
+    Ok((new_key, expiring))
+}
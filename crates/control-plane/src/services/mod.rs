@@ -1,2 +1,11 @@
 pub mod apps;
+pub mod apply_queue;
 pub mod deployments;
+pub mod events;
+pub mod keys;
+pub mod config;
+pub mod secrets;
+pub mod domains;
+pub mod processes;
+pub mod rollouts;
+pub mod webhooks;
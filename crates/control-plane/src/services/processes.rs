@@ -0,0 +1,98 @@
+use sqlx::{PgExecutor, Pool, Postgres};
+use std::collections::BTreeMap;
+use crate::k8s::{candidate_name, process_object_name, DeploySpec, ProcessSpec, WEB_PROCESS};
+use crate::models::{ArtifactMetadata, DeploymentProcess, PodProgress};
+
+/// Procfile entry reserved for the one-off release phase; never run as a long-lived Deployment.
+pub const RELEASE_PROCESS: &str = "release";
+
+/// Process types become part of Deployment names (`<app>-<type>`): short DNS-1123 labels, `release` excluded.
+pub fn valid_process_type(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 30
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-') && !name.ends_with('-')
+        && name != RELEASE_PROCESS
+}
+
+/// Non-web process types of an artifact (Procfile entries), with the app's replica counts (default 1).
+/// Entries with invalid type names are skipped.
+pub fn process_specs(meta: &ArtifactMetadata, replicas: &BTreeMap<String, i32>) -> Vec<ProcessSpec> {
+    meta.processes.iter()
+        .filter(|(name, cmd)| name.as_str() != WEB_PROCESS && name.as_str() != RELEASE_PROCESS && !cmd.trim().is_empty())
+        .filter(|(name, _)| {
+            let ok = valid_process_type(name);
+            if !ok { tracing::warn!(process=%name, "skipping Procfile entry with invalid process type"); }
+            ok
+        })
+        .map(|(name, cmd)| ProcessSpec {
+            name: name.clone(),
+            command: crate::runtime::procfile_command(cmd),
+            replicas: replicas.get(name).copied().unwrap_or(1),
+        })
+        .collect()
+}
+
+/// Replica counts of an app's non-web process types set through the scale API.
+pub async fn process_replicas<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid) -> Result<BTreeMap<String, i32>, sqlx::Error> {
+    let rows: Vec<(String, i32)> = sqlx::query_as("SELECT process_type, replicas FROM app_processes WHERE app_id=$1")
+        .bind(app_id).fetch_all(ex).await?;
+    Ok(rows.into_iter().collect())
+}
+
+pub async fn set_process_replicas<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, process: &str, replicas: i32) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO app_processes (app_id, process_type, replicas) VALUES ($1,$2,$3)
+        ON CONFLICT (app_id, process_type) DO UPDATE SET replicas=EXCLUDED.replicas, updated_at=now()")
+        .bind(app_id).bind(process).bind(replicas).execute(ex).await?;
+    Ok(())
+}
+
+/// Record the Kubernetes Deployments a release is rendered into (web plus `spec.processes`), resetting their rollout state.
+pub async fn record_processes(pool: &Pool<Postgres>, deployment_id: uuid::Uuid, spec: &DeploySpec) -> Result<(), sqlx::Error> {
+    let mut rows = vec![(WEB_PROCESS.to_string(), spec.app.clone(), spec.replicas.unwrap_or(1))];
+    rows.extend(spec.processes.iter().map(|p| (p.name.clone(), process_object_name(&spec.app, &p.name), p.replicas)));
+    record_rows(pool, deployment_id, rows).await
+}
+
+/// Record the candidate Deployment of a blue/green or canary release as its only tracked (web) process.
+pub async fn record_candidate(pool: &Pool<Postgres>, deployment_id: uuid::Uuid, spec: &DeploySpec) -> Result<(), sqlx::Error> {
+    record_rows(pool, deployment_id, vec![(WEB_PROCESS.to_string(), candidate_name(spec), spec.replicas.unwrap_or(1))]).await
+}
+
+async fn record_rows(pool: &Pool<Postgres>, deployment_id: uuid::Uuid, rows: Vec<(String, String, i32)>) -> Result<(), sqlx::Error> {
+    let names: Vec<String> = rows.iter().map(|(n, _, _)| n.clone()).collect();
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM deployment_processes WHERE deployment_id=$1 AND NOT (process_type = ANY($2))")
+        .bind(deployment_id).bind(&names).execute(&mut *tx).await?;
+    for (name, object_name, replicas) in rows {
+        sqlx::query("INSERT INTO deployment_processes (deployment_id, process_type, object_name, desired_replicas) VALUES ($1,$2,$3,$4)
+            ON CONFLICT (deployment_id, process_type) DO UPDATE SET object_name=EXCLUDED.object_name, desired_replicas=EXCLUDED.desired_replicas,
+                updated_replicas=0, ready_replicas=0, available_replicas=0, ready=FALSE, pods='[]'::jsonb, updated_at=now()")
+            .bind(deployment_id).bind(&name).bind(object_name).bind(replicas)
+            .execute(&mut *tx).await?;
+    }
+    tx.commit().await
+}
+
+pub async fn list_for_deployment(pool: &Pool<Postgres>, deployment_id: uuid::Uuid) -> Result<Vec<DeploymentProcess>, sqlx::Error> {
+    sqlx::query_as::<_, DeploymentProcess>("SELECT process_type, object_name, desired_replicas, updated_replicas, ready_replicas, available_replicas, ready, pods, updated_at FROM deployment_processes WHERE deployment_id=$1 ORDER BY process_type")
+        .bind(deployment_id).fetch_all(pool).await
+}
+
+/// Store the observed state of one process Deployment (ready once a pod is available, or when scaled to zero) and
+/// return whether every process of the release is ready; `None` when the release tracks no processes (applied before
+/// process types existed), leaving the decision to the caller.
+pub async fn observe(pool: &Pool<Postgres>, deployment_id: uuid::Uuid, process: &str, desired: i32, available: i32) -> Result<Option<bool>, sqlx::Error> {
+    sqlx::query("UPDATE deployment_processes SET desired_replicas=$3, available_replicas=$4, ready=($3 = 0 OR $4 >= 1), updated_at=now()
+        WHERE deployment_id=$1 AND process_type=$2")
+        .bind(deployment_id).bind(process).bind(desired).bind(available).execute(pool).await?;
+    sqlx::query_scalar("SELECT bool_and(ready) FROM deployment_processes WHERE deployment_id=$1")
+        .bind(deployment_id).fetch_one(pool).await
+}
+
+/// Cache the rollout progress of one process Deployment (updated and ready replicas, its pods) for the status API.
+pub async fn observe_progress(pool: &Pool<Postgres>, deployment_id: uuid::Uuid, process: &str, updated: i32, ready: i32, pods: &[PodProgress]) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE deployment_processes SET updated_replicas=$3, ready_replicas=$4, pods=$5, updated_at=now() WHERE deployment_id=$1 AND process_type=$2")
+        .bind(deployment_id).bind(process).bind(updated).bind(ready).bind(sqlx::types::Json(pods))
+        .execute(pool).await?;
+    Ok(())
+}
@@ -0,0 +1,133 @@
+//! Blue/green and canary rollouts. The candidate Deployment runs next to the serving one (`progressing`, then
+//! `awaiting_promotion` once the watcher sees it ready); promote moves traffic onto it and rolls the app's Deployments
+//! to the release (`promoting`, `promoted` once they are ready), abort tears it down (`aborted`).
+use sqlx::{Pool, Postgres};
+use crate::models::{Deployment, RolloutStrategy};
+
+pub const PHASE_PROGRESSING: &str = "progressing";
+pub const PHASE_AWAITING_PROMOTION: &str = "awaiting_promotion";
+pub const PHASE_PROMOTING: &str = "promoting";
+pub const PHASE_PROMOTED: &str = "promoted";
+pub const PHASE_ABORTED: &str = "aborted";
+
+#[derive(Debug, Clone)]
+pub struct RolloutState {
+    pub strategy: RolloutStrategy,
+    /// `None` for rolling deployments
+    pub phase: Option<String>,
+}
+
+pub async fn state(pool: &Pool<Postgres>, id: uuid::Uuid) -> Result<RolloutState, sqlx::Error> {
+    let (strategy, percent, phase): (String, Option<i32>, Option<String>) =
+        sqlx::query_as("SELECT strategy, canary_percent, rollout_phase FROM deployments WHERE id=$1")
+            .bind(id).fetch_one(pool).await?;
+    Ok(RolloutState { strategy: RolloutStrategy::from_db(&strategy, percent), phase })
+}
+
+/// Store the strategy of a new deployment; blue/green and canary rollouts start `progressing`.
+pub async fn set_strategy(pool: &Pool<Postgres>, id: uuid::Uuid, strategy: RolloutStrategy) -> Result<(), sqlx::Error> {
+    let phase = (strategy != RolloutStrategy::Rolling).then_some(PHASE_PROGRESSING);
+    sqlx::query("UPDATE deployments SET strategy=$2, canary_percent=$3, rollout_phase=$4 WHERE id=$1")
+        .bind(id).bind(strategy.as_str()).bind(strategy.canary_percent()).bind(phase)
+        .execute(pool).await?;
+    Ok(())
+}
+
+async fn set_phase(pool: &Pool<Postgres>, id: uuid::Uuid, phase: &str) {
+    let _ = sqlx::query("UPDATE deployments SET rollout_phase=$2 WHERE id=$1").bind(id).bind(phase).execute(pool).await;
+}
+
+/// Every Deployment tracked for a pending release is ready (called by the status watcher): a candidate now awaits
+/// promotion, a promotion finishes (running, candidate torn down), a rolling deployment is running.
+pub async fn on_ready(pool: &Pool<Postgres>, id: uuid::Uuid) {
+    let phase = match state(pool, id).await {
+        Ok(s) => s.phase,
+        Err(e) => { tracing::warn!(error=%e, deployment_id=%id, "rollout state lookup failed"); return; }
+    };
+    match phase.as_deref() {
+        Some(PHASE_PROGRESSING) => {
+            set_phase(pool, id, PHASE_AWAITING_PROMOTION).await;
+            crate::services::deployments::record_event(pool, id, "candidate_ready", None).await;
+            tracing::info!(deployment_id=%id, "candidate ready, awaiting promotion");
+        }
+        Some(PHASE_PROMOTING) => {
+            crate::services::deployments::mark_running(pool, id).await;
+            set_phase(pool, id, PHASE_PROMOTED).await;
+            spawn_k8s(pool, id, K8sAction::Teardown);
+            tracing::info!(deployment_id=%id, "promotion finished");
+        }
+        Some(_) => {}
+        None => {
+            crate::services::deployments::mark_running(pool, id).await;
+            tracing::info!(deployment_id=%id, "deployment running (watch)");
+        }
+    }
+}
+
+/// A tracked Deployment of a pending release failed (called by the status watcher). A failing candidate is torn down
+/// so the stable release keeps all traffic; otherwise the app may roll back automatically (see
+/// [`crate::services::deployments::auto_rollback`]).
+pub async fn on_failed(pool: &Pool<Postgres>, id: uuid::Uuid, reason: &str) {
+    crate::services::deployments::mark_failed(pool, id, reason).await;
+    let phase = state(pool, id).await.ok().and_then(|s| s.phase);
+    if matches!(phase.as_deref(), Some(PHASE_PROGRESSING | PHASE_AWAITING_PROMOTION)) {
+        set_phase(pool, id, PHASE_ABORTED).await;
+        spawn_k8s(pool, id, K8sAction::Teardown);
+        return;
+    }
+    if let Err(e) = crate::services::deployments::auto_rollback(pool, id).await {
+        tracing::error!(error=%e, deployment_id=%id, "automatic rollback failed");
+    }
+}
+
+/// Start promoting a candidate that is awaiting promotion: the app's Deployments are tracked from now on and the
+/// promotion is applied in the background. `Ok(false)` when the deployment is not in that phase.
+pub async fn promote(pool: &Pool<Postgres>, app_name: &str, dep: &Deployment) -> anyhow::Result<bool> {
+    let moved = sqlx::query("UPDATE deployments SET rollout_phase=$2, last_transition_at=now() WHERE id=$1 AND status='pending' AND rollout_phase=$3")
+        .bind(dep.id).bind(PHASE_PROMOTING).bind(PHASE_AWAITING_PROMOTION)
+        .execute(pool).await?.rows_affected();
+    if moved == 0 { return Ok(false); }
+    let spec = crate::services::deployments::build_spec(pool, app_name, dep, false).await?;
+    crate::services::processes::record_processes(pool, dep.id, &spec).await?;
+    crate::services::deployments::record_event(pool, dep.id, "promote", Some(&crate::k8s::candidate_name(&spec))).await;
+    spawn_k8s(pool, dep.id, K8sAction::Promote);
+    Ok(true)
+}
+
+/// Abort a candidate that has not been promoted: the deployment fails with `aborted` and the candidate is torn down.
+/// `Ok(false)` when the deployment is not a candidate in progress.
+pub async fn abort(pool: &Pool<Postgres>, dep: &Deployment) -> Result<bool, sqlx::Error> {
+    let moved = sqlx::query("UPDATE deployments SET rollout_phase=$2 WHERE id=$1 AND status='pending' AND rollout_phase = ANY($3)")
+        .bind(dep.id).bind(PHASE_ABORTED).bind([PHASE_PROGRESSING, PHASE_AWAITING_PROMOTION])
+        .execute(pool).await?.rows_affected();
+    if moved == 0 { return Ok(false); }
+    crate::services::deployments::mark_failed(pool, dep.id, "aborted").await;
+    spawn_k8s(pool, dep.id, K8sAction::Teardown);
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum K8sAction { Promote, Teardown }
+
+/// Fire-and-forget promotion / candidate teardown of a deployment row, spec rebuilt from the DB.
+fn spawn_k8s(pool: &Pool<Postgres>, id: uuid::Uuid, action: K8sAction) {
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        let spec = async {
+            let dep = crate::services::deployments::get_deployment(&pool, id).await?;
+            let app_name: String = sqlx::query_scalar("SELECT name FROM applications WHERE id=$1").bind(dep.app_id).fetch_one(&pool).await?;
+            crate::services::deployments::build_spec(&pool, &app_name, &dep, false).await
+        }.await;
+        let spec = match spec {
+            Ok(s) => s,
+            Err(e) => { tracing::error!(error=%e, deployment_id=%id, ?action, "deploy spec lookup failed"); return; }
+        };
+        let res = match action {
+            K8sAction::Promote => crate::k8s::promote_candidate(&spec).await,
+            K8sAction::Teardown => crate::k8s::teardown_candidate(&spec).await,
+        };
+        if let Err(e) = res {
+            tracing::error!(error=%e, app=%spec.app, deployment_id=%id, ?action, "k8s rollout action failed");
+        }
+    });
+}
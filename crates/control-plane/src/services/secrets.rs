@@ -0,0 +1,78 @@
+use sqlx::{Pool, Postgres};
+use std::collections::BTreeMap;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::secrets::{self, Keyring};
+
+/// Secret metadata exposed by the API (values are never returned).
+#[derive(sqlx::FromRow, serde::Serialize, utoipa::ToSchema, Debug, Clone)]
+pub struct SecretMeta { pub name: String, pub updated_at: DateTime<Utc> }
+
+fn aad(app_id: uuid::Uuid, name: &str) -> Vec<u8> { format!("{app_id}/{name}").into_bytes() }
+
+/// Unwrap the app's data key, creating (and wrapping) one on first use when `create` is set.
+pub(crate) async fn data_key(pool: &Pool<Postgres>, keyring: &Keyring, app_id: uuid::Uuid, create: bool) -> Result<Option<[u8; 32]>> {
+    let row: Option<(Vec<u8>, String)> = sqlx::query_as("SELECT wrapped_key, master_key_id FROM app_data_keys WHERE app_id=$1")
+        .bind(app_id).fetch_optional(pool).await?;
+    if let Some((wrapped, mk_id)) = row { return keyring.unwrap(&wrapped, &mk_id).map(Some); }
+    if !create { return Ok(None); }
+    let (wrapped, mk_id) = keyring.wrap(&secrets::generate_data_key())?;
+    // Concurrent first writers: keep whichever key landed first.
+    sqlx::query("INSERT INTO app_data_keys (app_id, wrapped_key, master_key_id) VALUES ($1,$2,$3) ON CONFLICT (app_id) DO NOTHING")
+        .bind(app_id).bind(&wrapped).bind(&mk_id).execute(pool).await?;
+    let (wrapped, mk_id): (Vec<u8>, String) = sqlx::query_as("SELECT wrapped_key, master_key_id FROM app_data_keys WHERE app_id=$1")
+        .bind(app_id).fetch_one(pool).await?;
+    keyring.unwrap(&wrapped, &mk_id).map(Some)
+}
+
+pub async fn list_secrets(pool: &Pool<Postgres>, app_id: uuid::Uuid) -> Result<Vec<SecretMeta>, sqlx::Error> {
+    sqlx::query_as::<_, SecretMeta>("SELECT name, updated_at FROM app_secrets WHERE app_id=$1 ORDER BY name")
+        .bind(app_id).fetch_all(pool).await
+}
+
+/// Encrypt and store (or replace) a secret value.
+pub async fn put_secret(pool: &Pool<Postgres>, keyring: &Keyring, app_id: uuid::Uuid, name: &str, value: &str) -> Result<SecretMeta> {
+    let dk = data_key(pool, keyring, app_id, true).await?.ok_or_else(|| anyhow!("data key unavailable"))?;
+    let sealed = secrets::seal(&dk, value.as_bytes(), &aad(app_id, name))?;
+    let meta = sqlx::query_as::<_, SecretMeta>("INSERT INTO app_secrets (app_id, name, ciphertext) VALUES ($1,$2,$3)
+        ON CONFLICT (app_id, name) DO UPDATE SET ciphertext=EXCLUDED.ciphertext, updated_at=now() RETURNING name, updated_at")
+        .bind(app_id).bind(name).bind(&sealed).fetch_one(pool).await?;
+    Ok(meta)
+}
+
+pub async fn delete_secret(pool: &Pool<Postgres>, app_id: uuid::Uuid, name: &str) -> Result<bool, sqlx::Error> {
+    let res = sqlx::query("DELETE FROM app_secrets WHERE app_id=$1 AND name=$2").bind(app_id).bind(name).execute(pool).await?;
+    Ok(res.rows_affected() > 0)
+}
+
+/// Decrypted secret values for projection into the pod. Apps without secrets never need the keyring.
+pub async fn load_values(pool: &Pool<Postgres>, app_id: uuid::Uuid) -> Result<BTreeMap<String, String>> {
+    let rows: Vec<(String, Vec<u8>)> = sqlx::query_as("SELECT name, ciphertext FROM app_secrets WHERE app_id=$1 ORDER BY name")
+        .bind(app_id).fetch_all(pool).await?;
+    if rows.is_empty() { return Ok(BTreeMap::new()); }
+    let keyring = Keyring::from_env()?.ok_or_else(|| anyhow!("app has secrets but AETHER_MASTER_KEY_FILE is not configured"))?;
+    let dk = data_key(pool, &keyring, app_id, false).await?.ok_or_else(|| anyhow!("data key missing for app {app_id}"))?;
+    let mut out = BTreeMap::new();
+    for (name, sealed) in rows {
+        let plain = secrets::open(&dk, &sealed, &aad(app_id, &name))?;
+        out.insert(name, String::from_utf8(plain)?);
+    }
+    Ok(out)
+}
+
+/// Re-wrap every data key not wrapped by the keyring's active master key. Returns the number re-wrapped.
+pub async fn rewrap_data_keys(pool: &Pool<Postgres>, keyring: &Keyring) -> Result<usize> {
+    let active = keyring.active().id.clone();
+    let rows: Vec<(uuid::Uuid, Vec<u8>, String)> = sqlx::query_as("SELECT app_id, wrapped_key, master_key_id FROM app_data_keys WHERE master_key_id <> $1")
+        .bind(&active).fetch_all(pool).await?;
+    let mut n = 0;
+    for (app_id, wrapped, mk_id) in rows {
+        let dk = keyring.unwrap(&wrapped, &mk_id)?;
+        let (rewrapped, new_id) = keyring.wrap(&dk)?;
+        // Guard on the old id so a concurrent rotation is not clobbered.
+        let res = sqlx::query("UPDATE app_data_keys SET wrapped_key=$2, master_key_id=$3, rotated_at=now() WHERE app_id=$1 AND master_key_id=$4")
+            .bind(app_id).bind(&rewrapped).bind(&new_id).bind(&mk_id).execute(pool).await?;
+        n += res.rows_affected() as usize;
+    }
+    Ok(n)
+}
@@ -0,0 +1,208 @@
+//! Outbound webhooks: per-app subscriptions (optionally filtered by event type), a persistent delivery queue in
+//! `webhook_deliveries` drained by a background worker with exponential backoff, and the delivery log.
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::{PgExecutor, Pool, Postgres, Row};
+use crate::{secrets::{self, Keyring}, services};
+
+/// Event types a webhook can subscribe to.
+pub const EVENT_TYPES: &[&str] = &["deployment.running", "deployment.failed", "artifact.stored", "artifact.retention_delete"];
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the request body keyed with the webhook secret>`.
+pub const SIGNATURE_HEADER: &str = "X-Aether-Signature";
+
+#[derive(sqlx::FromRow, serde::Serialize, utoipa::ToSchema, Debug, Clone)]
+pub struct Webhook {
+    pub id: uuid::Uuid,
+    pub url: String,
+    /// Subscribed event types (empty: every event)
+    pub event_types: Vec<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One entry of the delivery log (a queued event for one webhook).
+#[derive(sqlx::FromRow, serde::Serialize, utoipa::ToSchema, Debug, Clone)]
+pub struct WebhookDelivery {
+    pub id: uuid::Uuid,
+    pub event_type: String,
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    /// `pending`, `delivered` or `failed` (attempts exhausted)
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    /// HTTP status of the last attempt (absent when the request itself failed)
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+const WEBHOOK_COLS: &str = "id, url, event_types, active, created_at";
+const DELIVERY_COLS: &str = "id, event_type, payload, status, attempts, next_attempt_at, response_status, last_error, created_at, delivered_at";
+
+/// Absolute http(s) URL with a host.
+pub fn valid_url(raw: &str) -> bool {
+    url::Url::parse(raw).map(|u| matches!(u.scheme(), "http" | "https") && u.host_str().is_some()).unwrap_or(false)
+}
+
+pub async fn list_webhooks(pool: &Pool<Postgres>, app_id: uuid::Uuid) -> Result<Vec<Webhook>, sqlx::Error> {
+    sqlx::query_as::<_, Webhook>(&format!("SELECT {WEBHOOK_COLS} FROM webhooks WHERE app_id=$1 ORDER BY created_at"))
+        .bind(app_id).fetch_all(pool).await
+}
+
+/// The signing secret is sealed with the app data key (bound to the webhook id) and only opened for delivery.
+pub async fn create_webhook(pool: &Pool<Postgres>, keyring: &Keyring, app_id: uuid::Uuid, url: &str, secret: &str, event_types: &[String]) -> anyhow::Result<Webhook> {
+    let dk = services::secrets::data_key(pool, keyring, app_id, true).await?.ok_or_else(|| anyhow!("data key unavailable"))?;
+    let id = uuid::Uuid::new_v4();
+    let sealed = secrets::seal(&dk, secret.as_bytes(), &secret_aad(app_id, id))?;
+    let hook = sqlx::query_as::<_, Webhook>(&format!("INSERT INTO webhooks (id, app_id, url, secret_ciphertext, event_types) VALUES ($1,$2,$3,$4,$5) RETURNING {WEBHOOK_COLS}"))
+        .bind(id).bind(app_id).bind(url).bind(&sealed).bind(event_types).fetch_one(pool).await?;
+    Ok(hook)
+}
+
+fn secret_aad(app_id: uuid::Uuid, id: uuid::Uuid) -> Vec<u8> { format!("{app_id}/webhooks/{id}").into_bytes() }
+
+/// Decrypt the signing secret of a webhook.
+async fn open_secret(pool: &Pool<Postgres>, keyring: &Keyring, app_id: uuid::Uuid, id: uuid::Uuid, sealed: &[u8]) -> anyhow::Result<String> {
+    let dk = services::secrets::data_key(pool, keyring, app_id, false).await?.ok_or_else(|| anyhow!("data key missing for app {app_id}"))?;
+    Ok(String::from_utf8(secrets::open(&dk, sealed, &secret_aad(app_id, id))?)?)
+}
+
+/// Returns whether the webhook existed; its queued deliveries and log go with it.
+pub async fn delete_webhook(pool: &Pool<Postgres>, app_id: uuid::Uuid, id: uuid::Uuid) -> Result<bool, sqlx::Error> {
+    let res = sqlx::query("DELETE FROM webhooks WHERE app_id=$1 AND id=$2").bind(app_id).bind(id).execute(pool).await?;
+    Ok(res.rows_affected() > 0)
+}
+
+/// Delivery log of a webhook, newest first; `None` when the webhook does not belong to the app.
+pub async fn list_deliveries(pool: &Pool<Postgres>, app_id: uuid::Uuid, id: uuid::Uuid, limit: i64) -> Result<Option<Vec<WebhookDelivery>>, sqlx::Error> {
+    let exists: Option<i32> = sqlx::query_scalar("SELECT 1 FROM webhooks WHERE app_id=$1 AND id=$2").bind(app_id).bind(id).fetch_optional(pool).await?;
+    if exists.is_none() { return Ok(None); }
+    sqlx::query_as::<_, WebhookDelivery>(&format!("SELECT {DELIVERY_COLS} FROM webhook_deliveries WHERE webhook_id=$1 ORDER BY created_at DESC LIMIT $2"))
+        .bind(id).bind(limit).fetch_all(pool).await.map(Some)
+}
+
+/// Queue `event_type` for every active webhook of the app subscribed to it; returns the number of deliveries queued.
+/// The payload is `{"event", "app", "occurred_at", "data"}`.
+pub async fn enqueue<'c>(ex: impl PgExecutor<'c>, app_id: uuid::Uuid, event_type: &str, data: serde_json::Value) -> Result<u64, sqlx::Error> {
+    let res = sqlx::query("INSERT INTO webhook_deliveries (webhook_id, event_type, payload)
+        SELECT w.id, $2, jsonb_build_object('event', $2::text, 'app', a.name, 'occurred_at', now(), 'data', $3::jsonb)
+        FROM webhooks w JOIN applications a ON a.id=w.app_id
+        WHERE w.app_id=$1 AND w.active AND (cardinality(w.event_types)=0 OR $2 = ANY(w.event_types))")
+        .bind(app_id).bind(event_type).bind(data).execute(ex).await?;
+    Ok(res.rows_affected())
+}
+
+/// Queue `deployment.<status>` for a deployment that just changed status.
+pub async fn deployment_event(pool: &Pool<Postgres>, id: uuid::Uuid, status: &str) {
+    let res = async {
+        let row = sqlx::query("SELECT app_id, artifact_url, digest, failure_reason FROM deployments WHERE id=$1").bind(id).fetch_one(pool).await?;
+        let data = serde_json::json!({
+            "deployment_id": id,
+            "status": status,
+            "artifact_url": row.get::<String, _>("artifact_url"),
+            "digest": row.get::<Option<String>, _>("digest"),
+            "failure_reason": row.get::<Option<String>, _>("failure_reason"),
+        });
+        enqueue(pool, row.get("app_id"), &format!("deployment.{status}"), data).await
+    }.await;
+    if let Err(e) = res { tracing::warn!(error=%e, deployment_id=%id, status, "webhook enqueue failed"); }
+}
+
+/// Queue `artifact.<event_type>` for an artifact event (artifacts without an app have no webhooks).
+pub async fn artifact_event(conn: &mut sqlx::PgConnection, artifact_id: uuid::Uuid, event_type: &str) {
+    let res = async {
+        let row = sqlx::query("SELECT app_id, digest, size_bytes FROM artifacts WHERE id=$1").bind(artifact_id).fetch_optional(&mut *conn).await?;
+        let Some(row) = row else { return Ok(0) };
+        let Some(app_id) = row.get::<Option<uuid::Uuid>, _>("app_id") else { return Ok(0) };
+        let data = serde_json::json!({ "artifact_id": artifact_id, "digest": row.get::<String, _>("digest"), "size_bytes": row.get::<i64, _>("size_bytes") });
+        enqueue(&mut *conn, app_id, &format!("artifact.{event_type}"), data).await
+    }.await;
+    if let Err(e) = res { tracing::warn!(error=%e, artifact_id=%artifact_id, event_type, "webhook enqueue failed"); }
+}
+
+/// `sha256=<hex>` signature of a request body.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Delay before retrying after `attempts` failed attempts: `AETHER_WEBHOOK_BACKOFF_BASE_SECS` (default 10) doubled
+/// per attempt, capped at `AETHER_WEBHOOK_BACKOFF_MAX_SECS` (default 3600).
+pub fn backoff_secs(attempts: i32) -> i64 {
+    let base = std::env::var("AETHER_WEBHOOK_BACKOFF_BASE_SECS").ok().and_then(|v| v.parse::<i64>().ok()).unwrap_or(10).max(1);
+    let cap = std::env::var("AETHER_WEBHOOK_BACKOFF_MAX_SECS").ok().and_then(|v| v.parse::<i64>().ok()).unwrap_or(3600).max(base);
+    base.saturating_mul(1i64 << (attempts - 1).clamp(0, 20)).min(cap)
+}
+
+fn max_attempts() -> i32 {
+    std::env::var("AETHER_WEBHOOK_MAX_ATTEMPTS").ok().and_then(|v| v.parse::<i32>().ok()).unwrap_or(8).max(1)
+}
+
+/// HTTP client used for deliveries (`AETHER_WEBHOOK_TIMEOUT_SECS`, default 10).
+pub fn http_client() -> reqwest::Client {
+    let timeout = std::env::var("AETHER_WEBHOOK_TIMEOUT_SECS").ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(10);
+    reqwest::Client::builder().timeout(std::time::Duration::from_secs(timeout.max(1))).build().unwrap_or_default()
+}
+
+/// Send a batch of due deliveries. Claimed rows are leased (pushed into the future) so concurrent workers skip them
+/// while the request is in flight; the outcome then marks them delivered, schedules a retry or fails them for good.
+/// Returns the number of deliveries attempted.
+pub async fn deliver_due(pool: &Pool<Postgres>, client: &reqwest::Client) -> anyhow::Result<usize> {
+    let rows = sqlx::query("UPDATE webhook_deliveries d SET next_attempt_at = now() + interval '5 minutes'
+        FROM webhooks w
+        WHERE w.id = d.webhook_id AND w.active AND d.id IN (
+            SELECT id FROM webhook_deliveries WHERE status='pending' AND next_attempt_at <= now()
+            ORDER BY next_attempt_at LIMIT 32 FOR UPDATE SKIP LOCKED)
+        RETURNING d.id, d.event_type, d.payload, d.attempts, w.id AS webhook_id, w.app_id, w.url, w.secret_ciphertext")
+        .fetch_all(pool).await?;
+    if rows.is_empty() { return Ok(0); }
+    // Without the keyring nothing can be signed; the claimed rows are retried once their lease expires.
+    let keyring = Keyring::from_env()?.ok_or_else(|| anyhow!("webhooks pending but AETHER_MASTER_KEY_FILE is not configured"))?;
+    let max = max_attempts();
+    for row in &rows {
+        let id: uuid::Uuid = row.get("id");
+        let event_type: String = row.get("event_type");
+        let body = serde_json::to_vec(&row.get::<serde_json::Value, _>("payload"))?;
+        let url: String = row.get("url");
+        let attempts = row.get::<i32, _>("attempts") + 1;
+        let secret = open_secret(pool, &keyring, row.get("app_id"), row.get("webhook_id"), &row.get::<Vec<u8>, _>("secret_ciphertext")).await;
+        let (code, error) = match secret {
+            Ok(secret) => {
+                let res = client.post(&url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .header("X-Aether-Event", &event_type)
+                    .header("X-Aether-Delivery", id.to_string())
+                    .header(SIGNATURE_HEADER, sign(&secret, &body))
+                    .body(body)
+                    .send().await;
+                match res {
+                    Ok(r) if r.status().is_success() => (Some(r.status().as_u16() as i32), None),
+                    Ok(r) => (Some(r.status().as_u16() as i32), Some(format!("HTTP {}", r.status()))),
+                    Err(e) => (None, Some(e.to_string())),
+                }
+            }
+            Err(e) => (None, Some(format!("open secret: {e}"))),
+        };
+        let outcome = match &error {
+            None => "delivered",
+            Some(_) if attempts >= max => "failed",
+            Some(_) => "retry",
+        };
+        let status = if outcome == "retry" { "pending" } else { outcome };
+        sqlx::query("UPDATE webhook_deliveries SET status=$2, attempts=$3, response_status=$4, last_error=$5,
+                next_attempt_at = now() + ($6::bigint * interval '1 second'),
+                delivered_at = CASE WHEN $2='delivered' THEN now() ELSE NULL END
+            WHERE id=$1")
+            .bind(id).bind(status).bind(attempts).bind(code).bind(&error).bind(backoff_secs(attempts))
+            .execute(pool).await?;
+        crate::telemetry::WEBHOOK_DELIVERIES.with_label_values(&[outcome]).inc();
+        if let Some(e) = &error { tracing::warn!(delivery_id=%id, %url, event_type, attempts, error=%e, outcome, "webhook delivery failed"); }
+    }
+    Ok(rows.len())
+}
@@ -3,6 +3,58 @@ pub static DEPLOYMENT_STATUS: Lazy<IntCounterVec> = Lazy::new(|| {
     REGISTRY.register(Box::new(c.clone())).ok();
     c
 });
+/// Failed deployments by reason: `crash_loop`, `image_pull`, `oom_killed`, `checksum_mismatch`, `init_failed`,
+/// `progress_deadline`, `timeout`, `release_failed`, `apply_failed` or `other` (see `services::deployments::failure_label`).
+pub static DEPLOYMENT_FAILURES: Lazy<IntCounterVec> = Lazy::new(|| {
+    let c = IntCounterVec::new(opts!("deployment_failures_total", "Failed deployments by failure reason"), &["reason"]).unwrap();
+    REGISTRY.register(Box::new(c.clone())).ok();
+    c
+});
+/// Automatic rollbacks by outcome: `triggered`, `loop_guard` (the failed deployment was itself an automatic rollback),
+/// `superseded` (a newer deployment exists) or `no_target` (no earlier running release).
+pub static DEPLOYMENT_AUTO_ROLLBACKS: Lazy<IntCounterVec> = Lazy::new(|| {
+    let c = IntCounterVec::new(opts!("deployment_auto_rollbacks_total", "Automatic rollbacks after failed deployments"), &["outcome"]).unwrap();
+    REGISTRY.register(Box::new(c.clone())).ok();
+    c
+});
+/// Webhook delivery attempts by outcome: `delivered`, `retry` (scheduled again with backoff) or `failed` (attempts exhausted).
+pub static WEBHOOK_DELIVERIES: Lazy<IntCounterVec> = Lazy::new(|| {
+    let c = IntCounterVec::new(opts!("webhook_deliveries_total", "Outbound webhook delivery attempts"), &["outcome"]).unwrap();
+    REGISTRY.register(Box::new(c.clone())).ok();
+    c
+});
+/// Kubernetes apply attempts by outcome: `applied`, `skipped` (deployment no longer pending or its release phase
+/// failed), `retry` (scheduled again with backoff) or `failed` (attempts exhausted, deployment failed).
+pub static APPLY_ATTEMPTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    let c = IntCounterVec::new(opts!("apply_queue_attempts_total", "Kubernetes apply attempts from the apply queue"), &["outcome"]).unwrap();
+    REGISTRY.register(Box::new(c.clone())).ok();
+    c
+});
+/// Applies waiting in the queue (including scheduled retries).
+pub static APPLY_QUEUE_DEPTH: Lazy<IntGauge> = Lazy::new(|| {
+    let g = IntGauge::new("apply_queue_depth", "Pending entries in the Kubernetes apply queue").unwrap();
+    REGISTRY.register(Box::new(g.clone())).ok();
+    g
+});
+/// Drift found by the desired-state reconciler, by field: `missing` (Deployment deleted), `digest`, `image` or `env`.
+pub static RECONCILE_DRIFT: Lazy<IntCounterVec> = Lazy::new(|| {
+    let c = IntCounterVec::new(opts!("reconcile_drift_total", "Workload drift detected by the reconciler"), &["field"]).unwrap();
+    REGISTRY.register(Box::new(c.clone())).ok();
+    c
+});
+/// Re-applies after drift by outcome: `repaired` or `failed`.
+pub static RECONCILE_REPAIRS: Lazy<IntCounterVec> = Lazy::new(|| {
+    let c = IntCounterVec::new(opts!("reconcile_repairs_total", "Re-applies of drifted releases by the reconciler"), &["outcome"]).unwrap();
+    REGISTRY.register(Box::new(c.clone())).ok();
+    c
+});
+/// Event bus traffic by outcome: `published` (NOTIFY sent), `publish_failed`, `oversized` (payload over the NOTIFY
+/// limit, local only) or `received` (event of another replica re-broadcast).
+pub static EVENT_BUS_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    let c = IntCounterVec::new(opts!("event_bus_messages_total", "Event bus messages across replicas"), &["outcome"]).unwrap();
+    REGISTRY.register(Box::new(c.clone())).ok();
+    c
+});
 pub static DEPLOYMENT_TIME_TO_RUNNING: Lazy<prometheus::Histogram> = Lazy::new(|| {
     let h = prometheus::Histogram::with_opts(histogram_opts!("deployment_time_to_running_seconds", "Time from creation to running")).unwrap();
     REGISTRY.register(Box::new(h.clone())).ok();
@@ -65,6 +117,7 @@ pub fn normalize_path(raw: &str) -> String {
     // Broader normalization:
     // - Replace UUID segments with :id
     // - Replace purely numeric segments with :id
+    // - Replace sha256 digest segments with :digest
     // - Special-case app name position in /apps/{app}/...
     let uuid_like = |s: &str| s.len() == 36 && s.chars().filter(|c| *c == '-').count() == 4;
     let mut parts: Vec<String> = raw.split('/')
@@ -72,6 +125,7 @@ pub fn normalize_path(raw: &str) -> String {
         .map(|seg| {
             if seg.is_empty() { return seg.to_string(); }
             if seg.chars().all(|c| c.is_ascii_digit()) || uuid_like(seg) { return ":id".to_string(); }
+            if seg.len() == 64 && seg.chars().all(|c| c.is_ascii_hexdigit()) { return ":digest".to_string(); }
             seg.to_string()
         }).collect();
     if parts.len() >= 2 && parts[0] == "apps" { parts[1] = ":app_name".into(); }
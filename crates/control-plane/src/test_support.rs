@@ -13,6 +13,10 @@ static TEST_DB_URL_ENV: &str = "DATABASE_URL";
 async fn shared_pool() -> Pool<Postgres> {
     // Ensure tests never attempt live Kubernetes calls
     std::env::set_var("AETHER_DISABLE_K8S","1");
+    // Keep uploaded test artifacts out of the source tree
+    if std::env::var("ARTIFACT_STORE_DIR").is_err() {
+        std::env::set_var("ARTIFACT_STORE_DIR", std::env::temp_dir().join(format!("aether-test-artifacts-{}", std::process::id())));
+    }
     // Fast path: optional sqlite for tests (AETHER_USE_SQLITE=1) to avoid heavy Postgres setup in constrained CI
     if std::env::var("AETHER_USE_SQLITE").ok().as_deref()==Some("1") {
         // Build ephemeral in-memory schema using separate sqlite pool stored globally via OnceCell
@@ -118,6 +122,21 @@ pub async fn test_state() -> AppState {
     AppState { db: pool }
 }
 
+/// Send a request through the router (JSON body when given) and return the status with the parsed JSON response
+/// (`Null` when the body is not JSON).
+pub async fn call(app: &axum::Router, method: &str, uri: &str, body: Option<serde_json::Value>) -> (axum::http::StatusCode, serde_json::Value) {
+    use tower::util::ServiceExt;
+    let mut req = axum::http::Request::builder().method(method).uri(uri);
+    let body = match body {
+        Some(b) => { req = req.header("content-type","application/json"); axum::body::Body::from(b.to_string()) }
+        None => axum::body::Body::empty(),
+    };
+    let res = app.clone().oneshot(req.body(body).unwrap()).await.unwrap();
+    let status = res.status();
+    let bytes = axum::body::to_bytes(res.into_body(), 1024 * 64).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
 /// Ensure the test database exists (idempotent best-effort).
 async fn ensure_database(url: &str) {
     use url::Url;
@@ -0,0 +1,60 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+#[tokio::test]
+#[serial_test::serial]
+async fn config_set_list_unset_and_rollout() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('cfgapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+
+    // nothing deployed yet: values stored, no rollout
+    let (status, v) = call(&app, "PUT", "/apps/cfgapp/config", Some(serde_json::json!({"values": {"DATABASE_URL":"postgres://db/app"}}))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert!(v["rollout_deployment_id"].is_null());
+
+    sqlx::query("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://rel1','running')").bind(app_id).execute(&pool).await.unwrap();
+    let (status, v) = call(&app, "PUT", "/apps/cfgapp/config", Some(serde_json::json!({"values": {"LOG_LEVEL":"debug"}}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["values"]["DATABASE_URL"], "postgres://db/app");
+    assert_eq!(v["values"]["LOG_LEVEL"], "debug");
+    let rollout: uuid::Uuid = v["rollout_deployment_id"].as_str().expect("rollout expected").parse().unwrap();
+    let (artifact_url, ev): (String, String) = sqlx::query_as("SELECT d.artifact_url, e.event_type FROM deployments d JOIN deployment_events e ON e.deployment_id=d.id WHERE d.id=$1")
+        .bind(rollout).fetch_one(&pool).await.unwrap();
+    assert_eq!(artifact_url, "file://rel1");
+    assert_eq!(ev, "config_changed");
+
+    // identical value is a no-op
+    let (_, v) = call(&app, "PUT", "/apps/cfgapp/config", Some(serde_json::json!({"values": {"LOG_LEVEL":"debug"}}))).await;
+    assert!(v["rollout_deployment_id"].is_null());
+
+    // config is rendered into the deploy spec env
+    let dep = services::deployments::get_deployment(&pool, rollout).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "cfgapp", &dep, false).await.unwrap();
+    assert_eq!(spec.env, vec![("DATABASE_URL".to_string(), "postgres://db/app".to_string()), ("LOG_LEVEL".to_string(), "debug".to_string())]);
+
+    let (status, v) = call(&app, "DELETE", "/apps/cfgapp/config?keys=LOG_LEVEL,MISSING", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(v["values"].get("LOG_LEVEL").is_none());
+    assert!(v["rollout_deployment_id"].is_string());
+    let (status, v) = call(&app, "GET", "/apps/cfgapp/config", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["values"].as_object().unwrap().len(), 1);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn config_validation() {
+    let state = test_state().await;
+    sqlx::query("INSERT INTO applications (name) VALUES ('cfgapp2')").execute(&state.db).await.unwrap();
+    let app = build_router(state);
+    for bad in ["1ABC", "WITH-DASH", "AETHER_DIGEST"] {
+        let (status, _) = call(&app, "PUT", "/apps/cfgapp2/config", Some(serde_json::json!({"values": {bad: "x"}}))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{bad} should be rejected");
+    }
+    let (status, _) = call(&app, "DELETE", "/apps/cfgapp2/config", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = call(&app, "GET", "/apps/missing/config", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,66 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+#[tokio::test]
+#[serial_test::serial]
+async fn domains_crud_and_routes_in_spec() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('webapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    sqlx::query("INSERT INTO applications (name) VALUES ('otherapp')").execute(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let (status, v) = call(&app, "POST", "/apps/webapp/domains", Some(serde_json::json!({"hostname": "Shop.Example.com"}))).await;
+    assert_eq!(status, StatusCode::CREATED, "{v}");
+    assert_eq!(v["hostname"], "shop.example.com");
+    assert_eq!(v["path"], "/");
+    let (status, _) = call(&app, "POST", "/apps/webapp/domains", Some(serde_json::json!({"hostname": "shop.example.com", "path": "/api"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    // same host + path cannot be routed to two apps
+    let (status, _) = call(&app, "POST", "/apps/otherapp/domains", Some(serde_json::json!({"hostname": "shop.example.com", "path": "/api"}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    for bad in [serde_json::json!({"hostname": "localhost"}), serde_json::json!({"hostname": "-bad.example.com"}), serde_json::json!({"hostname": "ok.example.com", "path": "api"})] {
+        let (status, _) = call(&app, "POST", "/apps/webapp/domains", Some(bad)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    let (status, v) = call(&app, "GET", "/apps/webapp/domains", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["domains"].as_array().unwrap().len(), 2);
+
+    let routes = services::domains::routes_for(&pool, app_id, "webapp").await.unwrap();
+    assert!(routes.iter().any(|r| r.host == "shop.example.com" && r.path == "/api"));
+
+    let (status, _) = call(&app, "DELETE", "/apps/webapp/domains/shop.example.com", None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = call(&app, "DELETE", "/apps/webapp/domains/shop.example.com", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = call(&app, "GET", "/apps/missing/domains", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn port_change_rolls_out_current_release() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('portapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let (_, v) = call(&app, "GET", "/apps/portapp", None).await;
+    assert!(v["settings"]["port"].is_null(), "runtime default port");
+    let (status, v) = call(&app, "PATCH", "/apps/portapp", Some(serde_json::json!({"port": 8080}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["settings"]["port"], 8080);
+    assert!(v.get("rollout_deployment_id").is_none(), "nothing deployed yet");
+
+    sqlx::query("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://a','running')").bind(app_id).execute(&pool).await.unwrap();
+    let (_, v) = call(&app, "PATCH", "/apps/portapp", Some(serde_json::json!({"port": 9090}))).await;
+    let new_id: uuid::Uuid = v["rollout_deployment_id"].as_str().unwrap().parse().unwrap();
+    let dep = services::deployments::get_deployment(&pool, new_id).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "portapp", &dep, false).await.unwrap();
+    assert_eq!(spec.port, Some(9090));
+
+    let (status, _) = call(&app, "PATCH", "/apps/portapp", Some(serde_json::json!({"port": 70000}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "mock-kube")]
+use control_plane::{build_router, test_support::test_state};
+use axum::{body::Body, http::{Request, StatusCode}};
+use tower::util::ServiceExt;
+
+async fn get_logs(app: &axum::Router, uri: &str, accept: Option<&str>) -> (StatusCode, String, String) {
+    let mut req = Request::builder().uri(uri);
+    if let Some(a) = accept { req = req.header("accept", a); }
+    let res = app.clone().oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+    let status = res.status();
+    let ct = res.headers().get("content-type").and_then(|v| v.to_str().ok()).unwrap_or("").to_string();
+    let body = axum::body::to_bytes(res.into_body(), 1024 * 1024).await.unwrap();
+    (status, ct, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn mock_kube_logs_stream_text_and_sse() {
+    let state = test_state().await;
+    sqlx::query("INSERT INTO applications (name) VALUES ($1)").bind("logapp").execute(&state.db).await.unwrap();
+    let app = build_router(state);
+    let body = serde_json::json!({"app_name":"logapp","artifact_url":"file://artifact"}).to_string();
+    let res = app.clone().oneshot(Request::builder().method("POST").uri("/deployments")
+        .header("content-type","application/json").body(Body::from(body)).unwrap()).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CREATED);
+    // apply runs in a spawned task; wait until the mock cluster knows the app
+    let mut text = String::new();
+    for _ in 0..50 {
+        let (status, ct, b) = get_logs(&app, "/apps/logapp/logs?tail=3", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(ct.starts_with("text/plain"));
+        if !b.is_empty() { text = b; break; }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(text.lines().count(), 3, "tail=3 should yield 3 lines: {text}");
+    assert!(text.lines().all(|l| l.contains("pod=logapp-mock") && l.contains("container=app")));
+    let (status, ct, sse) = get_logs(&app, "/apps/logapp/logs?tail=2&container=fetcher", Some("text/event-stream")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(ct.starts_with("text/event-stream"));
+    assert_eq!(sse.matches("event: log").count(), 2);
+    assert!(sse.contains("container=fetcher"));
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn mock_kube_logs_bad_since_rejected() {
+    let state = test_state().await;
+    let app = build_router(state);
+    let (status, _, _) = get_logs(&app, "/apps/logapp/logs?since=yesterday", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn mock_kube_logs_use_app_namespace() {
+    let state = test_state().await;
+    sqlx::query("INSERT INTO applications (name, namespace) VALUES ($1,$2)").bind("nslogapp").bind("team-a").execute(&state.db).await.unwrap();
+    let app = build_router(state);
+    let body = serde_json::json!({"app_name":"nslogapp","artifact_url":"file://artifact"}).to_string();
+    let res = app.clone().oneshot(Request::builder().method("POST").uri("/deployments")
+        .header("content-type","application/json").body(Body::from(body)).unwrap()).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CREATED);
+    // the mock cluster only serves logs for (namespace, app) pairs that were applied
+    let mut found = false;
+    for _ in 0..50 {
+        let (_, _, b) = get_logs(&app, "/apps/nslogapp/logs?tail=1", None).await;
+        if !b.is_empty() { found = true; break; }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(found, "apply into namespace team-a should make logs available");
+}
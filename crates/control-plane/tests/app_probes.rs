@@ -0,0 +1,51 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+#[tokio::test]
+#[serial_test::serial]
+async fn probe_settings_validated_and_rolled_out() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('probeapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    sqlx::query("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://a','running')").bind(app_id).execute(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let (_, v) = call(&app, "GET", "/apps/probeapp", None).await;
+    assert_eq!(v["settings"]["probes"], serde_json::json!({}));
+    assert!(v["settings"]["termination_grace_period_seconds"].is_null());
+
+    for bad in [
+        serde_json::json!({"probes": {"readiness": {"http_path": "healthz"}}}),
+        serde_json::json!({"probes": {"liveness": {"success_threshold": 2}}}),
+        serde_json::json!({"probes": {"startup": {"period_seconds": 0}}}),
+        serde_json::json!({"termination_grace_period_seconds": 5, "pre_stop_sleep_seconds": 10}),
+    ] {
+        let (status, v) = call(&app, "PATCH", "/apps/probeapp", Some(bad.clone())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{bad} -> {v}");
+    }
+
+    let body = serde_json::json!({
+        "probes": {"readiness": {"http_path": "/ready", "failure_threshold": 5}, "liveness": {"enabled": false}},
+        "termination_grace_period_seconds": 45
+    });
+    let (status, v) = call(&app, "PATCH", "/apps/probeapp", Some(body.clone())).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert_eq!(v["settings"]["probes"]["readiness"]["http_path"], "/ready");
+    assert_eq!(v["settings"]["termination_grace_period_seconds"], 45);
+    let new_id: uuid::Uuid = v["rollout_deployment_id"].as_str().unwrap().parse().unwrap();
+    let msg: Option<String> = sqlx::query_scalar("SELECT message FROM deployment_events WHERE deployment_id=$1 AND event_type='settings_changed'")
+        .bind(new_id).fetch_one(&pool).await.unwrap();
+    assert_eq!(msg.as_deref(), Some("probes,termination"));
+    let dep = services::deployments::get_deployment(&pool, new_id).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "probeapp", &dep, false).await.unwrap();
+    assert_eq!(spec.probes.readiness.unwrap().failure_threshold, Some(5));
+    assert_eq!(spec.termination_grace_period_seconds, Some(45));
+
+    // re-sending identical settings is a no-op
+    let (_, v) = call(&app, "PATCH", "/apps/probeapp", Some(body)).await;
+    assert!(v.get("rollout_deployment_id").is_none());
+    // null restores the runtime default
+    let (_, v) = call(&app, "PATCH", "/apps/probeapp", Some(serde_json::json!({"termination_grace_period_seconds": null, "probes": {}}))).await;
+    assert!(v["settings"]["termination_grace_period_seconds"].is_null());
+    assert_eq!(v["settings"]["probes"], serde_json::json!({}));
+}
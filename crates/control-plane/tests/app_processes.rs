@@ -0,0 +1,60 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+const DIGEST: &str = "efefefefefefefefefefefefefefefefefefefefefefefefefefefefefefefef";
+
+#[tokio::test]
+#[serial_test::serial]
+async fn procfile_process_types_render_and_track_separately() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('procapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let (status, v) = call(&app, "POST", "/artifacts/complete", Some(serde_json::json!({
+        "app_name": "procapp", "digest": DIGEST, "size_bytes": 0,
+        "metadata": {"processes": {"web": "node server.js", "worker": "node worker.js", "clock": "node clock.js", "release": "node migrate.js", "Bad_Type": "x"}}
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    let dep_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status, digest) VALUES ($1,'file://a','pending',$2) RETURNING id")
+        .bind(app_id).bind(DIGEST).fetch_one(&pool).await.unwrap();
+    let dep = services::deployments::get_deployment(&pool, dep_id).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "procapp", &dep, false).await.unwrap();
+    assert_eq!(spec.command.as_ref().unwrap()[2], "exec node server.js");
+    let names: Vec<&str> = spec.processes.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["clock", "worker"], "web is the main Deployment; release and invalid names are not long-running processes");
+    assert!(spec.processes.iter().all(|p| p.replicas == 1));
+    assert_eq!(spec.processes[1].command[2], "exec node worker.js");
+
+    // per-process scaling, stored for the next rollout
+    let (status, v) = call(&app, "PUT", "/apps/procapp/scale", Some(serde_json::json!({"replicas": 3, "process": "worker"}))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert_eq!(v["process"], "worker");
+    assert_eq!(v["live_patched"], false);
+    for bad in ["release", "Worker", ""] {
+        let (status, _) = call(&app, "PUT", "/apps/procapp/scale", Some(serde_json::json!({"replicas": 1, "process": bad}))).await;
+        assert_eq!(status, if bad.is_empty() { StatusCode::OK } else { StatusCode::BAD_REQUEST }, "process {bad:?}");
+    }
+    call(&app, "PUT", "/apps/procapp/scale", Some(serde_json::json!({"replicas": 0, "process": "clock"}))).await;
+    let spec = services::deployments::build_spec(&pool, "procapp", &dep, false).await.unwrap();
+    assert_eq!(spec.replicas, Some(1), "empty process scales web");
+    assert_eq!(spec.processes.iter().map(|p| (p.name.as_str(), p.replicas)).collect::<Vec<_>>(), [("clock", 0), ("worker", 3)]);
+
+    // one release, three Kubernetes objects: running only once every process is ready
+    services::processes::record_processes(&pool, dep_id, &spec).await.unwrap();
+    let (status, v) = call(&app, "GET", "/apps/procapp/processes", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let objects: Vec<&str> = v.as_array().unwrap().iter().map(|p| p["object_name"].as_str().unwrap()).collect();
+    assert_eq!(objects, ["procapp-clock", "procapp", "procapp-worker"]);
+    assert_eq!(services::processes::observe(&pool, dep_id, "web", 1, 1).await.unwrap(), Some(false));
+    assert_eq!(services::processes::observe(&pool, dep_id, "clock", 0, 0).await.unwrap(), Some(false));
+    assert_eq!(services::processes::observe(&pool, dep_id, "worker", 3, 1).await.unwrap(), Some(true));
+    let (_, v) = call(&app, "GET", "/apps/procapp/processes", None).await;
+    assert_eq!(v[2]["available_replicas"], 1);
+    assert!(v.as_array().unwrap().iter().all(|p| p["ready"] == true));
+
+    // releases recorded before process tracking report no processes
+    let legacy: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://b','pending') RETURNING id")
+        .bind(app_id).fetch_one(&pool).await.unwrap();
+    assert_eq!(services::processes::observe(&pool, legacy, "web", 1, 1).await.unwrap(), None);
+}
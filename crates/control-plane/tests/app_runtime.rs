@@ -0,0 +1,44 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+const DIGEST: &str = "abababababababababababababababababababababababababababababababab";
+
+#[tokio::test]
+#[serial_test::serial]
+async fn runtime_detected_from_sbom_unless_pinned() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('pyapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let (status, v) = call(&app, "POST", "/artifacts/complete", Some(serde_json::json!({
+        "app_name": "pyapp", "digest": DIGEST, "size_bytes": 0,
+        "sbom": {"schema": "aether-sbom-v1", "ecosystem": "python", "dependencies": []}
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    let dep_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status, digest) VALUES ($1,'file://a','running',$2) RETURNING id")
+        .bind(app_id).bind(DIGEST).fetch_one(&pool).await.unwrap();
+    let dep = services::deployments::get_deployment(&pool, dep_id).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "pyapp", &dep, false).await.unwrap();
+    assert_eq!(spec.runtime.unwrap().id, "python3.12");
+
+    let (status, _) = call(&app, "PATCH", "/apps/pyapp", Some(serde_json::json!({"runtime": "ruby3"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, v) = call(&app, "PATCH", "/apps/pyapp", Some(serde_json::json!({"runtime": "static"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["settings"]["runtime"], "static");
+    assert!(v["rollout_deployment_id"].is_string(), "runtime change rolls out");
+    let spec = services::deployments::build_spec(&pool, "pyapp", &dep, false).await.unwrap();
+    assert_eq!(spec.runtime.unwrap().id, "static");
+
+    // unpinning returns to detection; no SBOM at all falls back to the platform default
+    call(&app, "PATCH", "/apps/pyapp", Some(serde_json::json!({"runtime": null}))).await;
+    sqlx::query("UPDATE artifacts SET sbom=NULL WHERE digest=$1").bind(DIGEST).execute(&pool).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "pyapp", &dep, false).await.unwrap();
+    assert_eq!(spec.runtime.unwrap().id, "node20");
+
+    let (status, v) = call(&app, "GET", "/runtimes", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<&str> = v.as_array().unwrap().iter().map(|r| r["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["node18", "node20", "node22", "python3.12", "static"]);
+}
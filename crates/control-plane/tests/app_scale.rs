@@ -0,0 +1,84 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+#[tokio::test]
+#[serial_test::serial]
+async fn scale_persists_and_validates_max() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('scaleapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let (status, v) = call(&app, "GET", "/apps/scaleapp", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["settings"]["replicas"], 1);
+    assert!(v["settings"]["max_replicas"].is_null());
+
+    let (status, v) = call(&app, "PUT", "/apps/scaleapp/scale", Some(serde_json::json!({"replicas": 3}))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert_eq!(v["replicas"], 3);
+    assert_eq!(v["live_patched"], false, "no live Deployment yet");
+
+    // persisted count flows into the next rollout
+    let dep_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://a','running') RETURNING id")
+        .bind(app_id).fetch_one(&pool).await.unwrap();
+    let dep = services::deployments::get_deployment(&pool, dep_id).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "scaleapp", &dep, false).await.unwrap();
+    assert_eq!(spec.replicas, Some(3));
+
+    // per-app cap
+    let (status, _) = call(&app, "PATCH", "/apps/scaleapp", Some(serde_json::json!({"max_replicas": 2}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "cap below current replicas is rejected");
+    call(&app, "PUT", "/apps/scaleapp/scale", Some(serde_json::json!({"replicas": 2}))).await;
+    let (status, v) = call(&app, "PATCH", "/apps/scaleapp", Some(serde_json::json!({"max_replicas": 2}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["effective_max_replicas"], 2);
+    let (status, _) = call(&app, "PUT", "/apps/scaleapp/scale", Some(serde_json::json!({"replicas": 5}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = call(&app, "PUT", "/apps/scaleapp/scale", Some(serde_json::json!({"replicas": -1}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    // clearing the cap falls back to the platform default
+    let (_, v) = call(&app, "PATCH", "/apps/scaleapp", Some(serde_json::json!({"max_replicas": null}))).await;
+    assert!(v["settings"]["max_replicas"].is_null());
+    assert_eq!(v["effective_max_replicas"], services::apps::default_max_replicas());
+
+    let events: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM deployment_events WHERE deployment_id=$1 AND event_type='scaled'")
+        .bind(dep_id).fetch_one(&pool).await.unwrap();
+    assert_eq!(events, 1);
+
+    // the cap covers every process type, and a rejected update leaves the other fields alone
+    call(&app, "PUT", "/apps/scaleapp/scale", Some(serde_json::json!({"replicas": 4, "process": "worker"}))).await;
+    let (status, v) = call(&app, "PATCH", "/apps/scaleapp", Some(serde_json::json!({"max_replicas": 3, "auto_rollback": true}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+    let settings = services::apps::get_settings(&pool, app_id).await.unwrap();
+    assert_eq!((settings.max_replicas, settings.auto_rollback), (None, false));
+
+    let (status, _) = call(&app, "PUT", "/apps/missing/scale", Some(serde_json::json!({"replicas": 1}))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[cfg(feature = "mock-kube")]
+#[tokio::test]
+#[serial_test::serial]
+async fn scale_patches_live_deployment() {
+    let state = test_state().await;
+    sqlx::query("INSERT INTO applications (name) VALUES ('livescale')").execute(&state.db).await.unwrap();
+    let app = build_router(state);
+    let (status, _) = call(&app, "POST", "/deployments", Some(serde_json::json!({"app_name":"livescale","artifact_url":"file://artifact"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let mut patched = false;
+    for _ in 0..50 {
+        let (_, v) = call(&app, "PUT", "/apps/livescale/scale", Some(serde_json::json!({"replicas": 2}))).await;
+        if v["live_patched"] == true { patched = true; break; }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(patched);
+
+    // a failed patch keeps the stored count in line with the cluster
+    control_plane::k8s::mock_fail_scale(true);
+    let (status, _) = call(&app, "PUT", "/apps/livescale/scale", Some(serde_json::json!({"replicas": 4}))).await;
+    control_plane::k8s::mock_fail_scale(false);
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    let (_, v) = call(&app, "GET", "/apps/livescale", None).await;
+    assert_eq!(v["settings"]["replicas"], 2);
+}
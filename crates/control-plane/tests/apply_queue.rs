@@ -0,0 +1,65 @@
+use control_plane::{test_support::test_state, services, telemetry};
+use sqlx::Row;
+
+async fn queue_row(pool: &sqlx::PgPool, id: uuid::Uuid) -> sqlx::postgres::PgRow {
+    sqlx::query("SELECT status, attempts, last_error, release_done, next_attempt_at > now() AS scheduled FROM deployment_apply_queue WHERE deployment_id=$1")
+        .bind(id).fetch_one(pool).await.unwrap()
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn queued_apply_completes() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name) VALUES ('queued')").execute(&pool).await.unwrap();
+    let dep = services::deployments::create_deployment(&pool, "queued", "file://q", None, None).await.unwrap();
+    services::apply_queue::enqueue(&pool, dep.id, false).await.unwrap();
+    services::apply_queue::enqueue(&pool, dep.id, false).await.unwrap();
+    let n: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM deployment_apply_queue WHERE deployment_id=$1").bind(dep.id).fetch_one(&pool).await.unwrap();
+    assert_eq!(n, 1, "enqueue is idempotent per deployment");
+
+    assert_eq!(services::apply_queue::process_due(&pool).await.unwrap(), 1);
+    let row = queue_row(&pool, dep.id).await;
+    assert_eq!(row.get::<String, _>("status"), "done");
+    assert_eq!(row.get::<i32, _>("attempts"), 1);
+    assert!(row.get::<bool, _>("release_done"));
+    assert_eq!(services::apply_queue::process_due(&pool).await.unwrap(), 0);
+    assert_eq!(telemetry::APPLY_QUEUE_DEPTH.get(), 0);
+    // the watcher moves it on, not the apply
+    assert_eq!(services::deployments::get_deployment(&pool, dep.id).await.unwrap().status, "pending");
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn exhausted_apply_fails_deployment() {
+    std::env::set_var("AETHER_APPLY_MAX_ATTEMPTS", "2");
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name, runtime) VALUES ('broken', 'nope')").execute(&pool).await.unwrap();
+    let dep = services::deployments::create_deployment(&pool, "broken", "file://b", None, None).await.unwrap();
+    services::apply_queue::enqueue(&pool, dep.id, false).await.unwrap();
+
+    let retries_before = telemetry::APPLY_ATTEMPTS.with_label_values(&["retry"]).get();
+    assert_eq!(services::apply_queue::process_due(&pool).await.unwrap(), 1);
+    let row = queue_row(&pool, dep.id).await;
+    assert_eq!(row.get::<String, _>("status"), "pending");
+    assert_eq!(row.get::<i32, _>("attempts"), 1);
+    assert!(row.get::<bool, _>("scheduled"), "retry is backed off");
+    assert!(row.get::<Option<String>, _>("last_error").unwrap().contains("unknown runtime 'nope'"));
+    assert_eq!(telemetry::APPLY_ATTEMPTS.with_label_values(&["retry"]).get(), retries_before + 1);
+    assert_eq!(telemetry::APPLY_QUEUE_DEPTH.get(), 1);
+    assert_eq!(services::apply_queue::process_due(&pool).await.unwrap(), 0, "not due yet");
+    assert_eq!(services::deployments::get_deployment(&pool, dep.id).await.unwrap().status, "pending");
+
+    sqlx::query("UPDATE deployment_apply_queue SET next_attempt_at=now() WHERE deployment_id=$1").bind(dep.id).execute(&pool).await.unwrap();
+    assert_eq!(services::apply_queue::process_due(&pool).await.unwrap(), 1);
+    std::env::remove_var("AETHER_APPLY_MAX_ATTEMPTS");
+    let row = queue_row(&pool, dep.id).await;
+    assert_eq!(row.get::<String, _>("status"), "failed");
+    assert_eq!(row.get::<i32, _>("attempts"), 2);
+    let failed = services::deployments::get_deployment(&pool, dep.id).await.unwrap();
+    assert_eq!(failed.status, "failed");
+    assert!(failed.failure_reason.unwrap().starts_with("apply_failed:unknown runtime 'nope'"));
+    let events: Vec<String> = sqlx::query_scalar("SELECT event_type FROM deployment_events WHERE deployment_id=$1 ORDER BY id").bind(dep.id).fetch_all(&pool).await.unwrap();
+    assert!(events.contains(&"apply_retry".to_string()) && events.last().map(String::as_str) == Some("failed"), "{events:?}");
+}
@@ -0,0 +1,39 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+const DIGEST: &str = "cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd";
+
+#[tokio::test]
+#[serial_test::serial]
+async fn start_metadata_selects_command_and_node_image() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('nextapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let (status, v) = call(&app, "POST", "/artifacts/complete", Some(serde_json::json!({
+        "app_name": "nextapp", "digest": DIGEST, "size_bytes": 0,
+        "sbom": {"schema": "aether-sbom-v1", "ecosystem": "node", "engines": {"node": "18.x"}},
+        "metadata": {"start_script": "next start", "main": "index.js", "engines_node": ">=22"}
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    let dep_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status, digest) VALUES ($1,'file://a','running',$2) RETURNING id")
+        .bind(app_id).bind(DIGEST).fetch_one(&pool).await.unwrap();
+    let dep = services::deployments::get_deployment(&pool, dep_id).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "nextapp", &dep, false).await.unwrap();
+    assert_eq!(spec.runtime.unwrap().id, "node22", "metadata engines.node wins over the SBOM");
+    let cmd = spec.command.unwrap();
+    assert_eq!(cmd[..2], ["sh", "-c"]);
+    assert!(cmd[2].ends_with("exec next start"), "{cmd:?}");
+
+    // a Procfile web process takes precedence over package.json
+    sqlx::query("UPDATE artifacts SET metadata = metadata || '{\"procfile_web\": \"node cluster.js\"}' WHERE digest=$1").bind(DIGEST).execute(&pool).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "nextapp", &dep, false).await.unwrap();
+    assert_eq!(spec.command.unwrap()[2], "exec node cluster.js");
+
+    // artifacts uploaded without metadata keep the runtime's default command
+    sqlx::query("UPDATE artifacts SET metadata=NULL WHERE digest=$1").bind(DIGEST).execute(&pool).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "nextapp", &dep, false).await.unwrap();
+    assert!(spec.command.is_none());
+    assert_eq!(spec.runtime.unwrap().id, "node18");
+}
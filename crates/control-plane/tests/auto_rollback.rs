@@ -0,0 +1,73 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::{body::Body, http::{Request, StatusCode}};
+use tower::util::ServiceExt;
+
+async fn insert_deployment(pool: &sqlx::PgPool, app_id: uuid::Uuid, url: &str, status: &str, age_secs: i64) -> uuid::Uuid {
+    sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status, created_at) VALUES ($1,$2,$3, now() - ($4::int * interval '1 second')) RETURNING id")
+        .bind(app_id).bind(url).bind(status).bind(age_secs as i32).fetch_one(pool).await.unwrap()
+}
+
+async fn events(pool: &sqlx::PgPool, id: uuid::Uuid) -> Vec<(String, Option<String>)> {
+    sqlx::query_as("SELECT event_type, message FROM deployment_events WHERE deployment_id=$1 ORDER BY id").bind(id).fetch_all(pool).await.unwrap()
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn failed_deployment_rolls_back_once() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('arapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+    let good = insert_deployment(&pool, app_id, "file://good", "running", 120).await;
+    let bad = insert_deployment(&pool, app_id, "file://bad", "pending", 60).await;
+
+    // opt-in: without the setting a failure stays a failure
+    services::rollouts::on_failed(&pool, bad, "timeout").await;
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM deployments WHERE app_id=$1").bind(app_id).fetch_one(&pool).await.unwrap();
+    assert_eq!(count, 2);
+
+    let (status, v) = call(&app, "PATCH", "/apps/arapp", Some(serde_json::json!({"auto_rollback": true}))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert_eq!(v["settings"]["auto_rollback"], true);
+    assert!(v.get("rollout_deployment_id").is_none());
+
+    let bad = insert_deployment(&pool, app_id, "file://bad2", "pending", 30).await;
+    services::rollouts::on_failed(&pool, bad, "ProgressDeadlineExceeded").await;
+    let (rollback, url, rollback_of): (uuid::Uuid, String, Option<uuid::Uuid>) = sqlx::query_as(
+        "SELECT id, artifact_url, auto_rollback_of FROM deployments WHERE app_id=$1 ORDER BY created_at DESC LIMIT 1")
+        .bind(app_id).fetch_one(&pool).await.unwrap();
+    assert_eq!(url, "file://good");
+    assert_eq!(rollback_of, Some(bad));
+    let bad_events = events(&pool, bad).await;
+    assert_eq!(bad_events[0], ("failed".to_string(), Some("ProgressDeadlineExceeded".to_string())));
+    assert_eq!(bad_events[1], ("auto_rollback".to_string(), Some(format!("rollback_deployment={rollback} target={good}"))));
+    assert_eq!(events(&pool, rollback).await[0], ("auto_rollback".to_string(), Some(format!("failed={bad} from={good} digest=-"))));
+
+    // the rollback failing too must not start another one
+    services::rollouts::on_failed(&pool, rollback, "timeout").await;
+    let latest: uuid::Uuid = sqlx::query_scalar("SELECT id FROM deployments WHERE app_id=$1 ORDER BY created_at DESC LIMIT 1").bind(app_id).fetch_one(&pool).await.unwrap();
+    assert_eq!(latest, rollback);
+    assert_eq!(events(&pool, rollback).await.last().unwrap(), &("auto_rollback_skipped".to_string(), Some("loop_guard".to_string())));
+
+    let res = app.clone().oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap()).await.unwrap();
+    let text = String::from_utf8(axum::body::to_bytes(res.into_body(), 1024 * 1024).await.unwrap().to_vec()).unwrap();
+    assert!(text.contains("deployment_auto_rollbacks_total{outcome=\"triggered\"}"), "{text}");
+    assert!(text.contains("deployment_auto_rollbacks_total{outcome=\"loop_guard\"}"));
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn auto_rollback_needs_an_earlier_running_release() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name, auto_rollback) VALUES ('arfirst', TRUE) RETURNING id").fetch_one(&pool).await.unwrap();
+    let first = insert_deployment(&pool, app_id, "file://first", "pending", 60).await;
+    services::rollouts::on_failed(&pool, first, "timeout").await;
+    assert_eq!(events(&pool, first).await.last().unwrap(), &("auto_rollback_skipped".to_string(), Some("no_target".to_string())));
+    // an older failure superseded by a newer deployment is left alone
+    insert_deployment(&pool, app_id, "file://ok", "running", 50).await;
+    let old = insert_deployment(&pool, app_id, "file://old", "pending", 40).await;
+    insert_deployment(&pool, app_id, "file://newer", "pending", 10).await;
+    services::rollouts::on_failed(&pool, old, "timeout").await;
+    assert_eq!(events(&pool, old).await.last().unwrap(), &("auto_rollback_skipped".to_string(), Some("superseded".to_string())));
+}
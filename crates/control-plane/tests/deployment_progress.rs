@@ -0,0 +1,65 @@
+use control_plane::{build_router, test_support::{call, test_state}, services, models::PodProgress};
+use axum::http::StatusCode;
+
+#[test]
+fn pod_progress_reads_main_container() {
+    let pod: k8s_openapi::api::core::v1::Pod = serde_json::from_value(serde_json::json!({
+        "metadata": {"name": "web-1"},
+        "status": {
+            "phase": "Running",
+            "conditions": [{"type": "Ready", "status": "False"}],
+            "containerStatuses": [
+                {"name": "fetcher", "image": "busybox", "imageID": "", "ready": true, "restartCount": 0},
+                {"name": "app", "image": "node", "imageID": "", "ready": false, "restartCount": 4,
+                 "state": {"waiting": {"reason": "CrashLoopBackOff"}},
+                 "lastState": {"terminated": {"exitCode": 137, "reason": "OOMKilled"}}}
+            ]
+        }
+    })).unwrap();
+    assert_eq!(control_plane::k8s_watch::pod_progress(&pod), PodProgress {
+        name: "web-1".into(), phase: "Running".into(), ready: false, restart_count: 4, last_termination_reason: Some("OOMKilled".into()),
+    });
+    let pending: k8s_openapi::api::core::v1::Pod = serde_json::from_value(serde_json::json!({"metadata": {"name": "web-2"}})).unwrap();
+    assert_eq!(control_plane::k8s_watch::pod_progress(&pending).phase, "Unknown");
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn deployment_reports_cached_rollout_progress() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name) VALUES ('progapp')").execute(&pool).await.unwrap();
+    let app = build_router(state);
+    let dep = services::deployments::create_deployment(&pool, "progapp", "file://p", None, None).await.unwrap();
+
+    // nothing observed yet
+    let (status, v) = call(&app, "GET", &format!("/deployments/{}", dep.id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["progress"]["desired_replicas"], 0);
+    assert_eq!(v["progress"]["processes"], serde_json::json!([]));
+
+    let spec = services::deployments::build_spec(&pool, "progapp", &dep, false).await.unwrap();
+    let spec = control_plane::k8s::DeploySpec { replicas: Some(2), processes: vec![control_plane::k8s::ProcessSpec { name: "worker".into(), command: vec![], replicas: 1 }], ..spec };
+    services::processes::record_processes(&pool, dep.id, &spec).await.unwrap();
+    let pods = [
+        PodProgress { name: "progapp-a".into(), phase: "Running".into(), ready: true, restart_count: 0, last_termination_reason: None },
+        PodProgress { name: "progapp-b".into(), phase: "Pending".into(), ready: false, restart_count: 2, last_termination_reason: Some("Error".into()) },
+    ];
+    services::processes::observe_progress(&pool, dep.id, "web", 2, 1, &pods).await.unwrap();
+    services::processes::observe(&pool, dep.id, "web", 2, 1).await.unwrap();
+    services::processes::observe_progress(&pool, dep.id, "worker", 1, 1, &pods[..1]).await.unwrap();
+    services::processes::observe(&pool, dep.id, "worker", 1, 1).await.unwrap();
+
+    let (_, v) = call(&app, "GET", &format!("/deployments/{}", dep.id), None).await;
+    let progress = &v["progress"];
+    assert_eq!((progress["desired_replicas"].as_i64(), progress["updated_replicas"].as_i64(), progress["ready_replicas"].as_i64(), progress["available_replicas"].as_i64()),
+        (Some(3), Some(3), Some(2), Some(2)), "{progress}");
+    let web = progress["processes"].as_array().unwrap().iter().find(|p| p["process_type"] == "web").unwrap();
+    assert_eq!(web["pods"][1], serde_json::json!({"name": "progapp-b", "phase": "Pending", "ready": false, "restart_count": 2, "last_termination_reason": "Error"}));
+
+    // a new apply of the release starts over
+    services::processes::record_processes(&pool, dep.id, &spec).await.unwrap();
+    let (_, v) = call(&app, "GET", &format!("/deployments/{}", dep.id), None).await;
+    assert_eq!(v["progress"]["ready_replicas"], 0);
+    assert_eq!(v["progress"]["processes"][0]["pods"], serde_json::json!([]));
+}
@@ -0,0 +1,69 @@
+use control_plane::{build_router, test_support::test_state, services};
+use axum::{body::Body, http::{Request, StatusCode}};
+use futures_util::StreamExt;
+use tower::util::ServiceExt;
+
+async fn open(app: &axum::Router, uri: &str) -> axum::response::Response {
+    app.clone().oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap()
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn watch_streams_until_terminal_status() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name) VALUES ('sseapp')").execute(&pool).await.unwrap();
+    let app = build_router(state);
+    let dep = services::deployments::create_deployment(&pool, "sseapp", "file://a", None, None).await.unwrap();
+
+    let res = open(&app, &format!("/deployments/{}/watch", dep.id)).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers()["content-type"].to_str().unwrap().starts_with("text/event-stream"));
+    services::deployments::record_event(&pool, dep.id, "rollout", Some("applying")).await;
+    services::deployments::mark_failed(&pool, dep.id, "timeout").await;
+    // the stream ends after the terminal status, so the whole body can be collected
+    let body = tokio::time::timeout(std::time::Duration::from_secs(5), axum::body::to_bytes(res.into_body(), 1024 * 64)).await
+        .expect("watch stream did not end").unwrap();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    let pending = text.find("\"status\":\"pending\"").expect("initial status");
+    let rollout = text.find("\"event_type\":\"rollout\"").expect("rollout event");
+    let failed = text.find("\"status\":\"failed\"").expect("failed status");
+    assert!(pending < rollout && rollout < failed, "unexpected order: {text}");
+    assert!(text.contains("event: status") && text.contains("event: event") && text.contains("\"failure_reason\":\"timeout\""), "{text}");
+
+    // already terminal: only the snapshot
+    let res = open(&app, &format!("/deployments/{}/watch", dep.id)).await;
+    let text = String::from_utf8(axum::body::to_bytes(res.into_body(), 1024 * 64).await.unwrap().to_vec()).unwrap();
+    assert_eq!(text.matches("event: status").count(), 1, "{text}");
+
+    let res = open(&app, &format!("/deployments/{}/watch", uuid::Uuid::new_v4())).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn app_stream_only_carries_own_deployments() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name) VALUES ('streamed'), ('other')").execute(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let res = open(&app, "/apps/streamed/events/stream").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let other = services::deployments::create_deployment(&pool, "other", "file://o", None, None).await.unwrap();
+    services::deployments::mark_running(&pool, other.id).await;
+    let dep = services::deployments::create_deployment(&pool, "streamed", "file://s", None, None).await.unwrap();
+    services::deployments::mark_running(&pool, dep.id).await;
+
+    let mut body = res.into_body().into_data_stream();
+    let mut text = String::new();
+    while !text.contains("\"event_type\":\"running\"") {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.next()).await.expect("no app event").unwrap().unwrap();
+        text.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    assert!(text.contains(&dep.id.to_string()), "{text}");
+    assert!(!text.contains(&other.id.to_string()), "foreign deployment leaked: {text}");
+    assert!(text.contains("\"status\":\"pending\"") && text.contains("\"status\":\"running\""), "{text}");
+
+    assert_eq!(open(&app, "/apps/nope/events/stream").await.status(), StatusCode::NOT_FOUND);
+}
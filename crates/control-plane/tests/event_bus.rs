@@ -0,0 +1,72 @@
+use control_plane::{build_router, test_support::test_state, services, event_bus::{self, BusEvent}};
+use axum::{body::Body, http::{Request, StatusCode}};
+use tower::util::ServiceExt;
+
+/// Notification as another replica would send it.
+fn remote_envelope(event: serde_json::Value) -> String {
+    serde_json::json!({"origin": uuid::Uuid::new_v4(), "event": event}).to_string()
+}
+
+async fn notify(pool: &sqlx::PgPool, payload: &str) {
+    sqlx::query("SELECT pg_notify($1, $2)").bind(event_bus::CHANNEL).bind(payload).execute(pool).await.unwrap();
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn local_publish_notifies_other_replicas() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name) VALUES ('busapp')").execute(&pool).await.unwrap();
+    let mut listener = sqlx::postgres::PgListener::connect_with(&pool).await.unwrap();
+    listener.listen(event_bus::CHANNEL).await.unwrap();
+    let dep = services::deployments::create_deployment(&pool, "busapp", "file://a", None, None).await.unwrap();
+    services::deployments::record_event(&pool, dep.id, "rollout", Some(&"x".repeat(20_000))).await;
+    let mut seen = Vec::new();
+    while seen.len() < 2 {
+        let n = tokio::time::timeout(std::time::Duration::from_secs(5), listener.recv()).await.expect("no notification").unwrap();
+        let v: serde_json::Value = serde_json::from_str(n.payload()).unwrap();
+        if v["event"]["data"]["deployment_id"] != dep.id.to_string() { continue; }
+        assert_eq!(v["origin"], event_bus::replica_id().to_string());
+        seen.push(v);
+    }
+    assert_eq!(seen[0]["event"]["data"]["status"], "pending");
+    assert_eq!(seen[1]["event"]["data"]["event_type"], "rollout");
+    // oversized messages are cut to fit the NOTIFY limit
+    assert_eq!(seen[1]["event"]["data"]["message"].as_str().unwrap().len(), 1024);
+    // our own notifications are not re-broadcast
+    assert!(event_bus::decode_remote(&seen[0].to_string()).is_none());
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn remote_status_reaches_local_watchers() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name) VALUES ('remoteapp')").execute(&pool).await.unwrap();
+    let app = build_router(state);
+    let dep = services::deployments::create_deployment(&pool, "remoteapp", "file://a", None, None).await.unwrap();
+    // wait until the listener started by the router re-broadcasts foreign notifications
+    let mut rx = event_bus::subscribe();
+    let probe = remote_envelope(serde_json::json!({"type":"artifact","data":{"artifact_id": uuid::Uuid::new_v4(), "app_id": null, "event_type":"stored"}}));
+    let mut ready = false;
+    for _ in 0..50 {
+        notify(&pool, &probe).await;
+        if let Ok(Ok(BusEvent::Artifact(a))) = tokio::time::timeout(std::time::Duration::from_millis(200), rx.recv()).await {
+            assert_eq!(a.event_type, "stored");
+            ready = true;
+            break;
+        }
+    }
+    assert!(ready, "event bus listener did not re-broadcast");
+
+    let res = app.clone().oneshot(Request::builder().uri(format!("/deployments/{}/watch", dep.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    // another replica's watcher marked it running
+    notify(&pool, &remote_envelope(serde_json::json!({"type":"deployment","data":{
+        "kind":"status", "deployment_id": dep.id, "app_id": dep.app_id, "status":"running", "failure_reason": null, "at": chrono::Utc::now()
+    }}))).await;
+    let body = tokio::time::timeout(std::time::Duration::from_secs(5), axum::body::to_bytes(res.into_body(), 1024 * 64)).await
+        .expect("watch stream did not end").unwrap();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    assert!(text.contains("\"status\":\"running\""), "{text}");
+}
@@ -0,0 +1,82 @@
+use control_plane::{build_router, test_support::test_state};
+use axum::{body::Body, http::{Request, StatusCode}};
+use tower::util::ServiceExt;
+
+const DIGEST: &str = "abababababababababababababababababababababababababababababababab";
+
+async fn get_json(app: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let res = app.clone().oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+    let status = res.status();
+    let bytes = axum::body::to_bytes(res.into_body(), 1024 * 256).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn event_history_endpoints() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('evapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let dep_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status, digest) VALUES ($1,'file://a','failed',$2) RETURNING id")
+        .bind(app_id).bind(DIGEST).fetch_one(&pool).await.unwrap();
+    for (ty, msg, hours_ago) in [("digest_updated", None, 3.0), ("running", None, 2.0), ("failed", Some("timeout"), 1.0)] {
+        sqlx::query("INSERT INTO deployment_events (deployment_id, event_type, message, created_at) VALUES ($1,$2,$3, now() - make_interval(hours => $4::int))")
+            .bind(dep_id).bind(ty).bind(msg).bind(hours_ago as i32).execute(&pool).await.unwrap();
+    }
+    let art_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO artifacts (app_id, digest, size_bytes, verified, status) VALUES ($1,$2,10,false,'stored') RETURNING id")
+        .bind(app_id).bind(DIGEST).fetch_one(&pool).await.unwrap();
+    sqlx::query("INSERT INTO artifact_events (artifact_id, event_type, created_at) VALUES ($1,'stored', now() - interval '4 hours')").bind(art_id).execute(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let (status, v) = get_json(&app, &format!("/deployments/{dep_id}/events")).await;
+    assert_eq!(status, StatusCode::OK);
+    let types: Vec<&str> = v.as_array().unwrap().iter().map(|e| e["event_type"].as_str().unwrap()).collect();
+    assert_eq!(types, vec!["failed", "running", "digest_updated"]);
+    assert_eq!(v[0]["message"], "timeout");
+
+    let (_, v) = get_json(&app, &format!("/deployments/{dep_id}/events?limit=1&offset=1")).await;
+    assert_eq!(v.as_array().unwrap().len(), 1);
+    assert_eq!(v[0]["event_type"], "running");
+
+    let since = (chrono::Utc::now() - chrono::Duration::minutes(150)).to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let (_, v) = get_json(&app, &format!("/deployments/{dep_id}/events?since={since}")).await;
+    assert_eq!(v.as_array().unwrap().len(), 2);
+
+    let (status, v) = get_json(&app, &format!("/artifacts/{DIGEST}/events")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v[0]["event_type"], "stored");
+
+    let (status, v) = get_json(&app, "/apps/evapp/events").await;
+    assert_eq!(status, StatusCode::OK);
+    let arr = v.as_array().unwrap();
+    assert_eq!(arr.len(), 4);
+    assert_eq!(arr[0]["source"], "deployment");
+    assert_eq!(arr[3]["source"], "artifact");
+    assert_eq!(arr[3]["digest"], DIGEST);
+
+    let until = (chrono::Utc::now() - chrono::Duration::minutes(150)).to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let (_, v) = get_json(&app, &format!("/apps/evapp/events?until={until}")).await;
+    assert_eq!(v.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn event_history_errors() {
+    let state = test_state().await;
+    let app = build_router(state);
+    let (status, _) = get_json(&app, &format!("/deployments/{}/events", uuid::Uuid::new_v4())).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_json(&app, &format!("/artifacts/{DIGEST}/events")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_json(&app, "/artifacts/short/events").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get_json(&app, "/apps/nope/events").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_json(&app, "/apps/nope/events?since=yesterday").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, v) = get_json(&app, "/openapi.json").await;
+    assert_eq!(status, StatusCode::OK);
+    for p in ["/deployments/{id}/events", "/artifacts/{digest}/events", "/apps/{app_name}/events"] {
+        assert!(v["paths"].get(p).is_some(), "missing {p} in openapi");
+    }
+}
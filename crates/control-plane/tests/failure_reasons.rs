@@ -0,0 +1,74 @@
+use control_plane::{k8s_watch::{event_failure, pod_failure}, services, telemetry, test_support::test_state};
+use k8s_openapi::api::core::v1::{Event, Pod};
+
+fn pod(status: serde_json::Value) -> Pod {
+    serde_json::from_value(serde_json::json!({"metadata": {"name": "web-1"}, "status": status})).unwrap()
+}
+
+fn app(state: serde_json::Value, last_state: serde_json::Value, restarts: i32) -> Pod {
+    pod(serde_json::json!({"phase": "Running", "containerStatuses": [
+        {"name": "app", "image": "node", "imageID": "", "ready": false, "restartCount": restarts, "state": state, "lastState": last_state}
+    ]}))
+}
+
+fn init(state: serde_json::Value) -> Pod {
+    pod(serde_json::json!({"phase": "Pending", "initContainerStatuses": [
+        {"name": "fetch-artifact", "image": "busybox", "imageID": "", "ready": false, "restartCount": 0, "state": state}
+    ]}))
+}
+
+#[test]
+fn pod_states_map_to_stable_reasons() {
+    let crash = serde_json::json!({"waiting": {"reason": "CrashLoopBackOff"}});
+    assert_eq!(pod_failure(&app(crash.clone(), serde_json::json!({"terminated": {"exitCode": 1, "reason": "Error"}}), 3), 3).as_deref(), Some("crash_loop"));
+    assert_eq!(pod_failure(&app(crash.clone(), serde_json::json!({"terminated": {"exitCode": 1, "reason": "Error"}}), 1), 3), None, "below the restart threshold");
+    assert_eq!(pod_failure(&app(crash, serde_json::json!({"terminated": {"exitCode": 137, "reason": "OOMKilled"}}), 5), 3).as_deref(), Some("oom_killed"));
+    assert_eq!(pod_failure(&app(serde_json::json!({"waiting": {"reason": "ImagePullBackOff"}}), serde_json::json!({}), 0), 3).as_deref(), Some("image_pull"));
+    assert_eq!(pod_failure(&app(serde_json::json!({"running": {}}), serde_json::json!({}), 0), 3), None);
+
+    assert_eq!(pod_failure(&init(serde_json::json!({"terminated": {"exitCode": 3, "reason": "Error", "message": "checksum_mismatch\n"}})), 3).as_deref(), Some("checksum_mismatch"));
+    assert_eq!(pod_failure(&init(serde_json::json!({"terminated": {"exitCode": 1, "reason": "Error"}})), 3).as_deref(), Some("init:fetch-artifact:Error"));
+    assert_eq!(pod_failure(&init(serde_json::json!({"waiting": {"reason": "ErrImagePull"}})), 3).as_deref(), Some("image_pull"));
+}
+
+#[test]
+fn warning_events_map_to_stable_reasons() {
+    let event = |type_: &str, reason: &str, message: &str, count: i32| -> Event {
+        serde_json::from_value(serde_json::json!({
+            "metadata": {"name": "e"}, "involvedObject": {"kind": "Pod", "name": "web-1"},
+            "type": type_, "reason": reason, "message": message, "count": count
+        })).unwrap()
+    };
+    assert_eq!(event_failure(&event("Warning", "Failed", "Failed to pull image \"nope:1\": not found", 1), 3), Some("image_pull"));
+    assert_eq!(event_failure(&event("Warning", "BackOff", "Back-off pulling image \"nope:1\"", 1), 3), Some("image_pull"));
+    assert_eq!(event_failure(&event("Warning", "BackOff", "Back-off restarting failed container app in pod web-1", 4), 3), Some("crash_loop"));
+    assert_eq!(event_failure(&event("Warning", "BackOff", "Back-off restarting failed container app in pod web-1", 1), 3), None);
+    assert_eq!(event_failure(&event("Normal", "Pulling", "Pulling image \"node\"", 1), 3), None);
+    assert_eq!(event_failure(&event("Warning", "FailedScheduling", "0/3 nodes are available", 1), 3), None);
+}
+
+#[test]
+fn failure_reasons_have_bounded_labels() {
+    use services::deployments::failure_label;
+    for (reason, label) in [
+        ("crash_loop", "crash_loop"), ("worker:oom_killed", "oom_killed"), ("image_pull", "image_pull"),
+        ("checksum_mismatch", "checksum_mismatch"), ("init:fetch-artifact:Error", "init_failed"),
+        ("ProgressDeadlineExceeded", "progress_deadline"), ("timeout", "timeout"), ("release_failed", "release_failed"),
+        ("apply_failed:unknown runtime 'x'", "apply_failed"), ("something else", "other"),
+    ] {
+        assert_eq!(failure_label(reason), label, "{reason}");
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn failed_deployment_counts_its_reason() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name) VALUES ('crashy')").execute(&pool).await.unwrap();
+    let dep = services::deployments::create_deployment(&pool, "crashy", "file://c", None, None).await.unwrap();
+    let before = telemetry::DEPLOYMENT_FAILURES.with_label_values(&["crash_loop"]).get();
+    services::rollouts::on_failed(&pool, dep.id, "worker:crash_loop").await;
+    assert_eq!(telemetry::DEPLOYMENT_FAILURES.with_label_values(&["crash_loop"]).get(), before + 1);
+    assert_eq!(services::deployments::get_deployment(&pool, dep.id).await.unwrap().failure_reason.as_deref(), Some("worker:crash_loop"));
+}
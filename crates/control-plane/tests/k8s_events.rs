@@ -0,0 +1,51 @@
+use control_plane::{build_router, k8s_watch::{event_object_name, ingest_event}, services, test_support::{call, test_state}};
+use axum::http::StatusCode;
+use k8s_openapi::api::core::v1::Event;
+
+fn event(uid: &str, type_: &str, kind: &str, name: &str, namespace: &str) -> Event {
+    serde_json::from_value(serde_json::json!({
+        "metadata": {"name": format!("{name}.1"), "namespace": namespace, "uid": uid},
+        "involvedObject": {"kind": kind, "name": name, "namespace": namespace},
+        "type": type_, "reason": "BackOff", "message": "Back-off restarting failed container app"
+    })).unwrap()
+}
+
+#[test]
+fn event_objects_map_to_deployment_names() {
+    assert_eq!(event_object_name("Pod", "web-7d4b9c-x2kq9").as_deref(), Some("web"));
+    assert_eq!(event_object_name("Pod", "web-worker-7d4b9c-x2kq9").as_deref(), Some("web-worker"));
+    assert_eq!(event_object_name("ReplicaSet", "web-7d4b9c").as_deref(), Some("web"));
+    assert_eq!(event_object_name("Deployment", "web").as_deref(), Some("web"));
+    assert_eq!(event_object_name("Pod", "web"), None);
+    assert_eq!(event_object_name("Node", "node-1"), None);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn warning_events_are_stored_once_on_the_tracking_deployment() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name, namespace) VALUES ('evapp', 'team-a')").execute(&pool).await.unwrap();
+    let app = build_router(state);
+    let dep = services::deployments::create_deployment(&pool, "evapp", "file://e", None, None).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "evapp", &dep, false).await.unwrap();
+    services::processes::record_processes(&pool, dep.id, &spec).await.unwrap();
+
+    assert!(ingest_event(&pool, &event("uid-1", "Warning", "Pod", "evapp-5f6c7d-abcde", "team-a")).await.unwrap());
+    assert!(!ingest_event(&pool, &event("uid-1", "Warning", "Pod", "evapp-5f6c7d-abcde", "team-a")).await.unwrap(), "same UID is deduplicated");
+    assert!(!ingest_event(&pool, &event("uid-2", "Normal", "Pod", "evapp-5f6c7d-abcde", "team-a")).await.unwrap(), "only warnings");
+    assert!(!ingest_event(&pool, &event("uid-3", "Warning", "Pod", "other-5f6c7d-abcde", "team-a")).await.unwrap(), "unmanaged object");
+    assert!(!ingest_event(&pool, &event("uid-4", "Warning", "Pod", "evapp-5f6c7d-abcde", "default")).await.unwrap(), "other namespace");
+    assert!(ingest_event(&pool, &event("uid-5", "Warning", "Deployment", "evapp", "team-a")).await.unwrap());
+
+    let (status, v) = call(&app, "GET", &format!("/deployments/{}/events", dep.id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let events = v.as_array().unwrap();
+    let k8s: Vec<_> = events.iter().filter(|e| e["event_type"] == "k8s_event").collect();
+    assert_eq!(k8s.len(), 2, "{events:?}");
+    let pod = k8s.iter().find(|e| e["source_kind"] == "Pod").unwrap();
+    assert_eq!(pod["source_name"], "evapp-5f6c7d-abcde");
+    assert_eq!(pod["source_reason"], "BackOff");
+    assert_eq!(pod["message"], "BackOff: Back-off restarting failed container app");
+    assert!(events.iter().filter(|e| e["event_type"] != "k8s_event").all(|e| e.get("source_kind").is_none()));
+}
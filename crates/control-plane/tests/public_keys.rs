@@ -0,0 +1,92 @@
+use control_plane::{build_router, test_support::{call, test_state}};
+use axum::http::StatusCode;
+use ed25519_dalek::{SigningKey, Signer};
+
+const DIGEST: &str = "cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd";
+
+fn keypair() -> (SigningKey, String) {
+    let sk = SigningKey::generate(&mut rand::rngs::OsRng);
+    let pk_hex = hex::encode(sk.verifying_key().to_bytes());
+    (sk, pk_hex)
+}
+
+async fn deploy_signed(app: &axum::Router, sk: &SigningKey) -> StatusCode {
+    let sig = hex::encode(sk.sign(DIGEST.as_bytes()).to_bytes());
+    call(app, "POST", "/deployments", Some(serde_json::json!({"app_name":"keyapp","artifact_url":format!("file://artifacts/{DIGEST}"),"signature":sig}))).await.0
+}
+
+async fn setup() -> axum::Router {
+    let state = test_state().await;
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('keyapp') RETURNING id").fetch_one(&state.db).await.unwrap();
+    sqlx::query("INSERT INTO artifacts (app_id, digest, size_bytes, verified, status) VALUES ($1,$2,1,false,'stored')")
+        .bind(app_id).bind(DIGEST).execute(&state.db).await.unwrap();
+    build_router(state)
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn list_and_deactivate_keys() {
+    let app = setup().await;
+    let (sk, pk_hex) = keypair();
+    let (status, _) = call(&app, "POST", "/apps/keyapp/public-keys", Some(serde_json::json!({"public_key_hex": pk_hex}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, v) = call(&app, "GET", "/apps/keyapp/public-keys", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v.as_array().unwrap().len(), 1);
+    assert_eq!(v[0]["public_key_hex"], pk_hex);
+    assert_eq!(v[0]["active"], true);
+    let key_id = v[0]["id"].as_str().unwrap().to_string();
+    assert_eq!(deploy_signed(&app, &sk).await, StatusCode::CREATED);
+
+    let (status, v) = call(&app, "DELETE", &format!("/apps/keyapp/public-keys/{key_id}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["active"], false);
+    assert_eq!(deploy_signed(&app, &sk).await, StatusCode::BAD_REQUEST);
+    let (status, _) = call(&app, "DELETE", &format!("/apps/keyapp/public-keys/{}", uuid::Uuid::new_v4()), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = call(&app, "GET", "/apps/missing/public-keys", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn validity_window_is_enforced() {
+    let app = setup().await;
+    let (sk, pk_hex) = keypair();
+    let future = (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339();
+    let (status, _) = call(&app, "POST", "/apps/keyapp/public-keys", Some(serde_json::json!({"public_key_hex": pk_hex, "not_before": future}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(deploy_signed(&app, &sk).await, StatusCode::BAD_REQUEST);
+    let past = (chrono::Utc::now() - chrono::Duration::hours(1)).to_rfc3339();
+    let (status, _) = call(&app, "POST", "/apps/keyapp/public-keys", Some(serde_json::json!({"public_key_hex": pk_hex, "expires_at": past}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(deploy_signed(&app, &sk).await, StatusCode::BAD_REQUEST);
+    let (status, _) = call(&app, "POST", "/apps/keyapp/public-keys", Some(serde_json::json!({"public_key_hex": pk_hex, "not_before": future, "expires_at": past}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn rotation_schedules_old_key_expiry() {
+    let app = setup().await;
+    let (old_sk, old_hex) = keypair();
+    let (new_sk, new_hex) = keypair();
+    call(&app, "POST", "/apps/keyapp/public-keys", Some(serde_json::json!({"public_key_hex": old_hex}))).await;
+    let (status, v) = call(&app, "POST", "/apps/keyapp/public-keys/rotate", Some(serde_json::json!({"public_key_hex": new_hex, "grace_period_secs": 3600}))).await;
+    assert_eq!(status, StatusCode::CREATED, "{v}");
+    assert_eq!(v["key"]["public_key_hex"], new_hex);
+    assert_eq!(v["expiring"].as_array().unwrap().len(), 1);
+    assert_eq!(v["expiring"][0]["public_key_hex"], old_hex);
+    assert!(v["expiring"][0]["expires_at"].is_string());
+    // both keys valid during the grace period
+    assert_eq!(deploy_signed(&app, &old_sk).await, StatusCode::CREATED);
+    assert_eq!(deploy_signed(&app, &new_sk).await, StatusCode::CREATED);
+    // rotating again with no grace expires the previous key immediately
+    let (_, third_hex) = keypair();
+    let (status, _) = call(&app, "POST", "/apps/keyapp/public-keys/rotate", Some(serde_json::json!({"public_key_hex": third_hex, "grace_period_secs": 0}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(deploy_signed(&app, &old_sk).await, StatusCode::BAD_REQUEST);
+    assert_eq!(deploy_signed(&app, &new_sk).await, StatusCode::BAD_REQUEST);
+    let (status, _) = call(&app, "POST", "/apps/keyapp/public-keys/rotate", Some(serde_json::json!({"public_key_hex": "zz"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
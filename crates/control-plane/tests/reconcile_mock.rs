@@ -0,0 +1,38 @@
+#![cfg(feature = "mock-kube")]
+use control_plane::{k8s, k8s_reconcile, services, test_support::test_state};
+
+async fn event_types(pool: &sqlx::PgPool, id: uuid::Uuid) -> Vec<String> {
+    sqlx::query_scalar("SELECT event_type FROM deployment_events WHERE deployment_id=$1 ORDER BY id").bind(id).fetch_all(pool).await.unwrap()
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn drifted_release_is_reapplied() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    sqlx::query("INSERT INTO applications (name) VALUES ('drifty')").execute(&pool).await.unwrap();
+    let dep = services::deployments::create_deployment(&pool, "drifty", "file://d", None, None).await.unwrap();
+    services::apply_queue::enqueue(&pool, dep.id, false).await.unwrap();
+    assert_eq!(services::apply_queue::process_due(&pool).await.unwrap(), 1);
+    let ns = services::apps::namespace_for(&pool, "drifty").await.unwrap().unwrap_or_else(services::apps::default_namespace);
+
+    // rolling out: left to the watcher even when the cluster differs
+    assert!(k8s::mock_edit_workload(&ns, "drifty", |d| d["spec"]["template"]["spec"]["containers"][0]["image"] = "nginx:edited".into()));
+    assert!(k8s_reconcile::reconcile_once(&pool).await.unwrap().is_empty());
+
+    services::deployments::mark_running(&pool, dep.id).await;
+    let drift = k8s_reconcile::reconcile_once(&pool).await.unwrap();
+    assert_eq!(drift.len(), 1, "{drift:?}");
+    assert_eq!((drift[0].deployment_id, drift[0].object.as_str(), drift[0].fields.clone()), (dep.id, "drifty", vec!["image"]));
+    let events = event_types(&pool, dep.id).await;
+    assert!(events.ends_with(&["drift".to_string(), "drift_repaired".to_string()]), "{events:?}");
+    assert!(k8s_reconcile::reconcile_once(&pool).await.unwrap().is_empty(), "re-apply restores the desired state");
+
+    k8s::mock_delete_workload(&ns, "drifty");
+    let drift = k8s_reconcile::reconcile_once(&pool).await.unwrap();
+    assert_eq!(drift.iter().map(|d| d.fields.clone()).collect::<Vec<_>>(), [vec!["missing"]]);
+    let message: Option<String> = sqlx::query_scalar("SELECT message FROM deployment_events WHERE deployment_id=$1 AND event_type='drift' ORDER BY id DESC LIMIT 1")
+        .bind(dep.id).fetch_one(&pool).await.unwrap();
+    assert_eq!(message.as_deref(), Some("object=drifty fields=missing"));
+    assert!(k8s_reconcile::reconcile_once(&pool).await.unwrap().is_empty());
+}
@@ -0,0 +1,73 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+const DIGEST: &str = "1212121212121212121212121212121212121212121212121212121212121212";
+
+async fn event_types(pool: &sqlx::PgPool, id: uuid::Uuid) -> Vec<String> {
+    sqlx::query_scalar("SELECT event_type FROM deployment_events WHERE deployment_id=$1 ORDER BY id").bind(id).fetch_all(pool).await.unwrap()
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn release_command_runs_before_rollout() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('relapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+    let (status, v) = call(&app, "POST", "/artifacts/complete", Some(serde_json::json!({
+        "app_name": "relapp", "digest": DIGEST, "size_bytes": 0,
+        "metadata": {"processes": {"web": "node server.js", "release": "node migrate.js"}}
+    }))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    let dep_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status, digest) VALUES ($1,'file://a','pending',$2) RETURNING id")
+        .bind(app_id).bind(DIGEST).fetch_one(&pool).await.unwrap();
+    let dep = services::deployments::get_deployment(&pool, dep_id).await.unwrap();
+
+    // Procfile `release:` entry unless the app sets its own command
+    let spec = services::deployments::build_spec(&pool, "relapp", &dep, false).await.unwrap();
+    assert_eq!(spec.deployment_id, dep_id);
+    assert_eq!(spec.release_command.as_ref().unwrap()[2], "exec node migrate.js");
+    let (status, v) = call(&app, "PATCH", "/apps/relapp", Some(serde_json::json!({"release_command": "  ./bin/migrate up "}))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert_eq!(v["settings"]["release_command"], "./bin/migrate up");
+    assert!(v.get("rollout_deployment_id").is_none(), "release command applies from the next rollout");
+    let spec = services::deployments::build_spec(&pool, "relapp", &dep, false).await.unwrap();
+    assert_eq!(spec.release_command.as_ref().unwrap()[2], "exec ./bin/migrate up");
+
+    assert!(services::deployments::run_release_phase(&pool, dep_id, &spec).await);
+    let events = event_types(&pool, dep_id).await;
+    assert_eq!(events.first().map(String::as_str), Some("release_started"));
+    assert_eq!(events.last().map(String::as_str), Some("release_succeeded"));
+    assert_eq!(services::deployments::get_deployment(&pool, dep_id).await.unwrap().status, "pending");
+
+    // no release command: nothing runs
+    let (_, v) = call(&app, "PATCH", "/apps/relapp", Some(serde_json::json!({"release_command": null}))).await;
+    assert!(v["settings"]["release_command"].is_null());
+    sqlx::query("UPDATE artifacts SET metadata=NULL WHERE digest=$1").bind(DIGEST).execute(&pool).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "relapp", &dep, false).await.unwrap();
+    assert!(spec.release_command.is_none());
+    let before = event_types(&pool, dep_id).await.len();
+    assert!(services::deployments::run_release_phase(&pool, dep_id, &spec).await);
+    assert_eq!(event_types(&pool, dep_id).await.len(), before);
+}
+
+#[cfg(feature = "mock-kube")]
+#[tokio::test]
+#[serial_test::serial]
+async fn failed_release_job_fails_deployment_and_keeps_logs() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name, release_command) VALUES ('badrel', 'sh -c \"exit 1\"') RETURNING id").fetch_one(&pool).await.unwrap();
+    let dep_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://a','pending') RETURNING id")
+        .bind(app_id).fetch_one(&pool).await.unwrap();
+    let dep = services::deployments::get_deployment(&pool, dep_id).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "badrel", &dep, false).await.unwrap();
+    assert!(!services::deployments::run_release_phase(&pool, dep_id, &spec).await);
+    let dep = services::deployments::get_deployment(&pool, dep_id).await.unwrap();
+    assert_eq!(dep.status, "failed");
+    assert_eq!(dep.failure_reason.as_deref(), Some("release_failed"));
+    let logs: Vec<String> = sqlx::query_scalar("SELECT message FROM deployment_events WHERE deployment_id=$1 AND event_type='release_log' ORDER BY id")
+        .bind(dep_id).fetch_all(&pool).await.unwrap();
+    assert!(logs.iter().any(|l| l.contains("exit=1")), "{logs:?}");
+    assert_eq!(logs.last().unwrap(), "release job failed: BackoffLimitExceeded");
+}
@@ -0,0 +1,87 @@
+use control_plane::{build_router, test_support::test_state};
+use axum::{body::Body, http::{Request, StatusCode}};
+use tower::util::ServiceExt;
+
+const D1: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+const D2: &str = "2222222222222222222222222222222222222222222222222222222222222222";
+
+async fn seed(pool: &sqlx::Pool<sqlx::Postgres>, app: &str, rows: &[(&str, &str, i32)]) -> Vec<uuid::Uuid> {
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ($1) RETURNING id")
+        .bind(app).fetch_one(pool).await.unwrap();
+    let mut ids = Vec::new();
+    for (digest, status, age_secs) in rows {
+        let id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status, digest, signature, created_at) VALUES ($1,$2,$3,$4,$5, now() - make_interval(secs => $6)) RETURNING id")
+            .bind(app_id).bind(format!("file://{digest}.tar.gz")).bind(status).bind(digest).bind(format!("sig-{}", &digest[..4])).bind(*age_secs as f64)
+            .fetch_one(pool).await.unwrap();
+        ids.push(id);
+    }
+    ids
+}
+
+async fn post_rollback(app: &axum::Router, name: &str, body: Option<serde_json::Value>) -> (StatusCode, serde_json::Value) {
+    let mut req = Request::builder().method("POST").uri(format!("/apps/{name}/rollback"));
+    let body = match body {
+        Some(b) => { req = req.header("content-type","application/json"); Body::from(b.to_string()) }
+        None => Body::empty(),
+    };
+    let res = app.clone().oneshot(req.body(body).unwrap()).await.unwrap();
+    let status = res.status();
+    let bytes = axum::body::to_bytes(res.into_body(), 1024 * 64).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn rollback_defaults_to_previous_running_release() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let ids = seed(&pool, "rbapp", &[(D1, "running", 120), (D2, "running", 60)]).await;
+    let app = build_router(state);
+    let (status, v) = post_rollback(&app, "rbapp", None).await;
+    assert_eq!(status, StatusCode::CREATED, "{v}");
+    assert_eq!(v["rolled_back_to"], ids[0].to_string());
+    assert_eq!(v["digest"], D1);
+    let new_id: uuid::Uuid = v["id"].as_str().unwrap().parse().unwrap();
+    let (artifact_url, signature, dep_status): (String, Option<String>, String) = sqlx::query_as("SELECT artifact_url, signature, status FROM deployments WHERE id=$1")
+        .bind(new_id).fetch_one(&pool).await.unwrap();
+    assert_eq!(artifact_url, format!("file://{D1}.tar.gz"));
+    assert_eq!(signature.as_deref(), Some("sig-1111"));
+    assert_eq!(dep_status, "pending");
+    let msg: Option<String> = sqlx::query_scalar("SELECT message FROM deployment_events WHERE deployment_id=$1 AND event_type='rollback'")
+        .bind(new_id).fetch_one(&pool).await.unwrap();
+    assert!(msg.unwrap().contains(&ids[0].to_string()));
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn rollback_to_explicit_target() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let ids = seed(&pool, "rbapp2", &[(D1, "running", 180), (D2, "running", 120), (D1, "failed", 60)]).await;
+    let app = build_router(state);
+    let (status, v) = post_rollback(&app, "rbapp2", Some(serde_json::json!({"digest": D2}))).await;
+    assert_eq!(status, StatusCode::CREATED, "{v}");
+    assert_eq!(v["rolled_back_to"], ids[1].to_string());
+    let (status, v) = post_rollback(&app, "rbapp2", Some(serde_json::json!({"deployment_id": ids[0]}))).await;
+    assert_eq!(status, StatusCode::CREATED, "{v}");
+    assert_eq!(v["digest"], D1);
+    // failed deployments are not valid rollback targets
+    let (status, _) = post_rollback(&app, "rbapp2", Some(serde_json::json!({"deployment_id": ids[2]}))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = post_rollback(&app, "rbapp2", Some(serde_json::json!({"digest": "nothex"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn rollback_without_previous_release_conflicts() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    seed(&pool, "rbapp3", &[(D1, "running", 60)]).await;
+    let app = build_router(state);
+    let (status, v) = post_rollback(&app, "rbapp3", None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(v["code"], "conflict");
+    let (status, _) = post_rollback(&app, "missing", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,39 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+#[tokio::test]
+#[serial_test::serial]
+async fn rollout_policy_is_validated_and_used_by_the_spec() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('polapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+    let (_, v) = call(&app, "GET", "/apps/polapp", None).await;
+    assert_eq!(v["settings"]["rollout_policy"], serde_json::json!({}));
+    for bad in [
+        serde_json::json!({"progress_deadline_seconds": 0}),
+        serde_json::json!({"min_ready_seconds": 300}),
+        serde_json::json!({"max_surge": 0, "max_unavailable": "0%"}),
+        serde_json::json!({"max_surge": "lots"}),
+        serde_json::json!({"max_unavailable": -1}),
+        serde_json::json!({"timeout_seconds": 0}),
+    ] {
+        let (status, v) = call(&app, "PATCH", "/apps/polapp", Some(serde_json::json!({"rollout_policy": bad}))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{bad} -> {v}");
+    }
+    let policy = serde_json::json!({"progress_deadline_seconds": 600, "min_ready_seconds": 15, "max_surge": 1, "max_unavailable": "10%", "timeout_seconds": 1200});
+    let (status, v) = call(&app, "PATCH", "/apps/polapp", Some(serde_json::json!({"rollout_policy": policy}))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert_eq!(v["settings"]["rollout_policy"], policy);
+    assert!(v.get("rollout_deployment_id").is_none(), "policy applies from the next rollout");
+
+    let dep_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://p','pending') RETURNING id")
+        .bind(app_id).fetch_one(&pool).await.unwrap();
+    let dep = services::deployments::get_deployment(&pool, dep_id).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "polapp", &dep, false).await.unwrap();
+    assert_eq!(spec.rollout.progress_deadline_seconds, Some(600));
+    assert_eq!(control_plane::k8s::rollout_timeout_secs(&spec.rollout), 1200);
+
+    let (_, v) = call(&app, "PATCH", "/apps/polapp", Some(serde_json::json!({"rollout_policy": {}}))).await;
+    assert_eq!(v["settings"]["rollout_policy"], serde_json::json!({}));
+}
@@ -0,0 +1,93 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+async fn create_app(pool: &sqlx::PgPool, name: &str) {
+    sqlx::query("INSERT INTO applications (name) VALUES ($1)").bind(name).execute(pool).await.unwrap();
+}
+
+async fn tracked_objects(pool: &sqlx::PgPool, id: uuid::Uuid) -> Vec<String> {
+    sqlx::query_scalar("SELECT object_name FROM deployment_processes WHERE deployment_id=$1 ORDER BY process_type").bind(id).fetch_all(pool).await.unwrap()
+}
+
+/// Wait for the background apply to record the Deployments it tracks.
+async fn wait_tracked(pool: &sqlx::PgPool, id: uuid::Uuid) -> Vec<String> {
+    for _ in 0..50 {
+        let objs = tracked_objects(pool, id).await;
+        if !objs.is_empty() { return objs; }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    panic!("deployment {id} tracks no objects");
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn canary_candidate_is_promoted() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    create_app(&pool, "canapp").await;
+    let app = build_router(state);
+    let (status, v) = call(&app, "POST", "/deployments", Some(serde_json::json!({"app_name": "canapp", "artifact_url": "file://c", "strategy": {"canary": {"percent": 0}}}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{v}");
+    let (status, v) = call(&app, "POST", "/deployments", Some(serde_json::json!({"app_name": "canapp", "artifact_url": "file://c", "strategy": {"canary": {"percent": 20}}}))).await;
+    assert_eq!(status, StatusCode::CREATED, "{v}");
+    let id: uuid::Uuid = v["id"].as_str().unwrap().parse().unwrap();
+    let (_, v) = call(&app, "GET", &format!("/deployments/{id}"), None).await;
+    assert_eq!(v["strategy"], serde_json::json!({"canary": {"percent": 20}}));
+    assert_eq!(v["rollout_phase"], "progressing");
+    // only the digest-suffixed candidate is tracked while it rolls out
+    let candidate = format!("canapp-{}", &id.simple().to_string()[..12]);
+    assert_eq!(wait_tracked(&pool, id).await, [candidate]);
+
+    let (status, _) = call(&app, "POST", &format!("/deployments/{id}/promote"), None).await;
+    assert_eq!(status, StatusCode::CONFLICT, "candidate not ready yet");
+    services::rollouts::on_ready(&pool, id).await; // watcher: candidate ready
+    let (_, v) = call(&app, "GET", &format!("/deployments/{id}"), None).await;
+    assert_eq!(v["status"], "pending");
+    assert_eq!(v["rollout_phase"], "awaiting_promotion");
+
+    let (status, v) = call(&app, "POST", &format!("/deployments/{id}/promote"), None).await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{v}");
+    assert_eq!(v["rollout_phase"], "promoting");
+    // from now on the app's own Deployment is tracked
+    assert_eq!(tracked_objects(&pool, id).await, ["canapp"]);
+    services::rollouts::on_ready(&pool, id).await; // watcher: app Deployment rolled to the release
+    let (_, v) = call(&app, "GET", &format!("/deployments/{id}"), None).await;
+    assert_eq!(v["status"], "running");
+    assert_eq!(v["rollout_phase"], "promoted");
+    let (status, _) = call(&app, "POST", &format!("/deployments/{id}/abort"), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let events: Vec<String> = sqlx::query_scalar("SELECT event_type FROM deployment_events WHERE deployment_id=$1 ORDER BY id").bind(id).fetch_all(&pool).await.unwrap();
+    assert_eq!(events, ["candidate_ready", "promote", "running"]);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn blue_green_candidate_is_aborted() {
+    let state = test_state().await;
+    let pool = state.db.clone();
+    create_app(&pool, "bgapp").await;
+    let app = build_router(state);
+    let (status, v) = call(&app, "POST", "/deployments", Some(serde_json::json!({"app_name": "bgapp", "artifact_url": "file://b", "strategy": "blue_green"}))).await;
+    assert_eq!(status, StatusCode::CREATED, "{v}");
+    let id: uuid::Uuid = v["id"].as_str().unwrap().parse().unwrap();
+    wait_tracked(&pool, id).await;
+    let (status, v) = call(&app, "POST", &format!("/deployments/{id}/abort"), None).await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{v}");
+    assert_eq!(v["status"], "failed");
+    assert_eq!(v["failure_reason"], "aborted");
+    assert_eq!(v["strategy"], "blue_green");
+    assert_eq!(v["rollout_phase"], "aborted");
+
+    // rolling deployments have nothing to promote or abort
+    let (_, v) = call(&app, "POST", "/deployments", Some(serde_json::json!({"app_name": "bgapp", "artifact_url": "file://r"}))).await;
+    let id = v["id"].as_str().unwrap();
+    let (_, v) = call(&app, "GET", &format!("/deployments/{id}"), None).await;
+    assert_eq!(v["strategy"], "rolling");
+    assert!(v.get("rollout_phase").is_none());
+    let (status, _) = call(&app, "POST", &format!("/deployments/{id}/promote"), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = call(&app, "POST", &format!("/deployments/{id}/abort"), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = call(&app, "POST", &format!("/deployments/{}/abort", uuid::Uuid::new_v4()), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
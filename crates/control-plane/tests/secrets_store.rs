@@ -0,0 +1,73 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+fn write_keyring(dir: &std::path::Path, keys: &[[u8; 32]]) {
+    let contents: Vec<String> = keys.iter().map(hex::encode).collect();
+    std::fs::write(dir.join("master.keys"), contents.join("\n")).unwrap();
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn secrets_encrypted_never_returned_and_rewrapped() {
+    let dir = std::env::temp_dir().join(format!("aether-keys-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    write_keyring(&dir, &[[1u8; 32]]);
+    std::env::set_var("AETHER_MASTER_KEY_FILE", dir.join("master.keys"));
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('secapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let dep_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://rel','running') RETURNING id")
+        .bind(app_id).fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+
+    let (status, v) = call(&app, "PUT", "/apps/secapp/secrets/DB_PASSWORD", Some(serde_json::json!({"value":"hunter2"}))).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert!(!v.to_string().contains("hunter2"));
+    assert!(v["rollout_deployment_id"].is_string(), "secret change should roll out");
+    let (status, v) = call(&app, "GET", "/apps/secapp/secrets", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(v.to_string().contains("DB_PASSWORD") && !v.to_string().contains("hunter2"));
+
+    // stored ciphertext is not the plaintext
+    let ct: Vec<u8> = sqlx::query_scalar("SELECT ciphertext FROM app_secrets WHERE app_id=$1").bind(app_id).fetch_one(&pool).await.unwrap();
+    assert!(!ct.windows(7).any(|w| w == b"hunter2"));
+
+    // projected into the deploy spec
+    let dep = services::deployments::get_deployment(&pool, dep_id).await.unwrap();
+    let spec = services::deployments::build_spec(&pool, "secapp", &dep, false).await.unwrap();
+    assert_eq!(spec.secrets.0.get("DB_PASSWORD").map(String::as_str), Some("hunter2"));
+
+    // rotate: new active key first, old key retained for unwrapping
+    let old_id: String = sqlx::query_scalar("SELECT master_key_id FROM app_data_keys WHERE app_id=$1").bind(app_id).fetch_one(&pool).await.unwrap();
+    write_keyring(&dir, &[[2u8; 32], [1u8; 32]]);
+    let (status, v) = call(&app, "POST", "/secrets/rotate-master-key", None).await;
+    assert_eq!(status, StatusCode::OK, "{v}");
+    assert_eq!(v["rewrapped"], 1);
+    let new_id: String = sqlx::query_scalar("SELECT master_key_id FROM app_data_keys WHERE app_id=$1").bind(app_id).fetch_one(&pool).await.unwrap();
+    assert_ne!(new_id, old_id);
+    assert_eq!(v["active_master_key_id"], new_id);
+    // the retired key can now be dropped from the keyring
+    write_keyring(&dir, &[[2u8; 32]]);
+    let spec = services::deployments::build_spec(&pool, "secapp", &dep, false).await.unwrap();
+    assert_eq!(spec.secrets.0.get("DB_PASSWORD").map(String::as_str), Some("hunter2"));
+
+    let (status, _) = call(&app, "DELETE", "/apps/secapp/secrets/DB_PASSWORD", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = call(&app, "DELETE", "/apps/secapp/secrets/DB_PASSWORD", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    std::env::remove_var("AETHER_MASTER_KEY_FILE");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn secrets_require_master_key() {
+    std::env::remove_var("AETHER_MASTER_KEY_FILE");
+    let state = test_state().await;
+    sqlx::query("INSERT INTO applications (name) VALUES ('secapp2')").execute(&state.db).await.unwrap();
+    let app = build_router(state);
+    let (status, _) = call(&app, "PUT", "/apps/secapp2/secrets/TOKEN", Some(serde_json::json!({"value":"x"}))).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let (status, _) = call(&app, "PUT", "/apps/secapp2/secrets/bad-name", Some(serde_json::json!({"value":"x"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,136 @@
+use control_plane::{build_router, test_support::{call, test_state}, services};
+use axum::http::StatusCode;
+
+/// Point `AETHER_MASTER_KEY_FILE` at a fresh one-key keyring (webhook secrets are sealed with the app data key).
+fn use_keyring() {
+    let dir = std::env::temp_dir().join(format!("aether-keys-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("master.keys"), hex::encode([7u8; 32])).unwrap();
+    std::env::set_var("AETHER_MASTER_KEY_FILE", dir.join("master.keys"));
+}
+
+type Received = std::sync::Arc<std::sync::Mutex<Vec<(axum::http::HeaderMap, Vec<u8>)>>>;
+
+/// Local receiver answering with `status`, recording every request.
+async fn receiver(status: StatusCode) -> (String, Received) {
+    let received: Received = Default::default();
+    let rec = received.clone();
+    let router = axum::Router::new().route("/hook", axum::routing::post(move |headers: axum::http::HeaderMap, body: axum::body::Bytes| {
+        let rec = rec.clone();
+        async move { rec.lock().unwrap().push((headers, body.to_vec())); status }
+    }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap(); });
+    (format!("http://{addr}/hook"), received)
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn webhook_deliveries_are_filtered_and_signed() {
+    use_keyring();
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('hookapp') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+    let (url, received) = receiver(StatusCode::OK).await;
+
+    let (status, _) = call(&app, "POST", "/apps/hookapp/webhooks", Some(serde_json::json!({"url": "ftp://x", "event_types": []}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = call(&app, "POST", "/apps/hookapp/webhooks", Some(serde_json::json!({"url": url, "event_types": ["deployment.exploded"]}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, hook) = call(&app, "POST", "/apps/hookapp/webhooks", Some(serde_json::json!({"url": url, "event_types": ["deployment.failed"], "secret": "0123456789abcdef"}))).await;
+    assert_eq!(status, StatusCode::CREATED, "{hook}");
+    assert_eq!(hook["secret"], "0123456789abcdef");
+    let (_, list) = call(&app, "GET", "/apps/hookapp/webhooks", None).await;
+    assert_eq!(list.as_array().unwrap().len(), 1);
+    assert!(list[0].get("secret").is_none(), "secrets are only returned on creation");
+    let stored: Vec<u8> = sqlx::query_scalar("SELECT secret_ciphertext FROM webhooks").fetch_one(&pool).await.unwrap();
+    assert!(!stored.windows(16).any(|w| w == b"0123456789abcdef"), "secret is stored encrypted");
+
+    let dep: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://a','pending') RETURNING id")
+        .bind(app_id).fetch_one(&pool).await.unwrap();
+    services::deployments::mark_running(&pool, dep).await; // not subscribed
+    services::deployments::mark_failed(&pool, dep, "crash").await;
+    let client = services::webhooks::http_client();
+    assert_eq!(services::webhooks::deliver_due(&pool, &client).await.unwrap(), 1);
+
+    let (headers, body) = received.lock().unwrap().pop().unwrap();
+    assert_eq!(headers["x-aether-event"], "deployment.failed");
+    assert_eq!(headers["x-aether-signature"].to_str().unwrap(), services::webhooks::sign("0123456789abcdef", &body));
+    let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(payload["app"], "hookapp");
+    assert_eq!(payload["data"]["deployment_id"], dep.to_string());
+    assert_eq!(payload["data"]["failure_reason"], "crash");
+
+    let (status, log) = call(&app, "GET", &format!("/apps/hookapp/webhooks/{}/deliveries", hook["id"].as_str().unwrap()), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(log.as_array().unwrap().len(), 1);
+    assert_eq!(log[0]["status"], "delivered");
+    assert_eq!(log[0]["attempts"], 1);
+    assert_eq!(log[0]["response_status"], 200);
+    assert_eq!(services::webhooks::deliver_due(&pool, &client).await.unwrap(), 0, "delivered once");
+
+    let (status, _) = call(&app, "DELETE", &format!("/apps/hookapp/webhooks/{}", hook["id"].as_str().unwrap()), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = call(&app, "GET", &format!("/apps/hookapp/webhooks/{}/deliveries", hook["id"].as_str().unwrap()), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn failing_webhook_is_retried_with_backoff_then_failed() {
+    use_keyring();
+    std::env::set_var("AETHER_WEBHOOK_MAX_ATTEMPTS", "2");
+    std::env::set_var("AETHER_WEBHOOK_BACKOFF_BASE_SECS", "30");
+    let state = test_state().await;
+    let pool = state.db.clone();
+    let app_id: uuid::Uuid = sqlx::query_scalar("INSERT INTO applications (name) VALUES ('hookfail') RETURNING id").fetch_one(&pool).await.unwrap();
+    let app = build_router(state);
+    let (url, received) = receiver(StatusCode::INTERNAL_SERVER_ERROR).await;
+    let (_, hook) = call(&app, "POST", "/apps/hookfail/webhooks", Some(serde_json::json!({"url": url}))).await;
+    assert_eq!(hook["secret"].as_str().unwrap().len(), 64, "generated secret");
+    let dep: uuid::Uuid = sqlx::query_scalar("INSERT INTO deployments (app_id, artifact_url, status) VALUES ($1,'file://a','pending') RETURNING id")
+        .bind(app_id).fetch_one(&pool).await.unwrap();
+    services::deployments::mark_running(&pool, dep).await;
+
+    let client = services::webhooks::http_client();
+    assert_eq!(services::webhooks::deliver_due(&pool, &client).await.unwrap(), 1);
+    let (status, attempts, wait, error): (String, i32, f64, Option<String>) = sqlx::query_as(
+        "SELECT status, attempts, EXTRACT(EPOCH FROM next_attempt_at - now())::float8, last_error FROM webhook_deliveries")
+        .fetch_one(&pool).await.unwrap();
+    assert_eq!((status.as_str(), attempts), ("pending", 1));
+    assert!(wait > 20.0 && wait <= 30.0, "backoff {wait}");
+    assert!(error.unwrap().contains("500"));
+    assert_eq!(services::webhooks::deliver_due(&pool, &client).await.unwrap(), 0, "not due yet");
+
+    sqlx::query("UPDATE webhook_deliveries SET next_attempt_at=now()").execute(&pool).await.unwrap();
+    assert_eq!(services::webhooks::deliver_due(&pool, &client).await.unwrap(), 1);
+    let (_, log) = call(&app, "GET", &format!("/apps/hookfail/webhooks/{}/deliveries", hook["id"].as_str().unwrap()), None).await;
+    assert_eq!(log[0]["status"], "failed");
+    assert_eq!(log[0]["attempts"], 2);
+    assert_eq!(log[0]["response_status"], 500);
+    assert_eq!(received.lock().unwrap().len(), 2);
+    std::env::remove_var("AETHER_WEBHOOK_MAX_ATTEMPTS");
+    std::env::remove_var("AETHER_WEBHOOK_BACKOFF_BASE_SECS");
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn webhooks_require_master_key() {
+    std::env::remove_var("AETHER_MASTER_KEY_FILE");
+    let state = test_state().await;
+    sqlx::query("INSERT INTO applications (name) VALUES ('hooknokey')").execute(&state.db).await.unwrap();
+    let app = build_router(state);
+    let (status, _) = call(&app, "POST", "/apps/hooknokey/webhooks", Some(serde_json::json!({"url": "http://127.0.0.1:9/hook"}))).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[test]
+#[serial_test::serial]
+fn backoff_doubles_up_to_the_cap() {
+    assert_eq!(services::webhooks::backoff_secs(1), 10);
+    assert_eq!(services::webhooks::backoff_secs(2), 20);
+    assert_eq!(services::webhooks::backoff_secs(4), 80);
+    assert_eq!(services::webhooks::backoff_secs(30), 3600);
+}